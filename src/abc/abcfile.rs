@@ -1,6 +1,13 @@
 use super::info::*;
+use super::parser::Instruction;
+#[cfg(feature = "rayon")]
+use super::view::skip_body;
 use crate::error::{RabcError, Result};
+use crate::io::{Read, Seek};
 use crate::{StreamReader, StreamWriter};
+use alloc::vec::Vec;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 
 #[derive(Clone, Debug, PartialEq, Default)]
 pub struct AbcVersion {
@@ -45,6 +52,20 @@ impl AbcFile {
         })
     }
 
+    /// Read the abc content from any reader, such as a file.
+    /// The method bodies are always read sequentially.
+    pub fn read_from<R: Read + Seek>(stream: &mut StreamReader<'_, R>) -> Result<Self> {
+        let version = AbcVersion::read(stream)?;
+        let cpool = ConstantPool::read(stream)?;
+        let abc = Abc::read_from(stream)?;
+
+        Ok(Self {
+            version,
+            cpool,
+            abc,
+        })
+    }
+
     /// Write the abc content to a stream
     pub fn write(&self, stream: &mut StreamWriter) -> Result<()> {
         self.version.write(stream)?;
@@ -59,6 +80,20 @@ impl Abc {
         Self::default()
     }
     pub fn read(stream: &mut StreamReader) -> Result<Self> {
+        let mut abc = Self::read_infos(stream)?;
+        Self::read_bodies(stream, &mut abc.methods)?;
+        Ok(abc)
+    }
+
+    /// Read from any reader, the method bodies being read sequentially
+    pub fn read_from<R: Read + Seek>(stream: &mut StreamReader<'_, R>) -> Result<Self> {
+        let mut abc = Self::read_infos(stream)?;
+        Self::read_bodies_sequential(stream, &mut abc.methods)?;
+        Ok(abc)
+    }
+
+    /// Read everything but the method bodies
+    fn read_infos<R: Read + Seek>(stream: &mut StreamReader<'_, R>) -> Result<Self> {
         let mut count = stream.read_u30()?;
         let mut methods = Vec::with_capacity(count as usize);
         for _ in 0..count {
@@ -86,7 +121,24 @@ impl Abc {
             scripts.push(Script::read(stream)?);
         }
 
-        count = stream.read_u30()?;
+        Ok(Self {
+            classes,
+            metadatas,
+            methods,
+            scripts,
+        })
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    fn read_bodies(stream: &mut StreamReader, methods: &mut [Method]) -> Result<()> {
+        Self::read_bodies_sequential(stream, methods)
+    }
+
+    fn read_bodies_sequential<R: Read + Seek>(
+        stream: &mut StreamReader<'_, R>,
+        methods: &mut [Method],
+    ) -> Result<()> {
+        let count = stream.read_u30()?;
         for _ in 0..count {
             let index = stream.read_u30()?;
             let method = methods
@@ -94,13 +146,46 @@ impl Abc {
                 .ok_or(RabcError::MethodOutOfBound(index))?;
             method.read_body(stream)?;
         }
+        Ok(())
+    }
 
-        Ok(Self {
-            classes,
-            metadatas,
-            methods,
-            scripts,
-        })
+    /// Find where each method body starts, then parse them in parallel
+    #[cfg(feature = "rayon")]
+    fn read_bodies(stream: &mut StreamReader, methods: &mut [Method]) -> Result<()> {
+        let count = stream.read_u30()?;
+        let mut bodies = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let index = stream.read_u30()?;
+            if index as usize >= methods.len() {
+                return Err(RabcError::MethodOutOfBound(index));
+            }
+            bodies.push((index, stream.pos()));
+            skip_body(stream)?;
+        }
+
+        let data: &[u8] = stream.buffer.get_ref();
+        let bodies = bodies
+            .into_par_iter()
+            .map(|(index, pos)| {
+                let mut stream = StreamReader::new(data);
+                stream.skip(pos)?;
+                let mut body = Method::new();
+                body.read_body(&mut stream)?;
+                Ok((index, body))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        for (index, body) in bodies {
+            let method = &mut methods[index as usize];
+            method.max_stack = body.max_stack;
+            method.local_count = body.local_count;
+            method.init_scope_depth = body.init_scope_depth;
+            method.max_scope_depth = body.max_scope_depth;
+            method.code = body.code;
+            method.exceptions = body.exceptions;
+            method.traits = body.traits;
+        }
+        Ok(())
     }
 
     pub fn write(&self, stream: &mut StreamWriter) -> Result<()> {
@@ -139,6 +224,16 @@ impl Abc {
         Ok(())
     }
 
+    /// Parse the instructions of every method, in parallel with the `rayon` feature.
+    /// Methods without a body have no instructions.
+    pub fn parse_methods(&self) -> Result<Vec<Vec<Instruction>>> {
+        #[cfg(feature = "rayon")]
+        let methods = self.methods.par_iter();
+        #[cfg(not(feature = "rayon"))]
+        let methods = self.methods.iter();
+        methods.map(Method::parse).collect()
+    }
+
     #[inline]
     pub fn get_method(&self, index: u32) -> Result<&Method> {
         self.methods
@@ -182,7 +277,7 @@ impl Abc {
 }
 
 impl AbcVersion {
-    pub fn read(stream: &mut StreamReader) -> Result<Self> {
+    pub fn read<R: Read + Seek>(stream: &mut StreamReader<'_, R>) -> Result<Self> {
         Ok(Self {
             minor: stream.read_u16()?,
             major: stream.read_u16()?,
@@ -195,3 +290,47 @@ impl AbcVersion {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abc::parser::Op;
+
+    #[test]
+    fn read_bodies_and_parse_methods() {
+        let mut abc = Abc::new();
+        abc.methods = vec![
+            Method {
+                code: vec![0xD0, 0x30, 0x47],
+                max_stack: 1,
+                ..Default::default()
+            },
+            Method::new(),
+            Method {
+                code: vec![0x24, 0x05, 0x48],
+                exceptions: vec![Exception {
+                    to: 2,
+                    target: 2,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        ];
+
+        let mut stream = StreamWriter::default();
+        abc.write(&mut stream).unwrap();
+        let read = Abc::read(&mut StreamReader::new(stream.buffer())).unwrap();
+        assert_eq!(read, abc);
+        let data = crate::io::Cursor::new(stream.buffer().clone());
+        assert_eq!(
+            Abc::read_from(&mut StreamReader::from_reader(data)).unwrap(),
+            abc
+        );
+
+        let instructions = read.parse_methods().unwrap();
+        assert_eq!(instructions.len(), 3);
+        assert_eq!(instructions[0].len(), 3);
+        assert!(instructions[1].is_empty());
+        assert!(matches!(instructions[2][1].op, Op::ReturnValue()));
+    }
+}
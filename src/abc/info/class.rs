@@ -1,5 +1,10 @@
 use super::Trait;
-use crate::{error::Result, StreamReader, StreamWriter};
+use crate::{
+    error::Result,
+    io::{Read, Seek},
+    StreamReader, StreamWriter,
+};
+use alloc::vec::Vec;
 use bitflags::bitflags;
 
 bitflags! {
@@ -27,7 +32,7 @@ pub struct Class {
 
 impl Class {
     #[inline]
-    pub fn read_instance(stream: &mut StreamReader) -> Result<Self> {
+    pub fn read_instance<R: Read + Seek>(stream: &mut StreamReader<'_, R>) -> Result<Self> {
         let name = stream.read_u30()?;
         let super_name = stream.read_u30()?;
         let flags = ClassFlag::from_bits_retain(stream.read_u8()?);
@@ -63,7 +68,7 @@ impl Class {
         })
     }
 
-    pub fn read(&mut self, stream: &mut StreamReader) -> Result<()> {
+    pub fn read<R: Read + Seek>(&mut self, stream: &mut StreamReader<'_, R>) -> Result<()> {
         self.cinit = stream.read_u30()?;
         let count = stream.read_u30()?;
 
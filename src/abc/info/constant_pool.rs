@@ -1,8 +1,13 @@
 use super::{Class, Multiname, Namespace};
 use crate::{
     error::{RabcError, Result},
+    io::{Read, Seek},
     StreamReader, StreamWriter,
 };
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
 
 #[derive(Clone, Debug, PartialEq, Default)]
 pub struct ConstantPool {
@@ -20,7 +25,7 @@ impl ConstantPool {
         Self::default()
     }
 
-    pub fn read(stream: &mut StreamReader) -> Result<Self> {
+    pub fn read<R: Read + Seek>(stream: &mut StreamReader<'_, R>) -> Result<Self> {
         let mut capacity = stream.read_u30()?.saturating_sub(1) + 1;
         let mut integers = Vec::with_capacity(capacity as usize);
         integers.push(0);
@@ -1,4 +1,8 @@
-use crate::{error::Result, StreamReader, StreamWriter};
+use crate::{
+    error::Result,
+    io::{Read, Seek},
+    StreamReader, StreamWriter,
+};
 
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct Exception {
@@ -14,7 +18,7 @@ impl Exception {
         Self::default()
     }
 
-    pub fn read(stream: &mut StreamReader) -> Result<Self> {
+    pub fn read<R: Read + Seek>(stream: &mut StreamReader<'_, R>) -> Result<Self> {
         Ok(Self {
             from: stream.read_u30()?,
             to: stream.read_u30()?,
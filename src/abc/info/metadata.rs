@@ -1,4 +1,9 @@
-use crate::{error::Result, StreamReader, StreamWriter};
+use crate::{
+    error::Result,
+    io::{Read, Seek},
+    StreamReader, StreamWriter,
+};
+use alloc::vec::Vec;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct MetadataItem {
@@ -19,7 +24,7 @@ impl MetadataItem {
 }
 
 impl Metadata {
-    pub fn read(stream: &mut StreamReader) -> Result<Self> {
+    pub fn read<R: Read + Seek>(stream: &mut StreamReader<'_, R>) -> Result<Self> {
         let name = stream.read_u30()?;
         let count = stream.read_u30()?;
         let mut items = Vec::with_capacity(count as usize);
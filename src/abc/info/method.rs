@@ -1,5 +1,10 @@
 use super::{Exception, Trait};
-use crate::{error::Result, StreamReader, StreamWriter};
+use crate::{
+    error::{RabcError, Result},
+    io::{Read, Seek},
+    StreamReader, StreamWriter,
+};
+use alloc::vec::Vec;
 use bitflags::bitflags;
 
 bitflags! {
@@ -32,7 +37,8 @@ pub struct Method {
     pub name: u32,
     pub flags: MethodFlag,
     pub optional: Vec<Option>,
-    pub param_names: Vec<String>,
+    /// Name of each parameter, as an index in the string pool
+    pub param_names: Vec<u32>,
     pub params: Vec<u32>,
 
     pub max_stack: u32,
@@ -48,7 +54,7 @@ impl Method {
     pub fn new() -> Self {
         Self::default()
     }
-    pub fn read(stream: &mut StreamReader) -> Result<Self> {
+    pub fn read<R: Read + Seek>(stream: &mut StreamReader<'_, R>) -> Result<Self> {
         let mut count = stream.read_u30()?;
         let mut params = Vec::with_capacity(count as usize);
         let return_type = stream.read_u30()?;
@@ -73,12 +79,12 @@ impl Method {
                 });
             }
         }
+        // There is a name for each parameter, without a count
         if flags.contains(MethodFlag::HAS_PARAM_NAMES) {
-            count = stream.read_u30()?;
-            param_names.reserve_exact(count as usize);
+            param_names.reserve_exact(params.len());
 
-            for _ in 0..count {
-                param_names.push(stream.read_string()?);
+            for _ in 0..params.len() {
+                param_names.push(stream.read_u30()?);
             }
         }
 
@@ -99,7 +105,7 @@ impl Method {
         })
     }
 
-    pub fn read_body(&mut self, stream: &mut StreamReader) -> Result<()> {
+    pub fn read_body<R: Read + Seek>(&mut self, stream: &mut StreamReader<'_, R>) -> Result<()> {
         self.max_stack = stream.read_u30()?;
         self.local_count = stream.read_u30()?;
         self.init_scope_depth = stream.read_u30()?;
@@ -131,11 +137,13 @@ impl Method {
         }
         stream.write_u30(self.name)?;
 
-        let has_optional = !self.optional.is_empty();
-        let has_param_names = !self.param_names.is_empty();
-        // self.flags.set(MethodFlag::HAS_OPTIONAL, has_optional);
-        // self.flags.set(MethodFlag::HAS_PARAM_NAMES, has_param_names);
-        stream.write_u8(self.flags.bits())?;
+        // Keep the stored flags, even if there are no values
+        let has_optional = self.has_optional() || !self.optional.is_empty();
+        let has_param_names = self.has_param_names() || !self.param_names.is_empty();
+        let mut flags = self.flags;
+        flags.set(MethodFlag::HAS_OPTIONAL, has_optional);
+        flags.set(MethodFlag::HAS_PARAM_NAMES, has_param_names);
+        stream.write_u8(flags.bits())?;
 
         if has_optional {
             stream.write_u30(self.optional.len() as u32)?;
@@ -145,9 +153,11 @@ impl Method {
             }
         }
         if has_param_names {
-            stream.write_u30(self.param_names.len() as u32)?;
+            if self.param_names.len() != self.params.len() {
+                return Err(RabcError::ValueOutOfRange("param names count"));
+            }
             for param in &self.param_names {
-                stream.write_string(param)?;
+                stream.write_u30(*param)?;
             }
         }
         Ok(())
@@ -186,3 +196,55 @@ impl Method {
         self.flags.contains(MethodFlag::NEED_REST)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keep_flags_without_values() {
+        let method = Method {
+            flags: MethodFlag::HAS_OPTIONAL | MethodFlag::HAS_PARAM_NAMES,
+            ..Default::default()
+        };
+        let mut stream = StreamWriter::default();
+        method.write(&mut stream).unwrap();
+        // No params, return type, name, flags and the empty optional count
+        assert_eq!(*stream.buffer(), [0, 0, 0, 0x88, 0]);
+        let read = Method::read(&mut StreamReader::new(stream.buffer())).unwrap();
+        assert_eq!(read, method);
+
+        // The flags are set when there are values
+        let method = Method {
+            params: vec![1],
+            optional: vec![Option { value: 2, kind: 3 }],
+            ..Default::default()
+        };
+        let mut stream = StreamWriter::default();
+        method.write(&mut stream).unwrap();
+        let read = Method::read(&mut StreamReader::new(stream.buffer())).unwrap();
+        assert_eq!(read.flags, MethodFlag::HAS_OPTIONAL);
+        assert_eq!(read.optional, method.optional);
+    }
+
+    #[test]
+    fn param_names() {
+        let method = Method {
+            params: vec![0, 3],
+            param_names: vec![1, 2],
+            ..Default::default()
+        };
+        let mut stream = StreamWriter::default();
+        method.write(&mut stream).unwrap();
+        // Two params, return type, their types, name, flags and a string index for each param
+        assert_eq!(*stream.buffer(), [2, 0, 0, 3, 0, 0x80, 1, 2]);
+        let read = Method::read(&mut StreamReader::new(stream.buffer())).unwrap();
+        assert_eq!(read.param_names, method.param_names);
+
+        let method = Method {
+            param_names: vec![1],
+            ..Default::default()
+        };
+        assert!(method.write(&mut StreamWriter::default()).is_err());
+    }
+}
@@ -1,7 +1,9 @@
 use crate::{
     error::{RabcError, Result},
+    io::{Read, Seek},
     StreamReader, StreamWriter,
 };
+use alloc::vec::Vec;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum Multiname {
@@ -43,7 +45,7 @@ pub struct Typename {
 }
 
 impl QName {
-    pub fn read(stream: &mut StreamReader) -> Result<Self> {
+    pub fn read<R: Read + Seek>(stream: &mut StreamReader<'_, R>) -> Result<Self> {
         Ok(Self {
             ns: stream.read_u30()?,
             name: stream.read_u30()?,
@@ -57,7 +59,7 @@ impl QName {
     }
 }
 impl RTQName {
-    pub fn read(stream: &mut StreamReader) -> Result<Self> {
+    pub fn read<R: Read + Seek>(stream: &mut StreamReader<'_, R>) -> Result<Self> {
         Ok(Self {
             name: stream.read_u30()?,
         })
@@ -69,7 +71,7 @@ impl RTQName {
     }
 }
 impl Multi {
-    pub fn read(stream: &mut StreamReader) -> Result<Self> {
+    pub fn read<R: Read + Seek>(stream: &mut StreamReader<'_, R>) -> Result<Self> {
         Ok(Self {
             name: stream.read_u30()?,
             ns_set: stream.read_u30()?,
@@ -83,7 +85,7 @@ impl Multi {
     }
 }
 impl MultiL {
-    pub fn read(stream: &mut StreamReader) -> Result<Self> {
+    pub fn read<R: Read + Seek>(stream: &mut StreamReader<'_, R>) -> Result<Self> {
         Ok(Self {
             ns_set: stream.read_u30()?,
         })
@@ -95,7 +97,7 @@ impl MultiL {
     }
 }
 impl Typename {
-    pub fn read(stream: &mut StreamReader) -> Result<Self> {
+    pub fn read<R: Read + Seek>(stream: &mut StreamReader<'_, R>) -> Result<Self> {
         let qname = stream.read_u30()?;
         let count = stream.read_u30()?;
         let mut types = Vec::with_capacity(count as usize);
@@ -137,7 +139,7 @@ impl Multiname {
         }
     }
 
-    pub fn read(stream: &mut StreamReader) -> Result<Self> {
+    pub fn read<R: Read + Seek>(stream: &mut StreamReader<'_, R>) -> Result<Self> {
         match stream.read_u8()? {
             0x07 => Ok(Self::QName(QName::read(stream)?)),
             0x0D => Ok(Self::QNameA(QName::read(stream)?)),
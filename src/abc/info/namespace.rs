@@ -1,5 +1,6 @@
 use crate::{
     error::{RabcError, Result},
+    io::{Read, Seek},
     StreamReader, StreamWriter,
 };
 
@@ -27,7 +28,7 @@ impl Namespace {
         Self::default()
     }
 
-    pub fn read(stream: &mut StreamReader) -> Result<Self> {
+    pub fn read<R: Read + Seek>(stream: &mut StreamReader<'_, R>) -> Result<Self> {
         Ok(Self {
             kind: NamespaceKind::from(stream.read_u30()?)?,
             name: stream.read_u30()?,
@@ -1,14 +1,19 @@
 use super::Trait;
-use crate::{error::Result, StreamReader, StreamWriter};
+use crate::{
+    error::Result,
+    io::{Read, Seek},
+    StreamReader, StreamWriter,
+};
+use alloc::vec::Vec;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Script {
-    init: u32,
-    traits: Vec<Trait>,
+    pub init: u32,
+    pub traits: Vec<Trait>,
 }
 
 impl Script {
-    pub fn read(stream: &mut StreamReader) -> Result<Self> {
+    pub fn read<R: Read + Seek>(stream: &mut StreamReader<'_, R>) -> Result<Self> {
         let init = stream.read_u30()?;
         let count = stream.read_u30()?;
         let mut traits = Vec::with_capacity(count as usize);
@@ -1,7 +1,9 @@
 use crate::{
     error::{RabcError, Result},
+    io::{Read, Seek},
     StreamReader, StreamWriter,
 };
+use alloc::vec::Vec;
 use bitflags::bitflags;
 
 bitflags! {
@@ -81,7 +83,7 @@ impl Trait {
     }
 
     #[inline]
-    pub fn read(stream: &mut StreamReader) -> Result<Self> {
+    pub fn read<R: Read + Seek>(stream: &mut StreamReader<'_, R>) -> Result<Self> {
         let name = stream.read_u30()?;
         let kkind = stream.read_u8()?;
         let attr = TraitAttr::from_bits_retain(kkind >> 4);
@@ -98,7 +100,10 @@ impl Trait {
     }
 
     #[inline]
-    pub fn read_metadata(stream: &mut StreamReader, attr: &TraitAttr) -> Result<Vec<u32>> {
+    pub fn read_metadata<R: Read + Seek>(
+        stream: &mut StreamReader<'_, R>,
+        attr: &TraitAttr,
+    ) -> Result<Vec<u32>> {
         if !attr.contains(TraitAttr::METADATA) {
             return Ok(Vec::new());
         }
@@ -159,7 +164,11 @@ impl From<&Trait> for u8 {
 }
 
 pub trait ITrait {
-    fn read(stream: &mut StreamReader, name: u32, attr: TraitAttr) -> Result<Self>
+    fn read<R: Read + Seek>(
+        stream: &mut StreamReader<'_, R>,
+        name: u32,
+        attr: TraitAttr,
+    ) -> Result<Self>
     where
         Self: Sized;
 
@@ -172,7 +181,11 @@ pub trait ITrait {
 
 impl ITrait for SlotTrait {
     #[inline]
-    fn read(stream: &mut StreamReader, name: u32, attr: TraitAttr) -> Result<Self> {
+    fn read<R: Read + Seek>(
+        stream: &mut StreamReader<'_, R>,
+        name: u32,
+        attr: TraitAttr,
+    ) -> Result<Self> {
         let slot_id = stream.read_u30()?;
         let slot_type = stream.read_u30()?;
         let index = stream.read_u30()?;
@@ -215,7 +228,11 @@ impl ITrait for SlotTrait {
 
 impl ITrait for IndexTrait {
     #[inline]
-    fn read(stream: &mut StreamReader, name: u32, attr: TraitAttr) -> Result<Self> {
+    fn read<R: Read + Seek>(
+        stream: &mut StreamReader<'_, R>,
+        name: u32,
+        attr: TraitAttr,
+    ) -> Result<Self> {
         let slot_id = stream.read_u30()?;
         let index = stream.read_u30()?;
         let metadatas = Trait::read_metadata(stream, &attr)?;
@@ -1,6 +1,9 @@
 mod abcfile;
 mod info;
 pub mod parser;
+mod strip;
+mod view;
 
 pub use abcfile::{Abc, AbcFile, AbcVersion};
 pub use info::*;
+pub use view::AbcView;
@@ -1,4 +1,5 @@
 use super::{opargs::*, opcodes::OpCode};
+use alloc::vec::Vec;
 
 #[derive(Clone, Debug)]
 pub struct Instruction {
@@ -15,8 +16,8 @@ trait U30Trait {
 }
 impl U30Trait for u32 {
     fn u30size(&self) -> u32 {
-        let bits = (u32::BITS - self.leading_zeros()) as f64;
-        (bits / 7.0).ceil().max(1.0) as Self
+        let bits = u32::BITS - self.leading_zeros();
+        bits.div_ceil(7).max(1)
     }
 }
 
@@ -1,4 +1,5 @@
 use super::{opmatch::OpMatch, Instruction, Op, OpCode};
+use alloc::vec::Vec;
 
 #[derive(Clone, Debug)]
 pub struct InsIterator<'a> {
@@ -123,11 +124,11 @@ impl<'a> InsIterator<'a> {
 }
 
 pub trait InsIter {
-    fn iter_prog(&self) -> InsIterator;
+    fn iter_prog(&self) -> InsIterator<'_>;
 }
 
 impl InsIter for Vec<Instruction> {
-    fn iter_prog(&self) -> InsIterator {
+    fn iter_prog(&self) -> InsIterator<'_> {
         InsIterator {
             instructions: self,
             cursor: 0,
@@ -135,7 +136,7 @@ impl InsIter for Vec<Instruction> {
     }
 }
 impl InsIter for &[Instruction] {
-    fn iter_prog(&self) -> InsIterator {
+    fn iter_prog(&self) -> InsIterator<'_> {
         InsIterator {
             instructions: self,
             cursor: 0,
@@ -143,7 +144,7 @@ impl InsIter for &[Instruction] {
     }
 }
 impl InsIter for Option<&[Instruction]> {
-    fn iter_prog(&self) -> InsIterator {
+    fn iter_prog(&self) -> InsIterator<'_> {
         InsIterator {
             instructions: self.unwrap_or(&[]),
             cursor: 0,
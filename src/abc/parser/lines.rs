@@ -0,0 +1,132 @@
+use super::{Instruction, Op};
+use crate::{abc::Method, error::Result};
+use alloc::vec::Vec;
+
+/// Source location of the instructions starting at `addr`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LineEntry {
+    pub addr: u32,
+    /// Index of the file name in the string pool, 0 if no debugfile instruction was found
+    pub file: u32,
+    pub line: u32,
+}
+
+/// Mapping of a method's instructions to their source location, built from the debugfile and
+/// debugline instructions
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct LineTable {
+    /// Entries sorted by address
+    pub entries: Vec<LineEntry>,
+}
+
+impl LineTable {
+    pub fn from_instructions(instructions: &[Instruction]) -> Self {
+        let mut entries: Vec<LineEntry> = Vec::new();
+        let mut file = 0;
+        // Whether only debug instructions were seen since the last entry
+        let mut consecutive = false;
+        for ins in instructions {
+            match &ins.op {
+                Op::DebugFile(arg) => file = arg.filename,
+                Op::DebugLine(arg) | Op::BkptLine(arg) => {
+                    // Consecutive debuglines only keep the last one, starting at the first
+                    match entries.last_mut() {
+                        Some(last) if consecutive => {
+                            last.file = file;
+                            last.line = arg.line;
+                        }
+                        _ => entries.push(LineEntry {
+                            addr: ins.addr,
+                            file,
+                            line: arg.line,
+                        }),
+                    }
+                    consecutive = true;
+                }
+                _ => consecutive = false,
+            }
+        }
+        Self { entries }
+    }
+
+    /// Get the source location of the instruction at the given address
+    pub fn lookup(&self, addr: u32) -> Option<&LineEntry> {
+        let index = self.entries.partition_point(|e| e.addr <= addr);
+        index.checked_sub(1).map(|i| &self.entries[i])
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl Method {
+    /// Build the line table of the method, which is empty if it was compiled without debug
+    /// information
+    pub fn line_table(&self) -> Result<LineTable> {
+        Ok(LineTable::from_instructions(&self.parse()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_table() {
+        let method = Method {
+            #[rustfmt::skip]
+            code: vec![
+                0xF1, 0x03, // debugfile 3
+                0xF0, 0x0A, // debugline 10
+                0x02, // nop
+                0xF0, 0x0C, // debugline 12
+                0x02, // nop
+                0x47, // returnvoid
+            ],
+            ..Default::default()
+        };
+        let table = method.line_table().unwrap();
+        assert_eq!(table.entries.len(), 2);
+        assert_eq!(table.lookup(0), None);
+        assert_eq!(table.lookup(4).unwrap().line, 10);
+        assert_eq!(table.lookup(4).unwrap().file, 3);
+        assert_eq!(table.lookup(8).unwrap().line, 12);
+        assert!(Method::default().line_table().unwrap().is_empty());
+    }
+
+    #[test]
+    fn consecutive_debuglines() {
+        let method = Method {
+            #[rustfmt::skip]
+            code: vec![
+                0xF0, 0x0A, // debugline 10
+                0xF1, 0x03, // debugfile 3
+                0xF0, 0x0B, // debugline 11
+                0x02, // nop
+                0xF0, 0x0C, // debugline 12
+                0xF2, 0x0D, // bkptline 13
+                0x47, // returnvoid
+            ],
+            ..Default::default()
+        };
+        let table = method.line_table().unwrap();
+        assert_eq!(
+            table.entries,
+            vec![
+                LineEntry {
+                    addr: 0,
+                    file: 3,
+                    line: 11
+                },
+                LineEntry {
+                    addr: 7,
+                    file: 3,
+                    line: 13
+                },
+            ]
+        );
+        assert_eq!(table.lookup(6).unwrap().line, 11);
+        assert_eq!(table.lookup(11).unwrap().line, 13);
+    }
+}
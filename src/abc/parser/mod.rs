@@ -1,5 +1,6 @@
 mod instruction;
 mod iter;
+mod lines;
 mod opcodes;
 mod parse;
 
@@ -8,4 +9,5 @@ pub mod opmatch;
 
 pub use instruction::{Instruction, Op};
 pub use iter::{InsIter, InsIterator};
+pub use lines::{LineEntry, LineTable};
 pub use opcodes::OpCode;
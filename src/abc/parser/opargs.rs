@@ -1,4 +1,5 @@
 use crate::{error::Result, StreamReader, StreamWriter};
+use alloc::{boxed::Box, vec::Vec};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ArgsCountArg {
@@ -1,6 +1,7 @@
+use alloc::string::{String, ToString};
+use core::fmt::Display;
 use num_derive::{FromPrimitive, ToPrimitive};
 use num_traits::FromPrimitive;
-use std::fmt::Display;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, FromPrimitive, ToPrimitive, Hash)]
 pub enum OpCode {
@@ -366,7 +367,7 @@ impl OpCode {
 }
 
 impl Display for OpCode {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.write_str(&self.name())
     }
 }
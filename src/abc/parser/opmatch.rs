@@ -1,13 +1,14 @@
-use std::ops::BitOr;
+use core::ops::BitOr;
 
 use super::{InsIterator, OpCode};
+use alloc::{boxed::Box, vec::Vec};
 
 #[derive(Debug)]
 pub struct AnyOp(Vec<Box<dyn OpMatch>>);
 #[derive(Debug, Clone, Copy)]
 pub struct OpSeq<const S: usize>(pub [OpCode; S]);
 
-pub trait OpMatch: std::fmt::Debug {
+pub trait OpMatch: core::fmt::Debug {
     fn matches(&self, prog: &InsIterator) -> Option<usize>;
 }
 
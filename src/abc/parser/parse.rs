@@ -8,17 +8,18 @@ use crate::{
     error::{RabcError, Result},
     StreamReader, StreamWriter,
 };
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
 use num_traits::cast::ToPrimitive;
-use std::collections::HashMap;
 
 impl Method {
     pub fn parse(&self) -> Result<Vec<Instruction>> {
         // Instructions takes on average 2.5 bytes, which can be used to pre-allocate containers
-        let size_hint = self.code.len() / 2;  // use a bigger ratio of 2
+        let size_hint = self.code.len() / 2; // use a bigger ratio of 2
         let mut stream = StreamReader::new(&self.code);
         let mut instructions: Vec<Instruction> = Vec::with_capacity(size_hint);
-        let mut targets: HashMap<u32, Vec<u32>> = HashMap::new();
-        let mut addr2idx = HashMap::with_capacity(size_hint);
+        let mut targets: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+        let mut addr2idx = BTreeMap::new();
         while !stream.finished() {
             instructions.push(parse(&mut stream)?);
             let ins = instructions.last().unwrap();
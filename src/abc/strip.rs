@@ -0,0 +1,254 @@
+use super::{
+    parser::{Instruction, Op},
+    AbcFile, Method, MethodFlag, Multiname, Trait,
+};
+use crate::error::Result;
+use alloc::vec::Vec;
+
+/// Constant kind of strings, used by optional parameters and slot traits
+const CONSTANT_UTF8: u8 = 0x01;
+
+impl AbcFile {
+    /// Remove the debug information: the debug instructions, the parameter names, and the strings
+    /// that were only used by them, such as source paths and local variable names.
+    pub fn strip_debug_info(&mut self) -> Result<()> {
+        for method in &mut self.abc.methods {
+            method.param_names.clear();
+            method.flags.remove(MethodFlag::HAS_PARAM_NAMES);
+            if method.has_body() {
+                let instructions: Vec<Instruction> = method
+                    .parse()?
+                    .into_iter()
+                    .filter(|ins| {
+                        !matches!(
+                            ins.op,
+                            Op::Debug(_) | Op::DebugFile(_) | Op::DebugLine(_) | Op::BkptLine(_)
+                        )
+                    })
+                    .collect();
+                method.save_relocated_instructions(&instructions)?;
+            }
+        }
+        self.compact_strings()
+    }
+
+    /// Remove the strings that aren't referenced anymore and update the references to the others
+    pub fn compact_strings(&mut self) -> Result<()> {
+        let mut used = vec![false; self.cpool.strings.len()];
+        if let Some(empty) = used.first_mut() {
+            *empty = true;
+        }
+        self.visit_strings(&mut |index| {
+            if let Some(used) = used.get_mut(*index as usize) {
+                *used = true;
+            }
+        })?;
+
+        let mut remap = Vec::with_capacity(used.len());
+        let mut next = 0;
+        for is_used in &used {
+            remap.push(next);
+            if *is_used {
+                next += 1;
+            }
+        }
+        let mut is_used = used.iter();
+        self.cpool.strings.retain(|_| *is_used.next().unwrap());
+        self.visit_strings(&mut |index| {
+            if let Some(new) = remap.get(*index as usize) {
+                *index = *new;
+            }
+        })
+    }
+
+    /// Call the closure with every reference to a string of the constant pool
+    fn visit_strings(&mut self, f: &mut impl FnMut(&mut u32)) -> Result<()> {
+        for ns in &mut self.cpool.namespaces {
+            f(&mut ns.name);
+        }
+        for mn in &mut self.cpool.multinames {
+            match mn {
+                Multiname::QName(mn) | Multiname::QNameA(mn) => f(&mut mn.name),
+                Multiname::RTQName(mn) | Multiname::RTQNameA(mn) => f(&mut mn.name),
+                Multiname::Multiname(mn) | Multiname::MultinameA(mn) => f(&mut mn.name),
+                _ => {}
+            }
+        }
+        for metadata in &mut self.abc.metadatas {
+            f(&mut metadata.name);
+            for item in &mut metadata.items {
+                f(&mut item.key);
+                f(&mut item.value);
+            }
+        }
+        for class in &mut self.abc.classes {
+            visit_trait_strings(&mut class.itraits, f);
+            visit_trait_strings(&mut class.ctraits, f);
+        }
+        for script in &mut self.abc.scripts {
+            visit_trait_strings(&mut script.traits, f);
+        }
+        for method in &mut self.abc.methods {
+            f(&mut method.name);
+            for name in &mut method.param_names {
+                f(name);
+            }
+            for option in &mut method.optional {
+                if option.kind == CONSTANT_UTF8 {
+                    f(&mut option.value);
+                }
+            }
+            visit_trait_strings(&mut method.traits, f);
+            if method.has_body() {
+                visit_code_strings(method, f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn visit_trait_strings(traits: &mut [Trait], f: &mut impl FnMut(&mut u32)) {
+    for t in traits {
+        if let Trait::Slot(slot) | Trait::Const(slot) = t {
+            if slot.kind == CONSTANT_UTF8 {
+                f(&mut slot.index);
+            }
+        }
+    }
+}
+
+fn visit_code_strings(method: &mut Method, f: &mut impl FnMut(&mut u32)) -> Result<()> {
+    let mut instructions = method.parse()?;
+    let mut changed = false;
+    for ins in &mut instructions {
+        let index = match &mut ins.op {
+            Op::PushString(arg) => &mut arg.value,
+            Op::Dxns(arg) => &mut arg.uri,
+            Op::DebugFile(arg) => &mut arg.filename,
+            Op::Debug(arg) => &mut arg.reg_name,
+            _ => continue,
+        };
+        let old = *index;
+        f(index);
+        changed |= *index != old;
+    }
+    // The size of the instructions changes with the indices
+    if changed {
+        method.save_relocated_instructions(&instructions)?;
+    }
+    Ok(())
+}
+
+impl Method {
+    /// Save instructions that may have been removed or resized since they were parsed, updating
+    /// the branch targets and the exception ranges.
+    /// The instructions must keep the address they were parsed at, targets and exception
+    /// offsets pointing to a removed instruction are moved to the next one.
+    pub fn save_relocated_instructions(&mut self, instructions: &[Instruction]) -> Result<()> {
+        let mut addresses = Vec::with_capacity(instructions.len());
+        let mut addr = 0;
+        for ins in instructions {
+            addresses.push((ins.addr, addr));
+            addr += ins.size();
+        }
+        let end = addr;
+        let relocate = |old: u32| {
+            let index = addresses.partition_point(|(o, _)| *o < old);
+            addresses.get(index).map_or(end, |(_, new)| *new)
+        };
+
+        let mut relocated = instructions.to_vec();
+        for (ins, (_, new)) in relocated.iter_mut().zip(&addresses) {
+            ins.addr = *new;
+            match &mut ins.op {
+                Op::IfNlt(arg)
+                | Op::IfNle(arg)
+                | Op::IfNgt(arg)
+                | Op::IfNge(arg)
+                | Op::Jump(arg)
+                | Op::IfTrue(arg)
+                | Op::IfFalse(arg)
+                | Op::IfEq(arg)
+                | Op::IfNe(arg)
+                | Op::IfLt(arg)
+                | Op::IfLe(arg)
+                | Op::IfGt(arg)
+                | Op::IfGe(arg)
+                | Op::IfStrictEq(arg)
+                | Op::IfStrictNe(arg) => arg.target = relocate(arg.target),
+                Op::LookupSwitch(arg) => {
+                    arg.default_target = relocate(arg.default_target);
+                    for target in arg.targets.iter_mut() {
+                        *target = relocate(*target);
+                    }
+                }
+                _ => {}
+            }
+        }
+        for exception in &mut self.exceptions {
+            exception.from = relocate(exception.from);
+            exception.to = relocate(exception.to);
+            exception.target = relocate(exception.target);
+        }
+        self.save_instructions(&relocated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abc::Exception;
+
+    #[test]
+    fn strip_debug_info() {
+        let mut abc = AbcFile::new();
+        abc.cpool.strings = ["", "used", "file.as", "unused", "pushed", "arg"]
+            .map(String::from)
+            .to_vec();
+        abc.abc.methods.push(Method {
+            name: 1,
+            flags: MethodFlag::HAS_PARAM_NAMES,
+            params: vec![0],
+            param_names: vec![5],
+            #[rustfmt::skip]
+            code: vec![
+                0xF1, 0x02, // debugfile "file.as"
+                0xF0, 0x05, // debugline 5
+                0x2C, 0x04, // pushstring "pushed"
+                0x12, 0x03, 0x00, 0x00, // iffalse 13
+                0xF0, 0x06, // debugline 6
+                0x02, // nop
+                0x47, // returnvoid
+            ],
+            exceptions: vec![Exception {
+                from: 2,
+                to: 12,
+                target: 13,
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+
+        // Parameter names are kept until the debug information is removed
+        let mut compacted = abc.clone();
+        compacted.compact_strings().unwrap();
+        assert_eq!(
+            compacted.cpool.strings,
+            ["", "used", "file.as", "pushed", "arg"]
+        );
+        assert_eq!(compacted.abc.methods[0].param_names, [4]);
+
+        abc.strip_debug_info().unwrap();
+        assert_eq!(abc.cpool.strings, ["", "used", "pushed"]);
+        let method = &abc.abc.methods[0];
+        assert_eq!(method.name, 1);
+        assert!(method.param_names.is_empty());
+        assert!(!method.has_param_names());
+        assert_eq!(
+            method.code,
+            [0x2C, 0x02, 0x12, 0x01, 0x00, 0x00, 0x02, 0x47]
+        );
+        let exception = &method.exceptions[0];
+        assert_eq!((exception.from, exception.to, exception.target), (0, 6, 7));
+    }
+}
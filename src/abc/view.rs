@@ -0,0 +1,379 @@
+use super::{
+    info::{class::ClassFlag, *},
+    AbcFile, AbcVersion,
+};
+use crate::{
+    error::{RabcError, Result},
+    StreamReader,
+};
+use alloc::vec::Vec;
+
+/// Borrowed view of an ABC file.
+/// Only the offsets of the strings, methods, classes and scripts are recorded when reading;
+/// they are decoded on demand. Numbers, namespaces and multinames are decoded eagerly,
+/// since they don't allocate much and are used everywhere.
+/// Use [`AbcView::to_abc_file`] to get an owned, editable, copy.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AbcView<'a> {
+    /// Data of the whole abc file
+    data: &'a [u8],
+    pub version: AbcVersion,
+    /// Constant pool, without the strings
+    pub cpool: ConstantPool,
+    /// Offset and length of each string, the first one is always empty
+    strings: Vec<(u32, u32)>,
+    /// Offset of each method info and its body, if any
+    methods: Vec<(u32, Option<u32>)>,
+    metadatas: Vec<u32>,
+    /// Offset of each instance info and class info
+    classes: Vec<(u32, u32)>,
+    scripts: Vec<u32>,
+}
+
+impl<'a> AbcView<'a> {
+    pub fn new(data: &'a [u8]) -> Result<Self> {
+        Self::read(&mut StreamReader::new(data))
+    }
+
+    /// Read the abc structure from a stream, without copying its content
+    pub fn read(stream: &mut StreamReader<'a>) -> Result<Self> {
+        let buffer: &'a [u8] = stream.buffer.get_ref();
+        let start = stream.pos();
+        let version = AbcVersion::read(stream)?;
+
+        let mut cpool = ConstantPool::new();
+        let mut count = stream.read_u30()?;
+        cpool.integers.push(0);
+        for _ in 1..count {
+            cpool.integers.push(stream.read_i30()?);
+        }
+        count = stream.read_u30()?;
+        cpool.uintegers.push(0);
+        for _ in 1..count {
+            cpool.uintegers.push(stream.read_u30()?);
+        }
+        count = stream.read_u30()?;
+        cpool.doubles.push(0.0);
+        for _ in 1..count {
+            cpool.doubles.push(stream.read_double()?);
+        }
+        count = stream.read_u30()?;
+        let mut strings = Vec::with_capacity(count.max(1) as usize);
+        strings.push((0, 0));
+        for _ in 1..count {
+            let length = stream.read_u30()?;
+            strings.push((stream.pos() - start, length));
+            stream.skip(length)?;
+        }
+        count = stream.read_u30()?;
+        cpool.namespaces.push(Namespace::new());
+        for _ in 1..count {
+            cpool.namespaces.push(Namespace::read(stream)?);
+        }
+        count = stream.read_u30()?;
+        cpool.ns_sets.push(Vec::new());
+        for _ in 1..count {
+            let ns_count = stream.read_u30()?;
+            let mut ns_set = Vec::with_capacity(ns_count as usize);
+            for _ in 0..ns_count {
+                ns_set.push(stream.read_u30()?);
+            }
+            cpool.ns_sets.push(ns_set);
+        }
+        count = stream.read_u30()?;
+        cpool.multinames.push(Multiname::new());
+        for _ in 1..count {
+            cpool.multinames.push(Multiname::read(stream)?);
+        }
+
+        count = stream.read_u30()?;
+        let mut methods = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            methods.push((stream.pos() - start, None));
+            skip_method(stream)?;
+        }
+
+        count = stream.read_u30()?;
+        let mut metadatas = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            metadatas.push(stream.pos() - start);
+            stream.read_u30()?;
+            let items = stream.read_u30()?;
+            skip_u30s(stream, items * 2)?;
+        }
+
+        count = stream.read_u30()?;
+        let mut classes = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            classes.push((stream.pos() - start, 0));
+            skip_u30s(stream, 2)?;
+            let flags = ClassFlag::from_bits_retain(stream.read_u8()?);
+            if flags.contains(ClassFlag::PROTECTED_NAMESPACE) {
+                stream.read_u30()?;
+            }
+            let interfaces = stream.read_u30()?;
+            skip_u30s(stream, interfaces + 1)?;
+            skip_traits(stream)?;
+        }
+        for class in &mut classes {
+            class.1 = stream.pos() - start;
+            stream.read_u30()?;
+            skip_traits(stream)?;
+        }
+
+        count = stream.read_u30()?;
+        let mut scripts = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            scripts.push(stream.pos() - start);
+            stream.read_u30()?;
+            skip_traits(stream)?;
+        }
+
+        count = stream.read_u30()?;
+        for _ in 0..count {
+            let index = stream.read_u30()?;
+            let method = methods
+                .get_mut(index as usize)
+                .ok_or(RabcError::MethodOutOfBound(index))?;
+            method.1 = Some(stream.pos() - start);
+            skip_body(stream)?;
+        }
+
+        Ok(Self {
+            data: &buffer[start as usize..stream.pos() as usize],
+            version,
+            cpool,
+            strings,
+            methods,
+            metadatas,
+            classes,
+            scripts,
+        })
+    }
+
+    /// Decode the whole file
+    pub fn to_abc_file(&self) -> Result<AbcFile> {
+        AbcFile::read(&mut StreamReader::new(self.data))
+    }
+
+    /// Raw data of the abc file
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    pub fn string_count(&self) -> usize {
+        self.strings.len()
+    }
+    pub fn method_count(&self) -> usize {
+        self.methods.len()
+    }
+    pub fn metadata_count(&self) -> usize {
+        self.metadatas.len()
+    }
+    pub fn class_count(&self) -> usize {
+        self.classes.len()
+    }
+    pub fn script_count(&self) -> usize {
+        self.scripts.len()
+    }
+
+    pub fn get_str(&self, index: u32) -> Result<&'a str> {
+        let (offset, length) = *Self::get("strings", &self.strings, index)?;
+        let (offset, length) = (offset as usize, length as usize);
+        Ok(core::str::from_utf8(&self.data[offset..offset + length])?)
+    }
+
+    pub fn get_method(&self, index: u32) -> Result<Method> {
+        let (offset, body) = *Self::get("methods", &self.methods, index)?;
+        let mut method = Method::read(&mut self.stream_at(offset)?)?;
+        if let Some(body) = body {
+            method.read_body(&mut self.stream_at(body)?)?;
+        }
+        Ok(method)
+    }
+    /// Get the bytecode of a method without copying it, empty if the method has no body
+    pub fn get_code(&self, index: u32) -> Result<&'a [u8]> {
+        let Some(body) = Self::get("methods", &self.methods, index)?.1 else {
+            return Ok(&[]);
+        };
+        let mut stream = self.stream_at(body)?;
+        skip_u30s(&mut stream, 4)?;
+        let length = stream.read_u30()? as usize;
+        let start = stream.pos() as usize;
+        Ok(&self.data[start..start + length])
+    }
+
+    pub fn get_metadata(&self, index: u32) -> Result<Metadata> {
+        let offset = *Self::get("metadatas", &self.metadatas, index)?;
+        Metadata::read(&mut self.stream_at(offset)?)
+    }
+
+    pub fn get_class(&self, index: u32) -> Result<Class> {
+        let (instance, class) = *Self::get("classes", &self.classes, index)?;
+        let mut value = Class::read_instance(&mut self.stream_at(instance)?)?;
+        value.read(&mut self.stream_at(class)?)?;
+        Ok(value)
+    }
+
+    pub fn get_script(&self, index: u32) -> Result<Script> {
+        let offset = *Self::get("scripts", &self.scripts, index)?;
+        Script::read(&mut self.stream_at(offset)?)
+    }
+
+    fn stream_at(&self, offset: u32) -> Result<StreamReader<'a>> {
+        let mut stream = StreamReader::new(self.data);
+        stream.skip(offset)?;
+        Ok(stream)
+    }
+
+    #[inline]
+    fn get<'b, T>(name: &'static str, values: &'b [T], index: u32) -> Result<&'b T> {
+        values
+            .get(index as usize)
+            .ok_or(RabcError::IndexOutOfBounds(
+                name,
+                index as usize,
+                values.len(),
+            ))
+    }
+}
+
+fn skip_u30s(stream: &mut StreamReader, count: u32) -> Result<()> {
+    for _ in 0..count {
+        stream.read_u30()?;
+    }
+    Ok(())
+}
+
+/// Skip a method body, used to find the bodies' offsets before parsing them
+pub(super) fn skip_body(stream: &mut StreamReader) -> Result<()> {
+    skip_u30s(stream, 4)?;
+    let length = stream.read_u30()?;
+    stream.skip(length)?;
+    let exceptions = stream.read_u30()?;
+    skip_u30s(stream, exceptions * 5)?;
+    skip_traits(stream)
+}
+
+fn skip_method(stream: &mut StreamReader) -> Result<()> {
+    let params = stream.read_u30()?;
+    skip_u30s(stream, params + 2)?;
+    let flags = MethodFlag::from_bits_retain(stream.read_u8()?);
+    if flags.contains(MethodFlag::HAS_OPTIONAL) {
+        let count = stream.read_u30()?;
+        for _ in 0..count {
+            stream.read_u30()?;
+            stream.read_u8()?;
+        }
+    }
+    if flags.contains(MethodFlag::HAS_PARAM_NAMES) {
+        skip_u30s(stream, params)?;
+    }
+    Ok(())
+}
+
+fn skip_traits(stream: &mut StreamReader) -> Result<()> {
+    let count = stream.read_u30()?;
+    for _ in 0..count {
+        stream.read_u30()?;
+        let kkind = stream.read_u8()?;
+        match kkind & 0x0f {
+            0 | 6 => {
+                skip_u30s(stream, 2)?;
+                if stream.read_u30()? != 0 {
+                    stream.read_u8()?;
+                }
+            }
+            1..=5 => skip_u30s(stream, 2)?,
+            k => return Err(RabcError::InvalidTraitKind(k)),
+        }
+        if TraitAttr::from_bits_retain(kkind >> 4).contains(TraitAttr::METADATA) {
+            let count = stream.read_u30()?;
+            skip_u30s(stream, count)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        abc::info::{class::ClassFlag, metadata::MetadataItem, r#trait::SlotTrait},
+        StreamWriter,
+    };
+
+    #[test]
+    fn view_and_promote() {
+        let mut abc = AbcFile::new();
+        abc.cpool.strings = ["", "Main", "value", "meta"].map(String::from).to_vec();
+        abc.cpool.integers = vec![0, -5];
+        abc.cpool.multinames = vec![Multiname::new(), Multiname::new()];
+        let slot = Trait::Slot(SlotTrait {
+            name: 1,
+            attr: TraitAttr::METADATA,
+            index: 2,
+            kind: 0x01,
+            metadatas: vec![0],
+            ..Default::default()
+        });
+        abc.abc.methods = vec![
+            Method {
+                name: 1,
+                flags: MethodFlag::HAS_PARAM_NAMES,
+                param_names: vec![2],
+                params: vec![0],
+                ..Default::default()
+            },
+            Method {
+                code: vec![0xD0, 0x30, 0x47],
+                traits: vec![slot.clone()],
+                ..Default::default()
+            },
+        ];
+        abc.abc.metadatas = vec![Metadata {
+            name: 3,
+            items: vec![MetadataItem { key: 0, value: 2 }],
+        }];
+        abc.abc.classes = vec![Class {
+            name: 1,
+            super_name: 0,
+            flags: ClassFlag::SEALED,
+            protected_ns: 0,
+            iinit: 0,
+            cinit: 1,
+            interfaces: vec![1],
+            itraits: vec![slot.clone()],
+            ctraits: vec![],
+        }];
+        abc.abc.scripts = vec![Script {
+            init: 1,
+            traits: vec![slot],
+        }];
+
+        let mut stream = StreamWriter::default();
+        abc.write(&mut stream).unwrap();
+        // Trailing data isn't part of the view
+        stream.write_u8(0xFF).unwrap();
+        let mut reader = StreamReader::new(stream.buffer());
+        let view = AbcView::read(&mut reader).unwrap();
+        assert_eq!(reader.remaining(), 1);
+
+        assert_eq!(view.string_count(), 4);
+        assert_eq!(view.get_str(1).unwrap(), "Main");
+        assert_eq!(view.get_str(0).unwrap(), "");
+        assert!(view.get_str(4).is_err());
+        assert_eq!(view.cpool.integers, abc.cpool.integers);
+        assert_eq!(view.get_code(0).unwrap(), []);
+        assert_eq!(view.get_code(1).unwrap(), [0xD0, 0x30, 0x47]);
+        assert_eq!(view.get_method(1).unwrap(), abc.abc.methods[1]);
+        assert_eq!(view.get_metadata(0).unwrap(), abc.abc.metadatas[0]);
+        assert_eq!(view.get_class(0).unwrap(), abc.abc.classes[0]);
+        assert_eq!(view.get_script(0).unwrap(), abc.abc.scripts[0]);
+        let mut reader = StreamReader::new(stream.buffer());
+        assert_eq!(
+            view.to_abc_file().unwrap(),
+            AbcFile::read(&mut reader).unwrap()
+        );
+    }
+}
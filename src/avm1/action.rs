@@ -0,0 +1,279 @@
+use super::{action_code::ActionCode, args::*};
+use std::fmt::Display;
+
+/// A single AVM1 action, located at `addr` bytes from the start of the action block
+#[derive(Clone, Debug, PartialEq)]
+pub struct Action {
+    pub code: ActionCode,
+    pub op: Op,
+    pub addr: u32,
+}
+
+impl Action {
+    pub fn is_branch(&self) -> bool {
+        matches!(self.code, ActionCode::Jump | ActionCode::If)
+    }
+
+    /// Size in bytes of the action, including its header
+    pub fn size(&self) -> u32 {
+        if self.code.has_payload() {
+            self.op.size() + 3
+        } else {
+            1
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Op {
+    Add(),
+    Add2(),
+    And(),
+    AsciiToChar(),
+    BitAnd(),
+    BitLShift(),
+    BitOr(),
+    BitRShift(),
+    BitURShift(),
+    BitXor(),
+    Call(),
+    CallFunction(),
+    CallMethod(),
+    CastOp(),
+    CharToAscii(),
+    CloneSprite(),
+    ConstantPool(ConstantPoolArg),
+    Decrement(),
+    DefineFunction(Box<DefineFunctionArg>),
+    DefineFunction2(Box<DefineFunction2Arg>),
+    DefineLocal(),
+    DefineLocal2(),
+    Delete(),
+    Delete2(),
+    Divide(),
+    End(),
+    EndDrag(),
+    Enumerate(),
+    Enumerate2(),
+    Equals(),
+    Equals2(),
+    Extends(),
+    FsCommand2(),
+    GetMember(),
+    GetProperty(),
+    GetTime(),
+    GetUrl(GetUrlArg),
+    GetUrl2(GetUrl2Arg),
+    GetVariable(),
+    GotoFrame(GotoFrameArg),
+    GotoFrame2(GotoFrame2Arg),
+    GotoLabel(LabelArg),
+    Greater(),
+    If(BranchArg),
+    ImplementsOp(),
+    Increment(),
+    InitArray(),
+    InitObject(),
+    InstanceOf(),
+    Jump(BranchArg),
+    Less(),
+    Less2(),
+    MbAsciiToChar(),
+    MbCharToAscii(),
+    MbStringExtract(),
+    MbStringLength(),
+    Modulo(),
+    Multiply(),
+    NewMethod(),
+    NewObject(),
+    NextFrame(),
+    Not(),
+    Or(),
+    Play(),
+    Pop(),
+    PreviousFrame(),
+    Push(PushArg),
+    PushDuplicate(),
+    RandomNumber(),
+    RemoveSprite(),
+    Return(),
+    SetMember(),
+    SetProperty(),
+    SetTarget(TargetNameArg),
+    SetTarget2(),
+    SetVariable(),
+    StackSwap(),
+    StartDrag(),
+    Stop(),
+    StopSounds(),
+    StoreRegister(RegisterArg),
+    StrictEquals(),
+    StrictMode(StrictModeArg),
+    StringAdd(),
+    StringEquals(),
+    StringExtract(),
+    StringGreater(),
+    StringLength(),
+    StringLess(),
+    Subtract(),
+    TargetPath(),
+    Throw(),
+    ToInteger(),
+    ToNumber(),
+    ToString(),
+    ToggleQuality(),
+    Trace(),
+    Try(Box<TryArg>),
+    TypeOf(),
+    WaitForFrame(WaitForFrameArg),
+    WaitForFrame2(SkipCountArg),
+    With(BlockSizeArg),
+}
+
+impl Op {
+    /// Size in bytes of the payload
+    pub fn size(&self) -> u32 {
+        fn str_size(value: &str) -> u32 {
+            value.len() as u32 + 1
+        }
+        match self {
+            Op::GotoFrame(_) | Op::With(_) | Op::Jump(_) | Op::If(_) => 2,
+            Op::GetUrl(arg) => str_size(&arg.url) + str_size(&arg.target),
+            Op::StoreRegister(_) | Op::StrictMode(_) | Op::WaitForFrame2(_) | Op::GetUrl2(_) => 1,
+            Op::ConstantPool(arg) => 2 + arg.constants.iter().map(|s| str_size(s)).sum::<u32>(),
+            Op::WaitForFrame(_) => 3,
+            Op::SetTarget(arg) => str_size(&arg.target),
+            Op::GotoLabel(arg) => str_size(&arg.label),
+            Op::DefineFunction(arg) => {
+                str_size(&arg.name) + 4 + arg.params.iter().map(|s| str_size(s)).sum::<u32>()
+            }
+            Op::DefineFunction2(arg) => {
+                str_size(&arg.name)
+                    + 7
+                    + arg
+                        .params
+                        .iter()
+                        .map(|p| 1 + str_size(&p.name))
+                        .sum::<u32>()
+            }
+            Op::Try(arg) => {
+                7 + match &arg.catch_target {
+                    CatchTarget::Variable(name) => str_size(name),
+                    CatchTarget::Register(_) => 1,
+                }
+            }
+            Op::Push(arg) => arg
+                .values
+                .iter()
+                .map(|v| {
+                    1 + match v {
+                        PushValue::String(value) => str_size(value),
+                        PushValue::Null | PushValue::Undefined => 0,
+                        PushValue::Register(_)
+                        | PushValue::Boolean(_)
+                        | PushValue::Constant8(_) => 1,
+                        PushValue::Constant16(_) => 2,
+                        PushValue::Float(_) | PushValue::Integer(_) => 4,
+                        PushValue::Double(_) => 8,
+                    }
+                })
+                .sum(),
+            Op::GotoFrame2(arg) => 1 + if arg.scene_bias.is_some() { 2 } else { 0 },
+            _ => 0,
+        }
+    }
+}
+
+impl Display for PushValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PushValue::String(value) => write!(f, "{:?}", value),
+            PushValue::Float(value) => write!(f, "{}f", value),
+            PushValue::Null => f.write_str("null"),
+            PushValue::Undefined => f.write_str("undefined"),
+            PushValue::Register(register) => write!(f, "r:{}", register),
+            PushValue::Boolean(value) => write!(f, "{}", value),
+            PushValue::Double(value) => write!(f, "{:?}", value),
+            PushValue::Integer(value) => write!(f, "{}", value),
+            PushValue::Constant8(index) => write!(f, "c:{}", index),
+            PushValue::Constant16(index) => write!(f, "c:{}", index),
+        }
+    }
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.code.name())?;
+        match &self.op {
+            Op::GotoFrame(arg) => write!(f, " {}", arg.frame),
+            Op::GetUrl(arg) => write!(f, " {:?}, {:?}", arg.url, arg.target),
+            Op::StoreRegister(arg) => write!(f, " r:{}", arg.register),
+            Op::ConstantPool(arg) => {
+                for (i, constant) in arg.constants.iter().enumerate() {
+                    write!(f, "{} {:?}", if i == 0 { "" } else { "," }, constant)?;
+                }
+                Ok(())
+            }
+            Op::StrictMode(arg) => write!(f, " {}", arg.strict),
+            Op::WaitForFrame(arg) => write!(f, " {}, {}", arg.frame, arg.skip_count),
+            Op::SetTarget(arg) => write!(f, " {:?}", arg.target),
+            Op::GotoLabel(arg) => write!(f, " {:?}", arg.label),
+            Op::WaitForFrame2(arg) => write!(f, " {}", arg.skip_count),
+            Op::DefineFunction(arg) => write!(f, " {:?} ({})", arg.name, arg.params.join(", ")),
+            Op::DefineFunction2(arg) => {
+                write!(f, " {:?} (", arg.name)?;
+                for (i, param) in arg.params.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    if param.register == 0 {
+                        write!(f, "{}", param.name)?;
+                    } else {
+                        write!(f, "r:{}={}", param.register, param.name)?;
+                    }
+                }
+                write!(
+                    f,
+                    ") registers:{} flags:{:?}",
+                    arg.register_count, arg.flags
+                )
+            }
+            Op::Try(arg) => {
+                write!(f, " try:{}", arg.try_size)?;
+                if let Some(size) = arg.catch_size {
+                    match &arg.catch_target {
+                        CatchTarget::Variable(name) => write!(f, " catch({}):{}", name, size)?,
+                        CatchTarget::Register(register) => {
+                            write!(f, " catch(r:{}):{}", register, size)?
+                        }
+                    }
+                }
+                if let Some(size) = arg.finally_size {
+                    write!(f, " finally:{}", size)?;
+                }
+                Ok(())
+            }
+            Op::With(arg) => write!(f, " {}", arg.size),
+            Op::Push(arg) => {
+                for (i, value) in arg.values.iter().enumerate() {
+                    write!(f, "{} {}", if i == 0 { "" } else { "," }, value)?;
+                }
+                Ok(())
+            }
+            Op::Jump(arg) | Op::If(arg) => write!(f, " loc_{:04x}", arg.target),
+            Op::GetUrl2(arg) => write!(
+                f,
+                " {:?} target:{} variables:{}",
+                arg.method, arg.load_target, arg.load_variables
+            ),
+            Op::GotoFrame2(arg) => {
+                write!(f, " play:{}", arg.play)?;
+                if let Some(bias) = arg.scene_bias {
+                    write!(f, " bias:{}", bias)?;
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use num_derive::{FromPrimitive, ToPrimitive};
+use std::fmt::Display;
+
+/// Code of an AVM1 action. Actions with a code of 0x80 or more carry a payload.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, FromPrimitive, ToPrimitive, Hash)]
+pub enum ActionCode {
+    End = 0x00,
+    NextFrame = 0x04,
+    PreviousFrame = 0x05,
+    Play = 0x06,
+    Stop = 0x07,
+    ToggleQuality = 0x08,
+    StopSounds = 0x09,
+    Add = 0x0A,
+    Subtract = 0x0B,
+    Multiply = 0x0C,
+    Divide = 0x0D,
+    Equals = 0x0E,
+    Less = 0x0F,
+    And = 0x10,
+    Or = 0x11,
+    Not = 0x12,
+    StringEquals = 0x13,
+    StringLength = 0x14,
+    StringExtract = 0x15,
+    Pop = 0x17,
+    ToInteger = 0x18,
+    GetVariable = 0x1C,
+    SetVariable = 0x1D,
+    SetTarget2 = 0x20,
+    StringAdd = 0x21,
+    GetProperty = 0x22,
+    SetProperty = 0x23,
+    CloneSprite = 0x24,
+    RemoveSprite = 0x25,
+    Trace = 0x26,
+    StartDrag = 0x27,
+    EndDrag = 0x28,
+    StringLess = 0x29,
+    Throw = 0x2A,
+    CastOp = 0x2B,
+    ImplementsOp = 0x2C,
+    FsCommand2 = 0x2D,
+    RandomNumber = 0x30,
+    MbStringLength = 0x31,
+    CharToAscii = 0x32,
+    AsciiToChar = 0x33,
+    GetTime = 0x34,
+    MbStringExtract = 0x35,
+    MbCharToAscii = 0x36,
+    MbAsciiToChar = 0x37,
+    Delete = 0x3A,
+    Delete2 = 0x3B,
+    DefineLocal = 0x3C,
+    CallFunction = 0x3D,
+    Return = 0x3E,
+    Modulo = 0x3F,
+    NewObject = 0x40,
+    DefineLocal2 = 0x41,
+    InitArray = 0x42,
+    InitObject = 0x43,
+    TypeOf = 0x44,
+    TargetPath = 0x45,
+    Enumerate = 0x46,
+    Add2 = 0x47,
+    Less2 = 0x48,
+    Equals2 = 0x49,
+    ToNumber = 0x4A,
+    ToString = 0x4B,
+    PushDuplicate = 0x4C,
+    StackSwap = 0x4D,
+    GetMember = 0x4E,
+    SetMember = 0x4F,
+    Increment = 0x50,
+    Decrement = 0x51,
+    CallMethod = 0x52,
+    NewMethod = 0x53,
+    InstanceOf = 0x54,
+    Enumerate2 = 0x55,
+    BitAnd = 0x60,
+    BitOr = 0x61,
+    BitXor = 0x62,
+    BitLShift = 0x63,
+    BitRShift = 0x64,
+    BitURShift = 0x65,
+    StrictEquals = 0x66,
+    Greater = 0x67,
+    StringGreater = 0x68,
+    Extends = 0x69,
+    GotoFrame = 0x81,
+    GetUrl = 0x83,
+    StoreRegister = 0x87,
+    ConstantPool = 0x88,
+    StrictMode = 0x89,
+    WaitForFrame = 0x8A,
+    SetTarget = 0x8B,
+    GotoLabel = 0x8C,
+    WaitForFrame2 = 0x8D,
+    DefineFunction2 = 0x8E,
+    Try = 0x8F,
+    With = 0x94,
+    Push = 0x96,
+    Jump = 0x99,
+    GetUrl2 = 0x9A,
+    DefineFunction = 0x9B,
+    If = 0x9D,
+    Call = 0x9E,
+    GotoFrame2 = 0x9F,
+}
+
+impl ActionCode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ActionCode::End => "end",
+            ActionCode::NextFrame => "nextFrame",
+            ActionCode::PreviousFrame => "previousFrame",
+            ActionCode::Play => "play",
+            ActionCode::Stop => "stop",
+            ActionCode::ToggleQuality => "toggleQuality",
+            ActionCode::StopSounds => "stopSounds",
+            ActionCode::Add => "add",
+            ActionCode::Subtract => "subtract",
+            ActionCode::Multiply => "multiply",
+            ActionCode::Divide => "divide",
+            ActionCode::Equals => "equals",
+            ActionCode::Less => "less",
+            ActionCode::And => "and",
+            ActionCode::Or => "or",
+            ActionCode::Not => "not",
+            ActionCode::StringEquals => "stringEquals",
+            ActionCode::StringLength => "stringLength",
+            ActionCode::StringExtract => "stringExtract",
+            ActionCode::Pop => "pop",
+            ActionCode::ToInteger => "toInteger",
+            ActionCode::GetVariable => "getVariable",
+            ActionCode::SetVariable => "setVariable",
+            ActionCode::SetTarget2 => "setTarget2",
+            ActionCode::StringAdd => "stringAdd",
+            ActionCode::GetProperty => "getProperty",
+            ActionCode::SetProperty => "setProperty",
+            ActionCode::CloneSprite => "cloneSprite",
+            ActionCode::RemoveSprite => "removeSprite",
+            ActionCode::Trace => "trace",
+            ActionCode::StartDrag => "startDrag",
+            ActionCode::EndDrag => "endDrag",
+            ActionCode::StringLess => "stringLess",
+            ActionCode::Throw => "throw",
+            ActionCode::CastOp => "castOp",
+            ActionCode::ImplementsOp => "implementsOp",
+            ActionCode::FsCommand2 => "fsCommand2",
+            ActionCode::RandomNumber => "randomNumber",
+            ActionCode::MbStringLength => "mbStringLength",
+            ActionCode::CharToAscii => "charToAscii",
+            ActionCode::AsciiToChar => "asciiToChar",
+            ActionCode::GetTime => "getTime",
+            ActionCode::MbStringExtract => "mbStringExtract",
+            ActionCode::MbCharToAscii => "mbCharToAscii",
+            ActionCode::MbAsciiToChar => "mbAsciiToChar",
+            ActionCode::Delete => "delete",
+            ActionCode::Delete2 => "delete2",
+            ActionCode::DefineLocal => "defineLocal",
+            ActionCode::CallFunction => "callFunction",
+            ActionCode::Return => "return",
+            ActionCode::Modulo => "modulo",
+            ActionCode::NewObject => "newObject",
+            ActionCode::DefineLocal2 => "defineLocal2",
+            ActionCode::InitArray => "initArray",
+            ActionCode::InitObject => "initObject",
+            ActionCode::TypeOf => "typeOf",
+            ActionCode::TargetPath => "targetPath",
+            ActionCode::Enumerate => "enumerate",
+            ActionCode::Add2 => "add2",
+            ActionCode::Less2 => "less2",
+            ActionCode::Equals2 => "equals2",
+            ActionCode::ToNumber => "toNumber",
+            ActionCode::ToString => "toString",
+            ActionCode::PushDuplicate => "pushDuplicate",
+            ActionCode::StackSwap => "stackSwap",
+            ActionCode::GetMember => "getMember",
+            ActionCode::SetMember => "setMember",
+            ActionCode::Increment => "increment",
+            ActionCode::Decrement => "decrement",
+            ActionCode::CallMethod => "callMethod",
+            ActionCode::NewMethod => "newMethod",
+            ActionCode::InstanceOf => "instanceOf",
+            ActionCode::Enumerate2 => "enumerate2",
+            ActionCode::BitAnd => "bitAnd",
+            ActionCode::BitOr => "bitOr",
+            ActionCode::BitXor => "bitXor",
+            ActionCode::BitLShift => "bitLShift",
+            ActionCode::BitRShift => "bitRShift",
+            ActionCode::BitURShift => "bitURShift",
+            ActionCode::StrictEquals => "strictEquals",
+            ActionCode::Greater => "greater",
+            ActionCode::StringGreater => "stringGreater",
+            ActionCode::Extends => "extends",
+            ActionCode::GotoFrame => "gotoFrame",
+            ActionCode::GetUrl => "getUrl",
+            ActionCode::StoreRegister => "storeRegister",
+            ActionCode::ConstantPool => "constantPool",
+            ActionCode::StrictMode => "strictMode",
+            ActionCode::WaitForFrame => "waitForFrame",
+            ActionCode::SetTarget => "setTarget",
+            ActionCode::GotoLabel => "gotoLabel",
+            ActionCode::WaitForFrame2 => "waitForFrame2",
+            ActionCode::DefineFunction2 => "defineFunction2",
+            ActionCode::Try => "try",
+            ActionCode::With => "with",
+            ActionCode::Push => "push",
+            ActionCode::Jump => "jump",
+            ActionCode::GetUrl2 => "getUrl2",
+            ActionCode::DefineFunction => "defineFunction",
+            ActionCode::If => "if",
+            ActionCode::Call => "call",
+            ActionCode::GotoFrame2 => "gotoFrame2",
+        }
+    }
+
+    /// Whether the action is followed by a length and a payload
+    pub fn has_payload(&self) -> bool {
+        *self as u8 >= 0x80
+    }
+}
+
+impl Display for ActionCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
@@ -0,0 +1,496 @@
+use crate::{
+    error::{RabcError, Result},
+    StreamReader, StreamWriter,
+};
+use bitflags::bitflags;
+
+bitflags! {
+    /// Registers preloaded and variables suppressed when calling a DefineFunction2 function
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+    pub struct FunctionFlags : u16 {
+        const PRELOAD_GLOBAL = 0x0001;
+        const PRELOAD_THIS = 0x0100;
+        const SUPPRESS_THIS = 0x0200;
+        const PRELOAD_ARGUMENTS = 0x0400;
+        const SUPPRESS_ARGUMENTS = 0x0800;
+        const PRELOAD_SUPER = 0x1000;
+        const SUPPRESS_SUPER = 0x2000;
+        const PRELOAD_ROOT = 0x4000;
+        const PRELOAD_PARENT = 0x8000;
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GotoFrameArg {
+    pub frame: u16,
+}
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GetUrlArg {
+    pub url: String,
+    pub target: String,
+}
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RegisterArg {
+    pub register: u8,
+}
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConstantPoolArg {
+    pub constants: Vec<String>,
+}
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StrictModeArg {
+    pub strict: bool,
+}
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WaitForFrameArg {
+    pub frame: u16,
+    /// Number of actions to skip if the frame isn't loaded
+    pub skip_count: u8,
+}
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TargetNameArg {
+    pub target: String,
+}
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LabelArg {
+    pub label: String,
+}
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SkipCountArg {
+    pub skip_count: u8,
+}
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockSizeArg {
+    /// Size in bytes of the actions following this one that are part of the block
+    pub size: u16,
+}
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BranchArg {
+    /// Absolute address of the target action
+    pub target: u32,
+}
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DefineFunctionArg {
+    pub name: String,
+    pub params: Vec<String>,
+    /// Size in bytes of the body, made of the actions following this one
+    pub code_size: u16,
+}
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FunctionParam {
+    /// Register the parameter is stored in, or 0 to store it in a variable
+    pub register: u8,
+    pub name: String,
+}
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DefineFunction2Arg {
+    pub name: String,
+    pub register_count: u8,
+    pub flags: FunctionFlags,
+    pub params: Vec<FunctionParam>,
+    /// Size in bytes of the body, made of the actions following this one
+    pub code_size: u16,
+}
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CatchTarget {
+    Variable(String),
+    Register(u8),
+}
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TryArg {
+    pub try_size: u16,
+    pub catch_size: Option<u16>,
+    pub finally_size: Option<u16>,
+    pub catch_target: CatchTarget,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum SendVarsMethod {
+    #[default]
+    None = 0,
+    Get = 1,
+    Post = 2,
+}
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GetUrl2Arg {
+    pub method: SendVarsMethod,
+    /// The target is a sprite path instead of a browser window
+    pub load_target: bool,
+    /// Load variables instead of a movie
+    pub load_variables: bool,
+}
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GotoFrame2Arg {
+    /// Play the movie after going to the frame
+    pub play: bool,
+    /// Number of frames to add to the frame number popped from the stack
+    pub scene_bias: Option<u16>,
+}
+#[derive(Clone, Debug, PartialEq)]
+pub enum PushValue {
+    String(String),
+    Float(f32),
+    Null,
+    Undefined,
+    Register(u8),
+    Boolean(bool),
+    Double(f64),
+    Integer(i32),
+    /// Index of a string in the last constant pool
+    Constant8(u8),
+    /// Index of a string in the last constant pool
+    Constant16(u16),
+}
+#[derive(Clone, Debug, PartialEq)]
+pub struct PushArg {
+    pub values: Vec<PushValue>,
+}
+
+impl SendVarsMethod {
+    pub fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::Get,
+            2 => Self::Post,
+            _ => Self::None,
+        }
+    }
+}
+
+/// Parse and serialize the payload of an action, without its header
+pub trait SerializeTrait {
+    fn parse(stream: &mut StreamReader) -> Result<Self>
+    where
+        Self: Sized;
+    fn serialize(&self, stream: &mut StreamWriter) -> Result<()>;
+}
+impl SerializeTrait for GotoFrameArg {
+    fn parse(stream: &mut StreamReader) -> Result<Self> {
+        let frame = stream.read_u16()?;
+        Ok(Self { frame })
+    }
+    fn serialize(&self, stream: &mut StreamWriter) -> Result<()> {
+        stream.write_u16(self.frame)
+    }
+}
+impl SerializeTrait for GetUrlArg {
+    fn parse(stream: &mut StreamReader) -> Result<Self> {
+        Ok(Self {
+            url: stream.read_null_string()?,
+            target: stream.read_null_string()?,
+        })
+    }
+    fn serialize(&self, stream: &mut StreamWriter) -> Result<()> {
+        stream.write_null_string(&self.url)?;
+        stream.write_null_string(&self.target)
+    }
+}
+impl SerializeTrait for RegisterArg {
+    fn parse(stream: &mut StreamReader) -> Result<Self> {
+        let register = stream.read_u8()?;
+        Ok(Self { register })
+    }
+    fn serialize(&self, stream: &mut StreamWriter) -> Result<()> {
+        stream.write_u8(self.register)
+    }
+}
+impl SerializeTrait for ConstantPoolArg {
+    fn parse(stream: &mut StreamReader) -> Result<Self> {
+        let count = stream.read_u16()?;
+        let mut constants = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            constants.push(stream.read_null_string()?);
+        }
+        Ok(Self { constants })
+    }
+    fn serialize(&self, stream: &mut StreamWriter) -> Result<()> {
+        let count = u16::try_from(self.constants.len())
+            .map_err(|_| RabcError::ValueOutOfRange("constant count"))?;
+        stream.write_u16(count)?;
+        for constant in &self.constants {
+            stream.write_null_string(constant)?;
+        }
+        Ok(())
+    }
+}
+impl SerializeTrait for StrictModeArg {
+    fn parse(stream: &mut StreamReader) -> Result<Self> {
+        let strict = stream.read_u8()? != 0;
+        Ok(Self { strict })
+    }
+    fn serialize(&self, stream: &mut StreamWriter) -> Result<()> {
+        stream.write_u8(self.strict.into())
+    }
+}
+impl SerializeTrait for WaitForFrameArg {
+    fn parse(stream: &mut StreamReader) -> Result<Self> {
+        Ok(Self {
+            frame: stream.read_u16()?,
+            skip_count: stream.read_u8()?,
+        })
+    }
+    fn serialize(&self, stream: &mut StreamWriter) -> Result<()> {
+        stream.write_u16(self.frame)?;
+        stream.write_u8(self.skip_count)
+    }
+}
+impl SerializeTrait for TargetNameArg {
+    fn parse(stream: &mut StreamReader) -> Result<Self> {
+        let target = stream.read_null_string()?;
+        Ok(Self { target })
+    }
+    fn serialize(&self, stream: &mut StreamWriter) -> Result<()> {
+        stream.write_null_string(&self.target)
+    }
+}
+impl SerializeTrait for LabelArg {
+    fn parse(stream: &mut StreamReader) -> Result<Self> {
+        let label = stream.read_null_string()?;
+        Ok(Self { label })
+    }
+    fn serialize(&self, stream: &mut StreamWriter) -> Result<()> {
+        stream.write_null_string(&self.label)
+    }
+}
+impl SerializeTrait for SkipCountArg {
+    fn parse(stream: &mut StreamReader) -> Result<Self> {
+        let skip_count = stream.read_u8()?;
+        Ok(Self { skip_count })
+    }
+    fn serialize(&self, stream: &mut StreamWriter) -> Result<()> {
+        stream.write_u8(self.skip_count)
+    }
+}
+impl SerializeTrait for BlockSizeArg {
+    fn parse(stream: &mut StreamReader) -> Result<Self> {
+        let size = stream.read_u16()?;
+        Ok(Self { size })
+    }
+    fn serialize(&self, stream: &mut StreamWriter) -> Result<()> {
+        stream.write_u16(self.size)
+    }
+}
+impl SerializeTrait for DefineFunctionArg {
+    fn parse(stream: &mut StreamReader) -> Result<Self> {
+        let name = stream.read_null_string()?;
+        let count = stream.read_u16()?;
+        let mut params = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            params.push(stream.read_null_string()?);
+        }
+        let code_size = stream.read_u16()?;
+        Ok(Self {
+            name,
+            params,
+            code_size,
+        })
+    }
+    fn serialize(&self, stream: &mut StreamWriter) -> Result<()> {
+        stream.write_null_string(&self.name)?;
+        let count = u16::try_from(self.params.len())
+            .map_err(|_| RabcError::ValueOutOfRange("param count"))?;
+        stream.write_u16(count)?;
+        for param in &self.params {
+            stream.write_null_string(param)?;
+        }
+        stream.write_u16(self.code_size)
+    }
+}
+impl SerializeTrait for DefineFunction2Arg {
+    fn parse(stream: &mut StreamReader) -> Result<Self> {
+        let name = stream.read_null_string()?;
+        let count = stream.read_u16()?;
+        let register_count = stream.read_u8()?;
+        let flags = FunctionFlags::from_bits_retain(stream.read_u16()?.swap_bytes());
+        let mut params = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            params.push(FunctionParam {
+                register: stream.read_u8()?,
+                name: stream.read_null_string()?,
+            });
+        }
+        let code_size = stream.read_u16()?;
+        Ok(Self {
+            name,
+            register_count,
+            flags,
+            params,
+            code_size,
+        })
+    }
+    fn serialize(&self, stream: &mut StreamWriter) -> Result<()> {
+        stream.write_null_string(&self.name)?;
+        let count = u16::try_from(self.params.len())
+            .map_err(|_| RabcError::ValueOutOfRange("param count"))?;
+        stream.write_u16(count)?;
+        stream.write_u8(self.register_count)?;
+        stream.write_u16(self.flags.bits().swap_bytes())?;
+        for param in &self.params {
+            stream.write_u8(param.register)?;
+            stream.write_null_string(&param.name)?;
+        }
+        stream.write_u16(self.code_size)
+    }
+}
+
+const CATCH_IN_REGISTER: u8 = 0x04;
+const HAS_FINALLY_BLOCK: u8 = 0x02;
+const HAS_CATCH_BLOCK: u8 = 0x01;
+
+impl SerializeTrait for TryArg {
+    fn parse(stream: &mut StreamReader) -> Result<Self> {
+        let flags = stream.read_u8()?;
+        let try_size = stream.read_u16()?;
+        let catch_size = stream.read_u16()?;
+        let finally_size = stream.read_u16()?;
+        let catch_target = if flags & CATCH_IN_REGISTER != 0 {
+            CatchTarget::Register(stream.read_u8()?)
+        } else {
+            CatchTarget::Variable(stream.read_null_string()?)
+        };
+        Ok(Self {
+            try_size,
+            catch_size: (flags & HAS_CATCH_BLOCK != 0).then_some(catch_size),
+            finally_size: (flags & HAS_FINALLY_BLOCK != 0).then_some(finally_size),
+            catch_target,
+        })
+    }
+    fn serialize(&self, stream: &mut StreamWriter) -> Result<()> {
+        let mut flags = 0;
+        if let CatchTarget::Register(_) = self.catch_target {
+            flags |= CATCH_IN_REGISTER;
+        }
+        if self.finally_size.is_some() {
+            flags |= HAS_FINALLY_BLOCK;
+        }
+        if self.catch_size.is_some() {
+            flags |= HAS_CATCH_BLOCK;
+        }
+
+        stream.write_u8(flags)?;
+        stream.write_u16(self.try_size)?;
+        stream.write_u16(self.catch_size.unwrap_or(0))?;
+        stream.write_u16(self.finally_size.unwrap_or(0))?;
+        match &self.catch_target {
+            CatchTarget::Variable(name) => stream.write_null_string(name),
+            CatchTarget::Register(register) => stream.write_u8(*register),
+        }
+    }
+}
+impl SerializeTrait for GetUrl2Arg {
+    fn parse(stream: &mut StreamReader) -> Result<Self> {
+        let flags = stream.read_u8()?;
+        Ok(Self {
+            method: SendVarsMethod::from_u8(flags >> 6),
+            load_target: flags & 0x02 != 0,
+            load_variables: flags & 0x01 != 0,
+        })
+    }
+    fn serialize(&self, stream: &mut StreamWriter) -> Result<()> {
+        stream.write_u8(
+            ((self.method as u8) << 6)
+                | (u8::from(self.load_target) << 1)
+                | u8::from(self.load_variables),
+        )
+    }
+}
+impl SerializeTrait for GotoFrame2Arg {
+    fn parse(stream: &mut StreamReader) -> Result<Self> {
+        let flags = stream.read_u8()?;
+        let scene_bias = if flags & 0x02 != 0 {
+            Some(stream.read_u16()?)
+        } else {
+            None
+        };
+        Ok(Self {
+            play: flags & 0x01 != 0,
+            scene_bias,
+        })
+    }
+    fn serialize(&self, stream: &mut StreamWriter) -> Result<()> {
+        stream.write_u8((u8::from(self.scene_bias.is_some()) << 1) | u8::from(self.play))?;
+        if let Some(scene_bias) = self.scene_bias {
+            stream.write_u16(scene_bias)?;
+        }
+        Ok(())
+    }
+}
+impl SerializeTrait for PushArg {
+    /// Read values until the end of the stream, which must be limited to the action's payload
+    fn parse(stream: &mut StreamReader) -> Result<Self> {
+        let mut values = Vec::new();
+        while !stream.finished() {
+            values.push(PushValue::parse(stream)?);
+        }
+        Ok(Self { values })
+    }
+    fn serialize(&self, stream: &mut StreamWriter) -> Result<()> {
+        for value in &self.values {
+            value.serialize(stream)?;
+        }
+        Ok(())
+    }
+}
+
+impl PushValue {
+    fn parse(stream: &mut StreamReader) -> Result<Self> {
+        let kind = stream.read_u8()?;
+        Ok(match kind {
+            0 => Self::String(stream.read_null_string()?),
+            1 => Self::Float(stream.read_float()?),
+            2 => Self::Null,
+            3 => Self::Undefined,
+            4 => Self::Register(stream.read_u8()?),
+            5 => Self::Boolean(stream.read_u8()? != 0),
+            6 => {
+                // The high 32 bits are stored first
+                let high = stream.read_u32()? as u64;
+                let low = stream.read_u32()? as u64;
+                Self::Double(f64::from_bits((high << 32) | low))
+            }
+            7 => Self::Integer(stream.read_i32()?),
+            8 => Self::Constant8(stream.read_u8()?),
+            9 => Self::Constant16(stream.read_u16()?),
+            _ => return Err(RabcError::InvalidPushValue(kind)),
+        })
+    }
+
+    fn serialize(&self, stream: &mut StreamWriter) -> Result<()> {
+        match self {
+            Self::String(value) => {
+                stream.write_u8(0)?;
+                stream.write_null_string(value)
+            }
+            Self::Float(value) => {
+                stream.write_u8(1)?;
+                stream.write_float(*value)
+            }
+            Self::Null => stream.write_u8(2),
+            Self::Undefined => stream.write_u8(3),
+            Self::Register(register) => {
+                stream.write_u8(4)?;
+                stream.write_u8(*register)
+            }
+            Self::Boolean(value) => {
+                stream.write_u8(5)?;
+                stream.write_u8((*value).into())
+            }
+            Self::Double(value) => {
+                let bits = value.to_bits();
+                stream.write_u8(6)?;
+                stream.write_u32((bits >> 32) as u32)?;
+                stream.write_u32(bits as u32)
+            }
+            Self::Integer(value) => {
+                stream.write_u8(7)?;
+                stream.write_i32(*value)
+            }
+            Self::Constant8(index) => {
+                stream.write_u8(8)?;
+                stream.write_u8(*index)
+            }
+            Self::Constant16(index) => {
+                stream.write_u8(9)?;
+                stream.write_u16(*index)
+            }
+        }
+    }
+}
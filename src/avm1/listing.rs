@@ -0,0 +1,87 @@
+use super::{action::*, args::PushValue};
+use std::{collections::HashSet, fmt::Write};
+
+/// Disassemble the actions into a human readable listing.
+/// Branch targets are labelled, constants are resolved using the last constant pool and
+/// function bodies are indented.
+pub fn listing(actions: &[Action]) -> String {
+    let labels: HashSet<u32> = actions
+        .iter()
+        .filter_map(|action| match &action.op {
+            Op::Jump(arg) | Op::If(arg) => Some(arg.target),
+            _ => None,
+        })
+        .collect();
+
+    let mut out = String::new();
+    let mut constants: &[String] = &[];
+    // End addresses of the function bodies being listed
+    let mut blocks: Vec<u32> = Vec::new();
+    for action in actions {
+        while blocks.last().is_some_and(|end| *end <= action.addr) {
+            blocks.pop();
+        }
+        if labels.contains(&action.addr) {
+            writeln!(out, "loc_{:04x}:", action.addr).unwrap();
+        }
+        let indent = "    ".repeat(blocks.len() + 1);
+
+        match &action.op {
+            Op::ConstantPool(arg) => constants = &arg.constants,
+            Op::Push(arg) => {
+                write!(out, "{}{}", indent, action.code).unwrap();
+                for (i, value) in arg.values.iter().enumerate() {
+                    let constant = match value {
+                        PushValue::Constant8(index) => constants.get(*index as usize),
+                        PushValue::Constant16(index) => constants.get(*index as usize),
+                        _ => None,
+                    };
+                    let sep = if i == 0 { " " } else { ", " };
+                    match constant {
+                        Some(constant) => write!(out, "{}{:?}", sep, constant),
+                        None => write!(out, "{}{}", sep, value),
+                    }
+                    .unwrap();
+                }
+                out.push('\n');
+                continue;
+            }
+            _ => {}
+        }
+        writeln!(out, "{}{}", indent, action).unwrap();
+
+        match &action.op {
+            Op::DefineFunction(arg) => {
+                blocks.push(action.addr + action.size() + arg.code_size as u32)
+            }
+            Op::DefineFunction2(arg) => {
+                blocks.push(action.addr + action.size() + arg.code_size as u32)
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::parse_actions;
+
+    #[test]
+    fn listing_labels_and_constants() {
+        #[rustfmt::skip]
+        let data = [
+            0x88, 0x04, 0x00, 0x01, 0x00, b'a', 0x00, // constantPool "a"
+            0x9b, 0x06, 0x00, b'f', 0x00, 0x00, 0x00, 0x05, 0x00, // function "f" ()
+            0x96, 0x02, 0x00, 0x08, 0x00, // push c:0
+            0x99, 0x02, 0x00, 0xf6, 0xff, // jump -10
+            0x00,
+        ];
+        let actions = parse_actions(&data).unwrap();
+        assert_eq!(
+            listing(&actions),
+            "    constantPool \"a\"\n    defineFunction \"f\" ()\nloc_0010:\n        push \"a\"\n    jump loc_0010\n    end\n"
+        );
+    }
+}
@@ -0,0 +1,10 @@
+mod action;
+mod action_code;
+pub mod args;
+mod listing;
+mod parse;
+
+pub use action::{Action, Op};
+pub use action_code::ActionCode;
+pub use listing::listing;
+pub use parse::{parse_actions, write_actions, write_relocated_actions};
@@ -0,0 +1,397 @@
+use super::{action::*, action_code::ActionCode, args::*};
+use crate::{
+    error::{RabcError, Result},
+    StreamReader, StreamWriter,
+};
+use num_traits::{FromPrimitive, ToPrimitive};
+
+/// Parse a block of actions, such as the content of a DoAction tag or a button action.
+/// The actions of function bodies are inlined after their definition.
+pub fn parse_actions(data: &[u8]) -> Result<Vec<Action>> {
+    // Most actions are a single byte or a short push, use a ratio of 2 to pre-allocate
+    let mut actions = Vec::with_capacity(data.len() / 2);
+    let mut stream = StreamReader::new(data);
+    while !stream.finished() {
+        actions.push(parse(&mut stream)?);
+    }
+    Ok(actions)
+}
+
+/// Serialize the actions, computing the branch offsets from the actions' current addresses.
+pub fn write_actions(actions: &[Action]) -> Result<Vec<u8>> {
+    let mut stream = StreamWriter::new(Vec::with_capacity(actions.len() * 2));
+    for action in actions {
+        serialize(action, &mut stream)?;
+    }
+    Ok(stream.move_buffer())
+}
+
+/// Serialize actions that may have been inserted, removed or resized since they were parsed,
+/// updating the branch targets and the sizes of the function, try and with blocks.
+/// The actions must keep the address they were parsed at, inserted actions using the address
+/// of the action following them. Targets and block ends pointing to a removed action are moved
+/// to the next one. Blocks are measured from the end of their action, so a DefineFunction(2),
+/// Try or With action must keep the size it was parsed with.
+pub fn write_relocated_actions(actions: &[Action]) -> Result<Vec<u8>> {
+    let mut addresses = Vec::with_capacity(actions.len());
+    let mut addr = 0;
+    for action in actions {
+        addresses.push((action.addr, addr));
+        addr += action.size();
+    }
+    let end = addr;
+    let relocate = |old: u32| {
+        let index = addresses.partition_point(|(o, _)| *o < old);
+        addresses.get(index).map_or(end, |(_, new)| *new)
+    };
+    // Size of a block starting at `start`, once its end is relocated
+    let block_size = |start: u32, old_end: u32| {
+        relocate(old_end)
+            .checked_sub(start)
+            .and_then(|size| u16::try_from(size).ok())
+            .ok_or(RabcError::ValueOutOfRange("block size"))
+    };
+
+    let mut relocated = actions.to_vec();
+    for (action, (_, new)) in relocated.iter_mut().zip(&addresses) {
+        let old_next = action.addr + action.size();
+        let next = *new + action.size();
+        action.addr = *new;
+        match &mut action.op {
+            Op::Jump(arg) | Op::If(arg) => arg.target = relocate(arg.target),
+            Op::DefineFunction(arg) => {
+                arg.code_size = block_size(next, old_next + u32::from(arg.code_size))?
+            }
+            Op::DefineFunction2(arg) => {
+                arg.code_size = block_size(next, old_next + u32::from(arg.code_size))?
+            }
+            Op::With(arg) => arg.size = block_size(next, old_next + u32::from(arg.size))?,
+            Op::Try(arg) => {
+                // The catch and finally blocks follow the try block
+                let try_end = old_next + u32::from(arg.try_size);
+                let catch_end = try_end + u32::from(arg.catch_size.unwrap_or(0));
+                let finally_end = catch_end + u32::from(arg.finally_size.unwrap_or(0));
+                arg.try_size = block_size(next, try_end)?;
+                if let Some(size) = &mut arg.catch_size {
+                    *size = block_size(relocate(try_end), catch_end)?;
+                }
+                if let Some(size) = &mut arg.finally_size {
+                    *size = block_size(relocate(catch_end), finally_end)?;
+                }
+            }
+            _ => {}
+        }
+    }
+    write_actions(&relocated)
+}
+
+fn parse(stream: &mut StreamReader) -> Result<Action> {
+    let addr = stream.pos();
+    let byte = stream.read_u8()?;
+    let code = ActionCode::from_u8(byte).ok_or(RabcError::InvalidActionCode(byte, addr))?;
+    if !code.has_payload() {
+        let op = match code {
+            ActionCode::End => Op::End(),
+            ActionCode::NextFrame => Op::NextFrame(),
+            ActionCode::PreviousFrame => Op::PreviousFrame(),
+            ActionCode::Play => Op::Play(),
+            ActionCode::Stop => Op::Stop(),
+            ActionCode::ToggleQuality => Op::ToggleQuality(),
+            ActionCode::StopSounds => Op::StopSounds(),
+            ActionCode::Add => Op::Add(),
+            ActionCode::Subtract => Op::Subtract(),
+            ActionCode::Multiply => Op::Multiply(),
+            ActionCode::Divide => Op::Divide(),
+            ActionCode::Equals => Op::Equals(),
+            ActionCode::Less => Op::Less(),
+            ActionCode::And => Op::And(),
+            ActionCode::Or => Op::Or(),
+            ActionCode::Not => Op::Not(),
+            ActionCode::StringEquals => Op::StringEquals(),
+            ActionCode::StringLength => Op::StringLength(),
+            ActionCode::StringExtract => Op::StringExtract(),
+            ActionCode::Pop => Op::Pop(),
+            ActionCode::ToInteger => Op::ToInteger(),
+            ActionCode::GetVariable => Op::GetVariable(),
+            ActionCode::SetVariable => Op::SetVariable(),
+            ActionCode::SetTarget2 => Op::SetTarget2(),
+            ActionCode::StringAdd => Op::StringAdd(),
+            ActionCode::GetProperty => Op::GetProperty(),
+            ActionCode::SetProperty => Op::SetProperty(),
+            ActionCode::CloneSprite => Op::CloneSprite(),
+            ActionCode::RemoveSprite => Op::RemoveSprite(),
+            ActionCode::Trace => Op::Trace(),
+            ActionCode::StartDrag => Op::StartDrag(),
+            ActionCode::EndDrag => Op::EndDrag(),
+            ActionCode::StringLess => Op::StringLess(),
+            ActionCode::Throw => Op::Throw(),
+            ActionCode::CastOp => Op::CastOp(),
+            ActionCode::ImplementsOp => Op::ImplementsOp(),
+            ActionCode::FsCommand2 => Op::FsCommand2(),
+            ActionCode::RandomNumber => Op::RandomNumber(),
+            ActionCode::MbStringLength => Op::MbStringLength(),
+            ActionCode::CharToAscii => Op::CharToAscii(),
+            ActionCode::AsciiToChar => Op::AsciiToChar(),
+            ActionCode::GetTime => Op::GetTime(),
+            ActionCode::MbStringExtract => Op::MbStringExtract(),
+            ActionCode::MbCharToAscii => Op::MbCharToAscii(),
+            ActionCode::MbAsciiToChar => Op::MbAsciiToChar(),
+            ActionCode::Delete => Op::Delete(),
+            ActionCode::Delete2 => Op::Delete2(),
+            ActionCode::DefineLocal => Op::DefineLocal(),
+            ActionCode::CallFunction => Op::CallFunction(),
+            ActionCode::Return => Op::Return(),
+            ActionCode::Modulo => Op::Modulo(),
+            ActionCode::NewObject => Op::NewObject(),
+            ActionCode::DefineLocal2 => Op::DefineLocal2(),
+            ActionCode::InitArray => Op::InitArray(),
+            ActionCode::InitObject => Op::InitObject(),
+            ActionCode::TypeOf => Op::TypeOf(),
+            ActionCode::TargetPath => Op::TargetPath(),
+            ActionCode::Enumerate => Op::Enumerate(),
+            ActionCode::Add2 => Op::Add2(),
+            ActionCode::Less2 => Op::Less2(),
+            ActionCode::Equals2 => Op::Equals2(),
+            ActionCode::ToNumber => Op::ToNumber(),
+            ActionCode::ToString => Op::ToString(),
+            ActionCode::PushDuplicate => Op::PushDuplicate(),
+            ActionCode::StackSwap => Op::StackSwap(),
+            ActionCode::GetMember => Op::GetMember(),
+            ActionCode::SetMember => Op::SetMember(),
+            ActionCode::Increment => Op::Increment(),
+            ActionCode::Decrement => Op::Decrement(),
+            ActionCode::CallMethod => Op::CallMethod(),
+            ActionCode::NewMethod => Op::NewMethod(),
+            ActionCode::InstanceOf => Op::InstanceOf(),
+            ActionCode::Enumerate2 => Op::Enumerate2(),
+            ActionCode::BitAnd => Op::BitAnd(),
+            ActionCode::BitOr => Op::BitOr(),
+            ActionCode::BitXor => Op::BitXor(),
+            ActionCode::BitLShift => Op::BitLShift(),
+            ActionCode::BitRShift => Op::BitRShift(),
+            ActionCode::BitURShift => Op::BitURShift(),
+            ActionCode::StrictEquals => Op::StrictEquals(),
+            ActionCode::Greater => Op::Greater(),
+            ActionCode::StringGreater => Op::StringGreater(),
+            ActionCode::Extends => Op::Extends(),
+            _ => unreachable!(),
+        };
+        return Ok(Action { code, op, addr });
+    }
+
+    let length = stream.read_u16()?;
+    let mut payload = vec![0u8; length as usize];
+    stream.read_exact(&mut payload)?;
+    let next = stream.pos();
+    let stream = &mut StreamReader::new(&payload);
+    let op = match code {
+        ActionCode::GotoFrame => Op::GotoFrame(GotoFrameArg::parse(stream)?),
+        ActionCode::GetUrl => Op::GetUrl(GetUrlArg::parse(stream)?),
+        ActionCode::StoreRegister => Op::StoreRegister(RegisterArg::parse(stream)?),
+        ActionCode::ConstantPool => Op::ConstantPool(ConstantPoolArg::parse(stream)?),
+        ActionCode::StrictMode => Op::StrictMode(StrictModeArg::parse(stream)?),
+        ActionCode::WaitForFrame => Op::WaitForFrame(WaitForFrameArg::parse(stream)?),
+        ActionCode::SetTarget => Op::SetTarget(TargetNameArg::parse(stream)?),
+        ActionCode::GotoLabel => Op::GotoLabel(LabelArg::parse(stream)?),
+        ActionCode::WaitForFrame2 => Op::WaitForFrame2(SkipCountArg::parse(stream)?),
+        ActionCode::DefineFunction2 => {
+            Op::DefineFunction2(Box::new(DefineFunction2Arg::parse(stream)?))
+        }
+        ActionCode::Try => Op::Try(Box::new(TryArg::parse(stream)?)),
+        ActionCode::With => Op::With(BlockSizeArg::parse(stream)?),
+        ActionCode::Push => Op::Push(PushArg::parse(stream)?),
+        ActionCode::Jump => Op::Jump(parse_branch(stream, next)?),
+        ActionCode::GetUrl2 => Op::GetUrl2(GetUrl2Arg::parse(stream)?),
+        ActionCode::DefineFunction => {
+            Op::DefineFunction(Box::new(DefineFunctionArg::parse(stream)?))
+        }
+        ActionCode::If => Op::If(parse_branch(stream, next)?),
+        ActionCode::Call => Op::Call(),
+        ActionCode::GotoFrame2 => Op::GotoFrame2(GotoFrame2Arg::parse(stream)?),
+        _ => unreachable!(),
+    };
+    Ok(Action { code, op, addr })
+}
+
+/// Branch offsets are relative to the next action
+fn parse_branch(stream: &mut StreamReader, next: u32) -> Result<BranchArg> {
+    let offset = stream.read_i16()?;
+    Ok(BranchArg {
+        target: (next as i32 + offset as i32) as u32,
+    })
+}
+
+fn serialize(action: &Action, stream: &mut StreamWriter) -> Result<()> {
+    stream.write_u8(action.code.to_u8().unwrap())?;
+    if !action.code.has_payload() {
+        return Ok(());
+    }
+
+    let mut payload = StreamWriter::default();
+    match &action.op {
+        Op::GotoFrame(arg) => arg.serialize(&mut payload)?,
+        Op::GetUrl(arg) => arg.serialize(&mut payload)?,
+        Op::StoreRegister(arg) => arg.serialize(&mut payload)?,
+        Op::ConstantPool(arg) => arg.serialize(&mut payload)?,
+        Op::StrictMode(arg) => arg.serialize(&mut payload)?,
+        Op::WaitForFrame(arg) => arg.serialize(&mut payload)?,
+        Op::SetTarget(arg) => arg.serialize(&mut payload)?,
+        Op::GotoLabel(arg) => arg.serialize(&mut payload)?,
+        Op::WaitForFrame2(arg) => arg.serialize(&mut payload)?,
+        Op::DefineFunction2(arg) => arg.serialize(&mut payload)?,
+        Op::Try(arg) => arg.serialize(&mut payload)?,
+        Op::With(arg) => arg.serialize(&mut payload)?,
+        Op::Push(arg) => arg.serialize(&mut payload)?,
+        Op::Jump(arg) | Op::If(arg) => {
+            // the header takes 2 more bytes for the length and 2 for the offset
+            let next = stream.len() as i32 + 4;
+            let offset = i16::try_from(arg.target as i32 - next)
+                .map_err(|_| RabcError::ValueOutOfRange("branch offset"))?;
+            payload.write_i16(offset)?;
+        }
+        Op::GetUrl2(arg) => arg.serialize(&mut payload)?,
+        Op::DefineFunction(arg) => arg.serialize(&mut payload)?,
+        Op::GotoFrame2(arg) => arg.serialize(&mut payload)?,
+        _ => {}
+    }
+    let length =
+        u16::try_from(payload.len()).map_err(|_| RabcError::ValueOutOfRange("action length"))?;
+    stream.write_u16(length)?;
+    stream.write_stream(&payload)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        #[rustfmt::skip]
+        let data = [
+            0x88, 0x07, 0x00, 0x02, 0x00, b'a', 0x00, b'b', b'c', 0x00, // constantPool "a", "bc"
+            0x96, 0x0d, 0x00, 0x08, 0x00, 0x06, 0x00, 0x00, 0xf8, 0x3f, 0x00, 0x00, 0x00, 0x00, 0x05, 0x01, // push c:0, 1.5, true
+            0x9d, 0x02, 0x00, 0x05, 0x00, // if +5
+            0x96, 0x02, 0x00, 0x04, 0x01, // push r:1
+            0x17, // pop
+            0x99, 0x02, 0x00, 0xf0, 0xff, // jump -16
+            0x8e, 0x0c, 0x00, b'f', 0x00, 0x01, 0x00, 0x02, 0x86, 0x00, 0x01, b'x', 0x00, 0x02, 0x00, // function2 "f" (r:1=x)
+            0x3e, 0x00, // return, end
+        ];
+        let actions = parse_actions(&data).unwrap();
+        assert_eq!(actions.len(), 9);
+        assert_eq!(actions[1].addr, 10);
+        assert_eq!(
+            actions[1].op,
+            Op::Push(PushArg {
+                values: vec![
+                    PushValue::Constant8(0),
+                    PushValue::Double(1.5),
+                    PushValue::Boolean(true)
+                ]
+            })
+        );
+        assert_eq!(actions[2].op, Op::If(BranchArg { target: 36 }));
+        assert_eq!(actions[5].op, Op::Jump(BranchArg { target: 26 }));
+        if let Op::DefineFunction2(arg) = &actions[6].op {
+            assert_eq!(arg.name, "f");
+            assert_eq!(arg.register_count, 2);
+            assert_eq!(
+                arg.flags,
+                FunctionFlags::PRELOAD_PARENT
+                    | FunctionFlags::PRELOAD_ARGUMENTS
+                    | FunctionFlags::SUPPRESS_THIS
+            );
+            assert_eq!(arg.params[0].register, 1);
+            assert_eq!(arg.code_size, 2);
+        } else {
+            panic!("expected a function, got {:?}", actions[6].op);
+        }
+        for action in &actions {
+            assert_eq!(action.size() as usize, {
+                let mut stream = StreamWriter::default();
+                serialize(action, &mut stream).unwrap();
+                stream.len()
+            });
+        }
+
+        assert_eq!(write_actions(&actions).unwrap(), data);
+    }
+
+    #[test]
+    fn relocate() {
+        #[rustfmt::skip]
+        let data = [
+            0x9d, 0x02, 0x00, 0x05, 0x00, // if +5
+            0x96, 0x02, 0x00, 0x04, 0x01, // push r:1
+            0x17, // pop
+            0x99, 0x02, 0x00, 0xf0, 0xff, // jump -16
+            0x9b, 0x06, 0x00, b'f', 0x00, 0x00, 0x00, 0x02, 0x00, // function "f"
+            0x3e, 0x00, // return, end
+        ];
+        let mut actions = parse_actions(&data).unwrap();
+        // Remove the push and insert an action at the start of the function body
+        actions.remove(1);
+        actions.insert(
+            4,
+            Action {
+                code: ActionCode::Play,
+                op: Op::Play(),
+                addr: 25,
+            },
+        );
+        let actions = parse_actions(&write_relocated_actions(&actions).unwrap()).unwrap();
+        assert_eq!(actions[0].op, Op::If(BranchArg { target: 5 }));
+        assert_eq!(actions[2].op, Op::Jump(BranchArg { target: 0 }));
+        match &actions[3].op {
+            Op::DefineFunction(arg) => assert_eq!(arg.code_size, 3),
+            op => panic!("expected a function, got {:?}", op),
+        }
+        assert_eq!(actions[4].op, Op::Play());
+
+        #[rustfmt::skip]
+        let data = [
+            0x8f, 0x08, 0x00, 0x07, 0x01, 0x00, 0x01, 0x00, 0x06, 0x00, 0x01, // try r:1
+            0x17, // pop (try)
+            0x17, // pop (catch)
+            0x94, 0x02, 0x00, 0x01, 0x00, // with 1 (finally)
+            0x17, // pop
+            0x00, // end
+        ];
+        let mut actions = parse_actions(&data).unwrap();
+        actions.insert(
+            1,
+            Action {
+                code: ActionCode::Stop,
+                op: Op::Stop(),
+                addr: 11,
+            },
+        );
+        actions.remove(5);
+        actions.remove(3);
+        let actions = parse_actions(&write_relocated_actions(&actions).unwrap()).unwrap();
+        match &actions[0].op {
+            Op::Try(arg) => {
+                assert_eq!(arg.try_size, 2);
+                assert_eq!(arg.catch_size, Some(0));
+                assert_eq!(arg.finally_size, Some(5));
+            }
+            op => panic!("expected a try, got {:?}", op),
+        }
+        assert_eq!(actions[3].op, Op::With(BlockSizeArg { size: 0 }));
+    }
+
+    #[test]
+    fn payload_too_long() {
+        let action = Action {
+            code: ActionCode::ConstantPool,
+            op: Op::ConstantPool(ConstantPoolArg {
+                constants: vec!["a".repeat(0x8000); 2],
+            }),
+            addr: 0,
+        };
+        assert!(matches!(
+            write_actions(&[action]),
+            Err(RabcError::ValueOutOfRange("action length"))
+        ));
+    }
+}
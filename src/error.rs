@@ -1,13 +1,18 @@
+#[cfg(feature = "std")]
+use crate::swf::datatypes::AudioCompression;
+use alloc::string::String;
 use thiserror::Error;
 
-pub type Result<T> = std::result::Result<T, RabcError>;
+pub type Result<T> = core::result::Result<T, RabcError>;
 
 #[derive(Debug, Error)]
 pub enum RabcError {
     #[error("IO error: {0}")]
-    IoError(#[from] std::io::Error),
+    IoError(#[from] crate::io::Error),
     #[error("utf8 error: {0}")]
-    Utf8Error(#[from] std::string::FromUtf8Error),
+    Utf8Error(#[from] alloc::string::FromUtf8Error),
+    #[error("utf8 error: {0}")]
+    StrUtf8Error(#[from] core::str::Utf8Error),
 
     #[error("Invalid compression: {0}")]
     InvalidCompression(char),
@@ -29,8 +34,21 @@ pub enum RabcError {
     InvalidTraitKind(u8),
     #[error("Invalid opcode: 0x{0:02x} at pos {1}")]
     InvalidOpCode(u8, u32),
+    #[error("Invalid action code: 0x{0:02x} at pos {1}")]
+    InvalidActionCode(u8, u32),
+    #[error("Invalid push value type: {0}")]
+    InvalidPushValue(u8),
     #[error("Index out of bounds in {0}: {1} > {2}")]
     IndexOutOfBounds(&'static str, usize, usize),
+    #[error("Value out of range: {0}")]
+    ValueOutOfRange(&'static str),
+    #[error("Invalid shape record: {0}")]
+    InvalidShapeRecord(&'static str),
+    #[cfg(feature = "std")]
+    #[error("Unsupported sound compression: {0:?}")]
+    UnsupportedSoundCompression(AudioCompression),
+    #[error("Invalid filter: {0}")]
+    InvalidFilter(u8),
 
     #[cfg(feature = "lzma-rs")]
     #[error("Lzma error: {0}")]
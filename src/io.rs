@@ -0,0 +1,189 @@
+//! I/O traits used by the streams: those of `std::io` when the `std` feature is enabled,
+//! otherwise a minimal subset working on byte slices and vectors.
+#[cfg(feature = "std")]
+pub use std::io::{Cursor, Error, ErrorKind, Read, Result, Seek, SeekFrom, Write};
+
+#[cfg(not(feature = "std"))]
+pub use self::core_io::*;
+
+#[cfg(not(feature = "std"))]
+mod core_io {
+    use alloc::vec::Vec;
+    use core::fmt;
+
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum ErrorKind {
+        InvalidInput,
+        InvalidData,
+        UnexpectedEof,
+        WriteZero,
+        Other,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct Error {
+        kind: ErrorKind,
+    }
+
+    impl Error {
+        pub fn kind(&self) -> ErrorKind {
+            self.kind
+        }
+    }
+
+    impl From<ErrorKind> for Error {
+        fn from(kind: ErrorKind) -> Self {
+            Self { kind }
+        }
+    }
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str(match self.kind {
+                ErrorKind::InvalidInput => "invalid input parameter",
+                ErrorKind::InvalidData => "invalid data",
+                ErrorKind::UnexpectedEof => "unexpected end of file",
+                ErrorKind::WriteZero => "write zero",
+                ErrorKind::Other => "other error",
+            })
+        }
+    }
+
+    impl core::error::Error for Error {}
+
+    pub trait Read {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize>;
+
+        fn read_exact(&mut self, mut buf: &mut [u8]) -> Result<()> {
+            while !buf.is_empty() {
+                match self.read(buf)? {
+                    0 => return Err(ErrorKind::UnexpectedEof.into()),
+                    n => buf = &mut buf[n..],
+                }
+            }
+            Ok(())
+        }
+    }
+
+    pub trait Write {
+        fn write(&mut self, buf: &[u8]) -> Result<usize>;
+        fn flush(&mut self) -> Result<()>;
+
+        fn write_all(&mut self, mut buf: &[u8]) -> Result<()> {
+            while !buf.is_empty() {
+                match self.write(buf)? {
+                    0 => return Err(ErrorKind::WriteZero.into()),
+                    n => buf = &buf[n..],
+                }
+            }
+            Ok(())
+        }
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum SeekFrom {
+        Start(u64),
+        End(i64),
+        Current(i64),
+    }
+
+    pub trait Seek {
+        fn seek(&mut self, pos: SeekFrom) -> Result<u64>;
+
+        fn stream_position(&mut self) -> Result<u64> {
+            self.seek(SeekFrom::Current(0))
+        }
+    }
+
+    #[derive(Clone, Debug, Default, PartialEq, Eq)]
+    pub struct Cursor<T> {
+        inner: T,
+        pos: u64,
+    }
+
+    impl<T> Cursor<T> {
+        pub fn new(inner: T) -> Self {
+            Self { inner, pos: 0 }
+        }
+        pub fn position(&self) -> u64 {
+            self.pos
+        }
+        pub fn set_position(&mut self, pos: u64) {
+            self.pos = pos;
+        }
+        pub fn get_ref(&self) -> &T {
+            &self.inner
+        }
+        pub fn get_mut(&mut self) -> &mut T {
+            &mut self.inner
+        }
+        pub fn into_inner(self) -> T {
+            self.inner
+        }
+    }
+
+    impl<T: AsRef<[u8]>> Read for Cursor<T> {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            let data = self.inner.as_ref();
+            let start = (self.pos as usize).min(data.len());
+            let n = buf.len().min(data.len() - start);
+            buf[..n].copy_from_slice(&data[start..start + n]);
+            self.pos += n as u64;
+            Ok(n)
+        }
+    }
+
+    impl<T: AsRef<[u8]>> Seek for Cursor<T> {
+        fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+            let (base, offset) = match pos {
+                SeekFrom::Start(n) => {
+                    self.pos = n;
+                    return Ok(n);
+                }
+                SeekFrom::End(n) => (self.inner.as_ref().len() as u64, n),
+                SeekFrom::Current(n) => (self.pos, n),
+            };
+            self.pos = base
+                .checked_add_signed(offset)
+                .ok_or(Error::from(ErrorKind::InvalidInput))?;
+            Ok(self.pos)
+        }
+    }
+
+    impl Write for Vec<u8> {
+        fn write(&mut self, buf: &[u8]) -> Result<usize> {
+            self.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Write for Cursor<Vec<u8>> {
+        fn write(&mut self, buf: &[u8]) -> Result<usize> {
+            let pos = self.pos as usize;
+            let end = pos + buf.len();
+            if self.inner.len() < end {
+                self.inner.resize(end, 0);
+            }
+            self.inner[pos..end].copy_from_slice(buf);
+            self.pos = end as u64;
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<W: Write + ?Sized> Write for &mut W {
+        fn write(&mut self, buf: &[u8]) -> Result<usize> {
+            (**self).write(buf)
+        }
+        fn flush(&mut self) -> Result<()> {
+            (**self).flush()
+        }
+    }
+}
@@ -1,13 +1,22 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+#[cfg_attr(not(any(feature = "std", test)), macro_use)]
+extern crate alloc;
+
 pub mod abc;
+#[cfg(feature = "std")]
+pub mod avm1;
 pub mod error;
+pub mod io;
 pub mod stream;
+#[cfg(feature = "std")]
 pub mod swf;
 
 pub use abc::{Abc, AbcFile};
 pub use stream::{StreamReader, StreamWriter};
+#[cfg(feature = "std")]
 pub use swf::{Compression, Movie};
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use crate::{
         stream::{StreamReader, StreamWriter},
@@ -33,7 +42,7 @@ mod tests {
         assert_eq!(movie.compression, Compression::None);
         assert_eq!(movie.file_length, 2828881);
         assert_eq!(movie.framecount, 1);
-        assert_eq!(movie.framerate, 60.0);
+        assert_eq!(movie.fps(), 60.0);
         assert_eq!(movie.framesize.min.x, 0);
         assert_eq!(movie.framesize.min.y, 0);
         assert_eq!(movie.framesize.max.x, 16000);
@@ -18,7 +18,7 @@ fn oldmain() {
     let stream = StreamReader::new(&buf);
     let movie = Movie::read(stream).unwrap();
     println!("Parsing took {}ms", now.elapsed().as_millis());
-    println!("fps: {}", movie.framerate);
+    println!("fps: {}", movie.fps());
 }
 
 fn main() {
@@ -2,5 +2,5 @@ mod bitstream;
 mod reader;
 mod writer;
 pub use bitstream::{BitStreamReader, BitStreamWriter};
-pub use reader::StreamReader;
-pub use writer::StreamWriter;
+pub use reader::{ReadNullString, StreamReader};
+pub use writer::{StreamWriter, LZMA_DICT_SIZE};
@@ -1,7 +1,7 @@
 use crate::error::Result;
 use crate::StreamReader;
 use crate::StreamWriter;
-use std::cmp::min;
+use core::cmp::min;
 
 #[derive(Debug)]
 pub struct BitStreamReader<'a, 'b> {
@@ -54,6 +54,11 @@ impl<'a, 'b> BitStreamReader<'a, 'b> {
         // Shift to retrieve the value's sign
         Ok((value << shift) >> shift)
     }
+
+    #[inline]
+    pub fn read_bit(&mut self) -> Result<bool> {
+        Ok(self.read_ub(1)? == 1)
+    }
 }
 
 #[derive(Debug)]
@@ -106,6 +111,11 @@ impl<'a> BitStreamWriter<'a> {
         }
     }
 
+    #[inline]
+    pub fn write_bit(&mut self, value: bool) -> Result<()> {
+        self.write_ub(1, value.into())
+    }
+
     pub fn flush(&mut self) -> Result<()> {
         if self.bitpos != 0 {
             self.bitpos = 0;
@@ -114,16 +124,20 @@ impl<'a> BitStreamWriter<'a> {
         Ok(())
     }
 
+    /// Minimum number of bits needed to store `value` as an unsigned bit value.
     #[inline]
-    pub fn calc_ubits(value: u32) -> u8 {
-        if value == 0 {
-            return 0;
-        }
-        (u32::BITS - (value - 1).leading_zeros()) as u8
+    pub fn min_ubits(value: u32) -> u8 {
+        (u32::BITS - value.leading_zeros()) as u8
     }
+    /// Minimum number of bits needed to store `value` as a signed bit value.
+    /// Signed values are never stored on a single bit, as it can't be read back.
     #[inline]
-    pub fn calc_sbits(value: i32) -> u8 {
-        Self::calc_ubits(value.unsigned_abs()) + 1
+    pub fn min_sbits(value: i32) -> u8 {
+        match value {
+            0 => 0,
+            v if v < 0 => (u32::BITS + 1 - (!v).leading_zeros()).max(2) as u8,
+            v => (u32::BITS + 1 - v.leading_zeros()).max(2) as u8,
+        }
     }
 }
 
@@ -139,37 +153,30 @@ impl Drop for BitStreamWriter<'_> {
 mod tests {
     use super::BitStreamWriter;
 
-    fn calc_ubits_math(value: u32) -> u8 {
-        (value as f64).log2().ceil() as u8
-    }
-    fn calc_sbits_math(value: i32) -> u8 {
-        calc_ubits_math(value.unsigned_abs()) + 1
-    }
-
-    #[test]
-    fn test_calc_ubits() {
-        for i in 2..100_000 {
-            assert_eq!(
-                BitStreamWriter::calc_ubits(i),
-                calc_ubits_math(i),
-                "incorrect bit size for {i}"
-            );
-        }
-    }
     #[test]
-    fn test_calc_sbits() {
-        for i in -50_000..-2 {
-            assert_eq!(
-                BitStreamWriter::calc_sbits(i),
-                calc_sbits_math(i),
-                "incorrect bit size for {i}"
-            );
-        }
-        for i in 2..50_000 {
-            assert_eq!(
-                BitStreamWriter::calc_sbits(i),
-                calc_sbits_math(i),
-                "incorrect bit size for {i}"
+    fn test_min_bits() {
+        assert_eq!(BitStreamWriter::min_ubits(0), 0);
+        assert_eq!(BitStreamWriter::min_ubits(1), 1);
+        assert_eq!(BitStreamWriter::min_ubits(4), 3);
+        assert_eq!(BitStreamWriter::min_sbits(0), 0);
+        assert_eq!(BitStreamWriter::min_sbits(-1), 2);
+        assert_eq!(BitStreamWriter::min_sbits(-2), 2);
+        assert_eq!(BitStreamWriter::min_sbits(1), 2);
+        assert_eq!(BitStreamWriter::min_sbits(2), 3);
+        assert_eq!(BitStreamWriter::min_sbits(16384), 16);
+        assert_eq!(BitStreamWriter::min_sbits(-16384), 15);
+
+        // The smallest size the value fits in
+        let fits = |value: i32, bits: u8| {
+            let half = 1i64 << (bits - 1);
+            (-half..half).contains(&i64::from(value))
+        };
+        for value in (-50_000..50_000).filter(|v| *v != 0) {
+            let bits = BitStreamWriter::min_sbits(value);
+            assert!(fits(value, bits), "{value} doesn't fit in {bits} bits");
+            assert!(
+                bits == 2 || !fits(value, bits - 1),
+                "{value} fits in less than {bits} bits"
             );
         }
     }
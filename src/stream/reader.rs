@@ -1,19 +1,28 @@
-use crate::error::{RabcError, Result};
-use byteorder::{LittleEndian, ReadBytesExt};
-use std::io::{BufRead, Cursor, Read, Seek, SeekFrom};
+use crate::{
+    error::{RabcError, Result},
+    io::{Cursor, Read, Seek, SeekFrom},
+};
+use alloc::{string::String, vec::Vec};
+use core::marker::PhantomData;
 
 use super::StreamWriter;
 
+/// Little-endian reader, over a byte slice by default.
+/// Any `Read + Seek` source can be used with [`StreamReader::from_reader`], and read with the
+/// ABC parsers, see [`crate::AbcFile::read_from`]. The tags, the instructions and the methods
+/// depending on the length of the data are only available for slices: tags are read from their
+/// data once in memory, see `swf::TagReader` to read a movie from any source.
+/// Generic readers are read a few bytes at a time, unbuffered sources such as files should be
+/// wrapped in a `BufReader`.
 #[derive(Clone, Debug, PartialEq)]
-pub struct StreamReader<'a> {
-    pub(crate) buffer: Cursor<&'a [u8]>,
+pub struct StreamReader<'a, R = Cursor<&'a [u8]>> {
+    pub(crate) buffer: R,
+    _data: PhantomData<&'a [u8]>,
 }
 
 impl<'a> StreamReader<'a> {
     pub fn new(buf: &'a [u8]) -> Self {
-        Self {
-            buffer: Cursor::new(buf),
-        }
+        Self::from_reader(Cursor::new(buf))
     }
 
     #[cfg(feature = "flate2")]
@@ -32,9 +41,17 @@ impl<'a> StreamReader<'a> {
 
         Ok(output)
     }
-    #[cfg(not(feature = "flate2"))]
+    #[cfg(all(feature = "miniz_oxide", not(feature = "flate2")))]
     pub fn inflate_zlib(&mut self, _capacity: usize) -> Result<Vec<u8>> {
-        Err(RabcError::unsupported_compression("zlib"))
+        use miniz_oxide::inflate::decompress_to_vec_zlib;
+
+        let pos = self.buffer.position() as usize;
+        decompress_to_vec_zlib(&self.buffer.get_ref()[pos..])
+            .map_err(|e| RabcError::InvalidDeflateStream(alloc::format!("{:?}", e.status)))
+    }
+    #[cfg(not(any(feature = "flate2", feature = "miniz_oxide")))]
+    pub fn inflate_zlib(&mut self, _capacity: usize) -> Result<Vec<u8>> {
+        Err(RabcError::UnsupportedCompression("zlib"))
     }
 
     #[cfg(feature = "lzma-rs")]
@@ -66,56 +83,123 @@ impl<'a> StreamReader<'a> {
     }
     #[cfg(not(feature = "lzma-rs"))]
     pub fn inflate_lzma(&mut self, _capacity: usize) -> Result<Vec<u8>> {
-        Err(RabcError::unsupported_compression("lzma"))
+        Err(RabcError::UnsupportedCompression("lzma"))
+    }
+
+    #[inline]
+    pub fn pos(&self) -> u32 {
+        self.buffer.position() as u32
+    }
+    #[inline]
+    pub fn len(&self) -> u32 {
+        self.buffer.get_ref().len() as u32
+    }
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    #[inline]
+    pub fn remaining(&self) -> u32 {
+        self.len() - self.buffer.position() as u32
+    }
+    #[inline]
+    pub fn finished(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// Read a null-terminated string, a missing null byte at the end of the data is tolerated
+    pub fn read_null_string(&mut self) -> Result<String> {
+        let pos = self.buffer.position() as usize;
+        let data = self.buffer.get_ref().get(pos..).unwrap_or_default();
+        let length = data.iter().position(|b| *b == 0).unwrap_or(data.len());
+        let string = String::from_utf8(data[..length].to_vec())?;
+        self.buffer
+            .set_position((pos + (length + 1).min(data.len())) as u64);
+        Ok(string)
+    }
+
+    pub fn copy(&self) -> Result<Self> {
+        let mut stream = StreamReader::new(self.buffer.get_ref());
+        stream.skip(self.buffer.position() as u32)?;
+        Ok(stream)
+    }
+
+    #[inline]
+    pub fn write_to_stream(&self, stream: &mut StreamWriter) -> Result<()> {
+        stream.write_exact(self.buffer.get_ref())
+    }
+}
+
+impl<R: Read + Seek> StreamReader<'_, R> {
+    pub fn from_reader(reader: R) -> Self {
+        Self {
+            buffer: reader,
+            _data: PhantomData,
+        }
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.buffer
+    }
+    pub fn into_inner(self) -> R {
+        self.buffer
+    }
+
+    /// Position in the underlying reader
+    #[inline]
+    pub fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.buffer.stream_position()?)
     }
 
     #[inline]
     pub fn read_u8(&mut self) -> Result<u8> {
-        Ok(self.buffer.read_u8()?)
+        Ok(self.read_array::<1>()?[0])
     }
     #[inline]
     pub fn read_u16(&mut self) -> Result<u16> {
-        Ok(self.buffer.read_u16::<LittleEndian>()?)
+        Ok(u16::from_le_bytes(self.read_array()?))
     }
     #[inline]
     pub fn read_u24(&mut self) -> Result<u32> {
-        Ok(self.buffer.read_u24::<LittleEndian>()?)
+        let [a, b, c] = self.read_array()?;
+        Ok(u32::from_le_bytes([a, b, c, 0]))
     }
     #[inline]
     pub fn read_u32(&mut self) -> Result<u32> {
-        Ok(self.buffer.read_u32::<LittleEndian>()?)
+        Ok(u32::from_le_bytes(self.read_array()?))
     }
     #[inline]
     pub fn read_u64(&mut self) -> Result<u64> {
-        Ok(self.buffer.read_u64::<LittleEndian>()?)
+        Ok(u64::from_le_bytes(self.read_array()?))
     }
     #[inline]
     pub fn read_i8(&mut self) -> Result<i8> {
-        Ok(self.buffer.read_i8()?)
+        Ok(self.read_u8()? as i8)
     }
     #[inline]
     pub fn read_i16(&mut self) -> Result<i16> {
-        Ok(self.buffer.read_i16::<LittleEndian>()?)
+        Ok(i16::from_le_bytes(self.read_array()?))
     }
     #[inline]
     pub fn read_i24(&mut self) -> Result<i32> {
-        Ok(self.buffer.read_i24::<LittleEndian>()?)
+        // Sign extend from the 24th bit
+        Ok(((self.read_u24()? << 8) as i32) >> 8)
     }
     #[inline]
     pub fn read_i32(&mut self) -> Result<i32> {
-        Ok(self.buffer.read_i32::<LittleEndian>()?)
+        Ok(i32::from_le_bytes(self.read_array()?))
     }
     #[inline]
     pub fn read_i64(&mut self) -> Result<i64> {
-        Ok(self.buffer.read_i64::<LittleEndian>()?)
+        Ok(i64::from_le_bytes(self.read_array()?))
     }
     #[inline]
     pub fn read_float(&mut self) -> Result<f32> {
-        Ok(self.buffer.read_f32::<LittleEndian>()?)
+        Ok(f32::from_le_bytes(self.read_array()?))
     }
     #[inline]
     pub fn read_double(&mut self) -> Result<f64> {
-        Ok(self.buffer.read_f64::<LittleEndian>()?)
+        Ok(f64::from_le_bytes(self.read_array()?))
     }
 
     // Read a variable-length unsigned integer. See https://en.wikipedia.org/wiki/LEB128 for more informations.
@@ -124,7 +208,7 @@ impl<'a> StreamReader<'a> {
         let mut value: u32 = 0;
 
         for i in (0..35).step_by(7) {
-            let byte = self.buffer.read_u8()?;
+            let byte = self.read_u8()?;
             value += ((byte & 0x7f) as u32) << i;
             if byte & 0x80 == 0 {
                 break;
@@ -138,6 +222,12 @@ impl<'a> StreamReader<'a> {
         Ok(self.read_u30()? as i32)
     }
 
+    #[inline]
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let mut buf = [0u8; N];
+        self.buffer.read_exact(&mut buf)?;
+        Ok(buf)
+    }
     #[inline]
     pub fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
         Ok(self.buffer.read_exact(buf)?)
@@ -148,13 +238,6 @@ impl<'a> StreamReader<'a> {
         Ok(self.buffer.seek(SeekFrom::Current(length.into()))? as u32)
     }
 
-    #[inline]
-    pub fn read_null_string(&mut self) -> Result<String> {
-        let mut buf = Vec::new();
-        self.buffer.read_until(0, &mut buf)?;
-        buf.pop();
-        Ok(String::from_utf8(buf)?)
-    }
     #[inline]
     pub fn read_string(&mut self) -> Result<String> {
         // This is quite slow, we copy the value to a new string, but also because rust validate utf-8
@@ -163,42 +246,29 @@ impl<'a> StreamReader<'a> {
         self.buffer.read_exact(buf.as_mut())?;
         Ok(String::from_utf8(buf)?)
     }
+}
 
-    #[inline]
-    pub fn pos(&self) -> u32 {
-        self.buffer.position() as u32
-    }
-    #[inline]
-    pub fn len(&self) -> u32 {
-        self.buffer.get_ref().len() as u32
-    }
-    #[inline]
-    pub fn is_empty(&self) -> bool {
-        self.len() == 0
-    }
-    #[inline]
-    pub fn remaining(&self) -> u32 {
-        self.len() - self.buffer.position() as u32
-    }
-    #[inline]
-    pub fn finished(&self) -> bool {
-        self.remaining() == 0
-    }
-
-    pub fn copy(&self) -> Result<Self> {
-        let mut stream = StreamReader::new(self.buffer.get_ref());
-        stream.skip(self.buffer.position() as u32)?;
-        Ok(stream)
-    }
+/// Null-terminated strings of any reader, read a byte at a time.
+/// Slices use the inherent [`StreamReader::read_null_string`], which searches the data directly.
+pub trait ReadNullString {
+    fn read_null_string(&mut self) -> Result<String>;
+}
 
-    #[inline]
-    pub fn write_to_stream(&self, stream: &mut StreamWriter) -> Result<()> {
-        stream.write_exact(self.buffer.get_ref())
+impl<R: Read + Seek> ReadNullString for StreamReader<'_, R> {
+    fn read_null_string(&mut self) -> Result<String> {
+        let mut buf = Vec::new();
+        let mut byte = [0u8];
+        // A missing null byte at the end of the data is tolerated
+        while self.buffer.read(&mut byte)? == 1 && byte[0] != 0 {
+            buf.push(byte[0]);
+        }
+        Ok(String::from_utf8(buf)?)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::ReadNullString;
     use crate::StreamReader;
 
     #[test]
@@ -286,4 +356,23 @@ mod tests {
         let mut stream = StreamReader::new(&[0x80, 0x80, 0x80, 0x80]);
         assert!(stream.read_u30().is_err());
     }
+    #[test]
+    pub fn test_read_null_string() {
+        let mut stream = StreamReader::new(b"ab\0\0c");
+        assert_eq!(stream.read_null_string().unwrap(), "ab");
+        assert_eq!(stream.read_null_string().unwrap(), "");
+        // Without a null byte at the end
+        assert_eq!(stream.read_null_string().unwrap(), "c");
+        assert!(stream.finished());
+        assert_eq!(stream.read_null_string().unwrap(), "");
+    }
+    #[test]
+    pub fn test_from_reader() {
+        let mut stream = StreamReader::from_reader(crate::io::Cursor::new(vec![1, 2, b'a', 0, 3]));
+        assert_eq!(stream.read_u16().unwrap(), 0x0201);
+        assert_eq!(stream.read_null_string().unwrap(), "a");
+        assert_eq!(stream.stream_position().unwrap(), 4);
+        assert_eq!(stream.read_u30().unwrap(), 3);
+        assert!(stream.read_u8().is_err());
+    }
 }
@@ -1,10 +1,24 @@
-use crate::error::Result;
-use byteorder::{LittleEndian, WriteBytesExt};
-use std::io::Write;
+use crate::{
+    error::Result,
+    io::{Seek, SeekFrom, Write},
+};
+use alloc::{string::String, vec::Vec};
 
-#[derive(Clone, Debug, PartialEq, Default)]
-pub struct StreamWriter {
-    buffer: Vec<u8>,
+/// Dictionary size written by lzma-rs
+pub const LZMA_DICT_SIZE: u32 = 0x80_0000;
+
+/// Little-endian writer, into a growable buffer by default.
+/// Any `Write` sink can be used with [`StreamWriter::from_writer`], but the compression and the
+/// methods depending on the written data are only available for buffers.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StreamWriter<W = Vec<u8>> {
+    buffer: W,
+}
+
+impl Default for StreamWriter {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
 }
 
 impl StreamWriter {
@@ -12,17 +26,25 @@ impl StreamWriter {
         Self { buffer: buf }
     }
 
-    #[cfg(feature = "flate2")]
     pub fn deflate_zlib(&mut self, offset: usize, length: usize) -> Result<()> {
+        self.deflate_zlib_with_level(offset, length, 9)
+    }
+    /// Compress with a zlib level from 0 (stored) to 9 (best)
+    #[cfg(feature = "flate2")]
+    pub fn deflate_zlib_with_level(
+        &mut self,
+        offset: usize,
+        length: usize,
+        level: u32,
+    ) -> Result<()> {
         use flate2::{write::ZlibEncoder, Compression};
         // Copy anything after the data we want to compress
-        let mut after = Vec::with_capacity(self.len() - offset - length);
-        after.clone_from_slice(&self.buffer[offset + length..]);
+        let after = self.buffer[offset + length..].to_vec();
 
         let mut buf = Vec::with_capacity(length);
-        let mut enc = ZlibEncoder::new(&mut buf, Compression::best());
+        let mut enc = ZlibEncoder::new(&mut buf, Compression::new(level.min(9)));
         // Compress from `offset` and store the result into the temporary `buf`
-        enc.write_all(&self.buffer[offset..])?;
+        enc.write_all(&self.buffer[offset..offset + length])?;
         enc.finish()?;
 
         let end_pos = offset + buf.len();
@@ -35,23 +57,52 @@ impl StreamWriter {
         out[end_pos..].copy_from_slice(&after);
         Ok(())
     }
-    #[cfg(not(feature = "flate2"))]
-    pub fn deflate_zlib(&mut self, _offset: usize, _length: usize) -> Result<()> {
-        use crate::error::RabcError;
-        Err(Error::unsupported_compression("zlib"))
+    #[cfg(all(feature = "miniz_oxide", not(feature = "flate2")))]
+    pub fn deflate_zlib_with_level(
+        &mut self,
+        offset: usize,
+        length: usize,
+        level: u32,
+    ) -> Result<()> {
+        use miniz_oxide::deflate::compress_to_vec_zlib;
+
+        let buf = compress_to_vec_zlib(&self.buffer[offset..offset + length], level.min(9) as u8);
+        self.buffer.splice(offset..offset + length, buf);
+        Ok(())
+    }
+    #[cfg(not(any(feature = "flate2", feature = "miniz_oxide")))]
+    pub fn deflate_zlib_with_level(
+        &mut self,
+        _offset: usize,
+        _length: usize,
+        _level: u32,
+    ) -> Result<()> {
+        Err(crate::error::RabcError::UnsupportedCompression("zlib"))
     }
 
-    #[cfg(feature = "lzma-rs")]
     pub fn deflate_lzma(&mut self, offset: usize, length: usize) -> Result<()> {
+        self.deflate_lzma_with_dict_size(offset, length, LZMA_DICT_SIZE)
+    }
+    /// Compress with LZMA, announcing the given dictionary size to the decoder.
+    /// The encoder of lzma-rs only writes literals, so the dictionary size doesn't change the
+    /// compressed size, only the memory used to decompress the movie, and there is no preset.
+    #[cfg(feature = "lzma-rs")]
+    pub fn deflate_lzma_with_dict_size(
+        &mut self,
+        offset: usize,
+        length: usize,
+        dict_size: u32,
+    ) -> Result<()> {
         use lzma_rs::lzma_compress;
         use std::io::Cursor;
         // Copy anything after the data we want to compress
-        let mut after = Vec::with_capacity(self.len() - offset - length);
-        after.clone_from_slice(&self.buffer[offset + length..]);
+        let after = self.buffer[offset + length..].to_vec();
 
         let mut buf = Vec::with_capacity(length);
         let mut cur = Cursor::new(&self.buffer[offset..offset + length]);
         lzma_compress(&mut cur, &mut buf)?;
+        // Properties byte followed by the dictionary size
+        buf[1..5].copy_from_slice(&dict_size.to_le_bytes());
 
         let end_pos = offset + buf.len() - 4;
         // Resize the buffer to the new size
@@ -59,9 +110,8 @@ impl StreamWriter {
 
         // Copy the compressed data into the buffer, while keeping the start & end of the buffer
         // Write the compressed size to respect the mangled header
-        let mut size = Vec::with_capacity(4);
+        let size = (buf.len() as u32 - 13).to_le_bytes();
         let b = &mut self.buffer;
-        size.write_u32::<LittleEndian>(buf.len() as u32 - 13)?;
 
         // Shitty mangled header ...
         b[offset..offset + 4].copy_from_slice(&size);
@@ -71,9 +121,13 @@ impl StreamWriter {
         Ok(())
     }
     #[cfg(not(feature = "lzma-rs"))]
-    pub fn deflate_lzma(&mut self, _offset: usize, _length: usize) -> Result<()> {
-        use crate::error::RabcError;
-        Err(Error::unsupported_compression("lzma"))
+    pub fn deflate_lzma_with_dict_size(
+        &mut self,
+        _offset: usize,
+        _length: usize,
+        _dict_size: u32,
+    ) -> Result<()> {
+        Err(crate::error::RabcError::UnsupportedCompression("lzma"))
     }
 
     #[inline]
@@ -86,53 +140,96 @@ impl StreamWriter {
         self.buffer
     }
 
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[inline]
+    pub fn copy(&self) -> Result<Self> {
+        Ok(StreamWriter::new(self.buffer.clone()))
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn to_file<W: Write>(&self, mut file: W) -> Result<()> {
+        Ok(file.write_all(&self.buffer)?)
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    pub(crate) fn write_u32_at(&mut self, value: u32, offset: usize) -> Result<()> {
+        self.buffer[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+        Ok(())
+    }
+}
+
+impl<W: Write> StreamWriter<W> {
+    pub fn from_writer(writer: W) -> Self {
+        Self { buffer: writer }
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.buffer
+    }
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.buffer
+    }
+    pub fn into_inner(self) -> W {
+        self.buffer
+    }
+
     #[inline]
     pub fn write_u8(&mut self, value: u8) -> Result<()> {
-        Ok(self.buffer.write_u8(value)?)
+        self.write_exact(&[value])
     }
     #[inline]
     pub fn write_u16(&mut self, value: u16) -> Result<()> {
-        Ok(self.buffer.write_u16::<LittleEndian>(value)?)
+        self.write_exact(&value.to_le_bytes())
     }
     #[inline]
     pub fn write_u24(&mut self, value: u32) -> Result<()> {
-        Ok(self.buffer.write_u24::<LittleEndian>(value)?)
+        self.write_exact(&value.to_le_bytes()[..3])
     }
     #[inline]
     pub fn write_u32(&mut self, value: u32) -> Result<()> {
-        Ok(self.buffer.write_u32::<LittleEndian>(value)?)
+        self.write_exact(&value.to_le_bytes())
     }
     #[inline]
     pub fn write_u64(&mut self, value: u64) -> Result<()> {
-        Ok(self.buffer.write_u64::<LittleEndian>(value)?)
+        self.write_exact(&value.to_le_bytes())
     }
     #[inline]
     pub fn write_i8(&mut self, value: i8) -> Result<()> {
-        Ok(self.buffer.write_i8(value)?)
+        self.write_exact(&value.to_le_bytes())
     }
     #[inline]
     pub fn write_i16(&mut self, value: i16) -> Result<()> {
-        Ok(self.buffer.write_i16::<LittleEndian>(value)?)
+        self.write_exact(&value.to_le_bytes())
     }
     #[inline]
     pub fn write_i24(&mut self, value: i32) -> Result<()> {
-        Ok(self.buffer.write_i24::<LittleEndian>(value)?)
+        self.write_exact(&value.to_le_bytes()[..3])
     }
     #[inline]
     pub fn write_i32(&mut self, value: i32) -> Result<()> {
-        Ok(self.buffer.write_i32::<LittleEndian>(value)?)
+        self.write_exact(&value.to_le_bytes())
     }
     #[inline]
     pub fn write_i64(&mut self, value: i64) -> Result<()> {
-        Ok(self.buffer.write_i64::<LittleEndian>(value)?)
+        self.write_exact(&value.to_le_bytes())
     }
     #[inline]
     pub fn write_float(&mut self, value: f32) -> Result<()> {
-        Ok(self.buffer.write_f32::<LittleEndian>(value)?)
+        self.write_exact(&value.to_le_bytes())
     }
     #[inline]
     pub fn write_double(&mut self, value: f64) -> Result<()> {
-        Ok(self.buffer.write_f64::<LittleEndian>(value)?)
+        self.write_exact(&value.to_le_bytes())
     }
 
     // Read a variable-length unsigned integer. See https://en.wikipedia.org/wiki/LEB128 for more informations.
@@ -142,9 +239,9 @@ impl StreamWriter {
             let byte = (value & 0x7F) as u8;
             value >>= 7;
             if value != 0 {
-                self.buffer.push(byte | 0x80);
+                self.write_u8(byte | 0x80)?;
             } else {
-                self.buffer.push(byte);
+                self.write_u8(byte)?;
                 break;
             }
         }
@@ -165,7 +262,7 @@ impl StreamWriter {
     #[inline]
     pub fn write_null_string(&mut self, value: &String) -> Result<()> {
         self.write_exact(value.as_bytes())?;
-        self.buffer.push(0);
+        self.write_u8(0)?;
         Ok(())
     }
     #[inline]
@@ -180,42 +277,33 @@ impl StreamWriter {
     pub fn write_stream(&mut self, value: &StreamWriter) -> Result<()> {
         self.write_exact(&value.buffer)
     }
+}
 
+impl<W: Write + Seek> StreamWriter<W> {
+    /// Position in the underlying writer
     #[inline]
-    pub fn len(&self) -> usize {
-        self.buffer.len()
-    }
-    #[inline]
-    pub fn is_empty(&self) -> bool {
-        self.len() == 0
-    }
-
-    #[inline]
-    pub fn copy(&self) -> Result<Self> {
-        Ok(StreamWriter::new(self.buffer.clone()))
-    }
-
-    #[inline]
-    pub fn to_file(&self, mut file: std::fs::File) -> Result<()> {
-        Ok(file.write_all(&self.buffer)?)
+    pub fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.buffer.stream_position()?)
     }
 
-    #[inline]
-    pub(crate) fn write_u32_at(&mut self, value: u32, offset: usize) -> Result<()> {
-        let mut buf = &mut self.buffer[offset..offset + 4];
-        buf.write_u32::<LittleEndian>(value)?;
+    /// Overwrite a value previously written at `offset`, then go back to the current position
+    pub fn patch_u32(&mut self, value: u32, offset: u64) -> Result<()> {
+        let pos = self.buffer.stream_position()?;
+        self.buffer.seek(SeekFrom::Start(offset))?;
+        self.write_u32(value)?;
+        self.buffer.seek(SeekFrom::Start(pos))?;
         Ok(())
     }
 }
 
-impl Write for StreamWriter {
+impl<W: Write> Write for StreamWriter<W> {
     #[inline]
-    fn write(&mut self, buf: &[u8]) -> std::result::Result<usize, std::io::Error> {
+    fn write(&mut self, buf: &[u8]) -> crate::io::Result<usize> {
         self.buffer.write(buf)
     }
 
     #[inline]
-    fn flush(&mut self) -> std::io::Result<()> {
+    fn flush(&mut self) -> crate::io::Result<()> {
         self.buffer.flush()
     }
 }
@@ -313,4 +401,13 @@ mod tests {
         stream.write_i30(-69420).unwrap();
         assert_eq!(stream.buffer, vec![212, 225, 251, 255, 15]);
     }
+    #[test]
+    pub fn test_from_writer() {
+        let mut stream = StreamWriter::from_writer(crate::io::Cursor::new(Vec::new()));
+        stream.write_u32(0).unwrap();
+        stream.write_u30(0x80).unwrap();
+        stream.patch_u32(0x01020304, 0).unwrap();
+        assert_eq!(stream.stream_position().unwrap(), 6);
+        assert_eq!(stream.into_inner().into_inner(), vec![4, 3, 2, 1, 0x80, 1]);
+    }
 }
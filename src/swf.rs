@@ -1,5 +1,12 @@
-mod datatypes;
 mod movie;
+mod resolver;
+mod tag_reader;
 
+pub mod crypt;
+pub mod datatypes;
+pub mod export;
+pub mod swd;
 pub mod tags;
-pub use movie::{Compression, Movie};
+pub use movie::{Compression, Header, Movie, Preserved, PreservedTag, ReadOptions, WriteOptions};
+pub use resolver::{ImportResolver, ResolvedImport};
+pub use tag_reader::{TagHeader, TagReader};
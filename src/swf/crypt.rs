@@ -0,0 +1,279 @@
+//! Password hashes used by the Protect, EnableDebugger(2) and EnableTelemetry tags.
+//! Protect and EnableDebugger use MD5-crypt, EnableTelemetry uses SHA-256.
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+};
+
+const MD5_CRYPT_MAGIC: &str = "$1$";
+const ITOA64: &[u8; 64] = b"./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+const MD5_SHIFTS: [u32; 64] = [
+    7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9,
+    14, 20, 5, 9, 14, 20, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 6, 10, 15,
+    21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+];
+
+const MD5_K: [u32; 64] = [
+    0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee, 0xf57c0faf, 0x4787c62a, 0xa8304613, 0xfd469501,
+    0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be, 0x6b901122, 0xfd987193, 0xa679438e, 0x49b40821,
+    0xf61e2562, 0xc040b340, 0x265e5a51, 0xe9b6c7aa, 0xd62f105d, 0x02441453, 0xd8a1e681, 0xe7d3fbc8,
+    0x21e1cde6, 0xc33707d6, 0xf4d50d87, 0x455a14ed, 0xa9e3e905, 0xfcefa3f8, 0x676f02d9, 0x8d2a4c8a,
+    0xfffa3942, 0x8771f681, 0x6d9d6122, 0xfde5380c, 0xa4beea44, 0x4bdecfa9, 0xf6bb4b60, 0xbebfbc70,
+    0x289b7ec6, 0xeaa127fa, 0xd4ef3085, 0x04881d05, 0xd9d4d039, 0xe6db99e5, 0x1fa27cf8, 0xc4ac5665,
+    0xf4292244, 0x432aff97, 0xab9423a7, 0xfc93a039, 0x655b59c3, 0x8f0ccc92, 0xffeff47d, 0x85845dd1,
+    0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1, 0xf7537e82, 0xbd3af235, 0x2ad7d2bb, 0xeb86d391,
+];
+
+const SHA256_K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// Pad the message to a multiple of 64 bytes, ending with its length in bits
+fn pad_message(data: &[u8], big_endian: bool) -> Vec<u8> {
+    let bit_len = (data.len() as u64).wrapping_mul(8);
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    if big_endian {
+        message.extend_from_slice(&bit_len.to_be_bytes());
+    } else {
+        message.extend_from_slice(&bit_len.to_le_bytes());
+    }
+    message
+}
+
+/// MD5 digest of the data
+pub fn md5(data: &[u8]) -> [u8; 16] {
+    let mut state: [u32; 4] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+    for chunk in pad_message(data, false).chunks_exact(64) {
+        let words: Vec<u32> = chunk
+            .chunks_exact(4)
+            .map(|w| u32::from_le_bytes([w[0], w[1], w[2], w[3]]))
+            .collect();
+        let [mut a, mut b, mut c, mut d] = state;
+        for (i, (shift, k)) in MD5_SHIFTS.iter().zip(MD5_K).enumerate() {
+            let (f, g) = match i / 16 {
+                0 => ((b & c) | (!b & d), i),
+                1 => ((d & b) | (!d & c), (5 * i + 1) % 16),
+                2 => (b ^ c ^ d, (3 * i + 5) % 16),
+                _ => (c ^ (b | !d), (7 * i) % 16),
+            };
+            let f = f.wrapping_add(a).wrapping_add(k).wrapping_add(words[g]);
+            a = d;
+            d = c;
+            c = b;
+            b = b.wrapping_add(f.rotate_left(*shift));
+        }
+        for (s, v) in state.iter_mut().zip([a, b, c, d]) {
+            *s = s.wrapping_add(v);
+        }
+    }
+
+    let mut digest = [0u8; 16];
+    for (out, s) in digest.chunks_exact_mut(4).zip(state) {
+        out.copy_from_slice(&s.to_le_bytes());
+    }
+    digest
+}
+
+/// SHA-256 digest of the data
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    let mut state: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+        0x5be0cd19,
+    ];
+    for chunk in pad_message(data, true).chunks_exact(64) {
+        let mut w = [0u32; 64];
+        for (i, word) in chunk.chunks_exact(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[i - 7])
+                .wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+        for (k, w) in SHA256_K.iter().zip(w) {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let t1 = h
+                .wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(*k)
+                .wrapping_add(w);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(maj);
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+        for (s, v) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *s = s.wrapping_add(v);
+        }
+    }
+
+    let mut digest = [0u8; 32];
+    for (out, s) in digest.chunks_exact_mut(4).zip(state) {
+        out.copy_from_slice(&s.to_be_bytes());
+    }
+    digest
+}
+
+/// Generate a random 8 characters salt for MD5-crypt
+pub fn generate_salt() -> String {
+    let mut bits = RandomState::new().build_hasher().finish();
+    (0..8)
+        .map(|_| {
+            let c = ITOA64[(bits & 0x3F) as usize] as char;
+            bits >>= 6;
+            c
+        })
+        .collect()
+}
+
+/// Hash the password with MD5-crypt, as `$1$salt$hash`.
+/// Only the first 8 characters of the salt are used.
+pub fn md5_crypt(password: &str, salt: &str) -> String {
+    let password = password.as_bytes();
+    let salt = salt.strip_prefix(MD5_CRYPT_MAGIC).unwrap_or(salt);
+    let salt = salt.split('$').next().unwrap_or_default().as_bytes();
+    let salt = &salt[..salt.len().min(8)];
+
+    let alternate = md5(&[password, salt, password].concat());
+    let mut ctx = [password, MD5_CRYPT_MAGIC.as_bytes(), salt].concat();
+    let mut i = password.len();
+    while i > 0 {
+        ctx.extend_from_slice(&alternate[..i.min(16)]);
+        i = i.saturating_sub(16);
+    }
+    let mut i = password.len();
+    while i > 0 {
+        if i & 1 != 0 {
+            ctx.push(0);
+        } else {
+            ctx.push(password[0]);
+        }
+        i >>= 1;
+    }
+    let mut digest = md5(&ctx);
+
+    // Slow down brute force attacks
+    for i in 0..1000 {
+        let mut ctx = Vec::with_capacity(password.len() * 2 + salt.len() + 16);
+        if i & 1 != 0 {
+            ctx.extend_from_slice(password);
+        } else {
+            ctx.extend_from_slice(&digest);
+        }
+        if i % 3 != 0 {
+            ctx.extend_from_slice(salt);
+        }
+        if i % 7 != 0 {
+            ctx.extend_from_slice(password);
+        }
+        if i & 1 != 0 {
+            ctx.extend_from_slice(&digest);
+        } else {
+            ctx.extend_from_slice(password);
+        }
+        digest = md5(&ctx);
+    }
+
+    let mut hash = String::with_capacity(34);
+    hash.push_str(MD5_CRYPT_MAGIC);
+    hash.push_str(&String::from_utf8_lossy(salt));
+    hash.push('$');
+    for (a, b, c) in [(0, 6, 12), (1, 7, 13), (2, 8, 14), (3, 9, 15), (4, 10, 5)] {
+        let value = ((digest[a] as u32) << 16) | ((digest[b] as u32) << 8) | digest[c] as u32;
+        push_base64(&mut hash, value, 4);
+    }
+    push_base64(&mut hash, digest[11] as u32, 2);
+    hash
+}
+
+fn push_base64(out: &mut String, mut value: u32, count: usize) {
+    for _ in 0..count {
+        out.push(ITOA64[(value & 0x3F) as usize] as char);
+        value >>= 6;
+    }
+}
+
+/// Check a password against an MD5-crypt hash. Returns false if the hash is malformed.
+pub fn md5_crypt_verify(password: &str, hash: &str) -> bool {
+    match hash.strip_prefix(MD5_CRYPT_MAGIC) {
+        Some(rest) if rest.contains('$') => md5_crypt(password, rest) == hash,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(digest: &[u8]) -> String {
+        digest.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn digests() {
+        assert_eq!(hex(&md5(b"")), "d41d8cd98f00b204e9800998ecf8427e");
+        assert_eq!(hex(&md5(b"abc")), "900150983cd24fb0d6963f7d28e17f72");
+        assert_eq!(
+            hex(&sha256(b"abc")),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        // Messages spanning two blocks
+        assert_eq!(hex(&md5(&[b'a'; 64])), "014842d480b571495a4a0363793f7367");
+        assert_eq!(
+            hex(&sha256(&[b'a'; 64])),
+            "ffe054fe7ae0cb6dc65c3af9b61d5209f439851db43d0ba5997337df154668eb"
+        );
+    }
+
+    #[test]
+    fn md5_crypt_hashes() {
+        assert_eq!(
+            md5_crypt("password", "saltsalt"),
+            "$1$saltsalt$qjXMvbEw8oaL.CzflDtaK/"
+        );
+        assert_eq!(md5_crypt("", "ab"), "$1$ab$rn6aQS/o7141mj179E/zA.");
+        assert_eq!(
+            md5_crypt("a much longer password that exceeds sixteen bytes", "x"),
+            "$1$x$uHfyW0UiW4REjJOAce9790"
+        );
+
+        assert!(md5_crypt_verify(
+            "password",
+            "$1$saltsalt$qjXMvbEw8oaL.CzflDtaK/"
+        ));
+        assert!(!md5_crypt_verify(
+            "Password",
+            "$1$saltsalt$qjXMvbEw8oaL.CzflDtaK/"
+        ));
+        assert!(!md5_crypt_verify("password", "qjXMvbEw8oaL.CzflDtaK/"));
+
+        let salt = generate_salt();
+        assert_eq!(salt.len(), 8);
+        assert!(md5_crypt_verify("secret", &md5_crypt("secret", &salt)));
+    }
+}
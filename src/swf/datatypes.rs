@@ -1,7 +1,26 @@
-use crate::error::Result;
+mod filter;
+mod fixed;
+mod shape;
+mod sound;
+mod style;
+
+use crate::error::{RabcError, Result};
 use crate::stream::{BitStreamReader, BitStreamWriter, StreamReader, StreamWriter};
 use std::fmt;
 
+pub use filter::{
+    BevelFilter, BlendMode, BlurFilter, ColorMatrixFilter, ConvolutionFilter, DropShadowFilter,
+    Filter, GlowFilter, GradientFilter, GradientStop,
+};
+pub use fixed::{Fixed16, Fixed8, Float16, UFixed8};
+pub use shape::{
+    CurvedEdgeRecord, Shape, ShapeRecord, StraightEdgeRecord, StyleChangeRecord, StyledShape,
+};
+pub use sound::{AudioCompression, SoundEnvelope, SoundFormat, SoundInfo, SoundRate};
+pub use style::{
+    CapStyle, FillStyle, Gradient, InterpolationMode, JoinStyle, LineStyle, SpreadMode,
+};
+
 const TWIPS: i32 = 20;
 
 #[derive(Clone, Debug, PartialEq, Default)]
@@ -16,6 +35,32 @@ pub struct Rect {
     pub max: Position,
 }
 
+/// 2x3 affine transformation matrix.
+/// Translation is in twips.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Matrix {
+    pub scale_x: Fixed16,
+    pub scale_y: Fixed16,
+    pub rotate_skew0: Fixed16,
+    pub rotate_skew1: Fixed16,
+    pub translate_x: i32,
+    pub translate_y: i32,
+}
+
+/// Color transformation, applied as `color * mult + add` to each channel.
+/// The alpha terms are only written by tags supporting transparency.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColorTransform {
+    pub red_mult: Fixed8,
+    pub green_mult: Fixed8,
+    pub blue_mult: Fixed8,
+    pub alpha_mult: Fixed8,
+    pub red_add: i16,
+    pub green_add: i16,
+    pub blue_add: i16,
+    pub alpha_add: i16,
+}
+
 #[derive(Clone, Debug, PartialEq, Default)]
 pub struct Rgb {
     pub r: u8,
@@ -50,7 +95,7 @@ impl Rect {
         let mut bs = BitStreamWriter::new(stream);
 
         let n_bits = *[self.min.x, self.min.y, self.max.x, self.max.y]
-            .map(BitStreamWriter::calc_sbits)
+            .map(BitStreamWriter::min_sbits)
             .iter()
             .max()
             .unwrap();
@@ -65,6 +110,171 @@ impl Rect {
     }
 }
 
+impl Default for Matrix {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Matrix {
+    pub const IDENTITY: Self = Self {
+        scale_x: Fixed16::ONE,
+        scale_y: Fixed16::ONE,
+        rotate_skew0: Fixed16::ZERO,
+        rotate_skew1: Fixed16::ZERO,
+        translate_x: 0,
+        translate_y: 0,
+    };
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn read(stream: &mut StreamReader) -> Result<Self> {
+        let mut bs = BitStreamReader::new(stream);
+        let mut matrix = Self::IDENTITY;
+
+        if bs.read_bit()? {
+            let n_bits = bs.read_ub(5)? as u8;
+            matrix.scale_x = Fixed16(bs.read_sb(n_bits)?);
+            matrix.scale_y = Fixed16(bs.read_sb(n_bits)?);
+        }
+        if bs.read_bit()? {
+            let n_bits = bs.read_ub(5)? as u8;
+            matrix.rotate_skew0 = Fixed16(bs.read_sb(n_bits)?);
+            matrix.rotate_skew1 = Fixed16(bs.read_sb(n_bits)?);
+        }
+        let n_bits = bs.read_ub(5)? as u8;
+        matrix.translate_x = bs.read_sb(n_bits)?;
+        matrix.translate_y = bs.read_sb(n_bits)?;
+        Ok(matrix)
+    }
+    pub fn write(&self, stream: &mut StreamWriter) -> Result<()> {
+        let mut bs = BitStreamWriter::new(stream);
+        let has_scale = self.scale_x != Fixed16::ONE || self.scale_y != Fixed16::ONE;
+        let has_rotate = self.rotate_skew0 != Fixed16::ZERO || self.rotate_skew1 != Fixed16::ZERO;
+
+        bs.write_bit(has_scale)?;
+        if has_scale {
+            Self::write_pair(&mut bs, self.scale_x.0, self.scale_y.0)?;
+        }
+        bs.write_bit(has_rotate)?;
+        if has_rotate {
+            Self::write_pair(&mut bs, self.rotate_skew0.0, self.rotate_skew1.0)?;
+        }
+        Self::write_pair(&mut bs, self.translate_x, self.translate_y)?;
+        bs.flush()?;
+        Ok(())
+    }
+
+    fn write_pair(bs: &mut BitStreamWriter, a: i32, b: i32) -> Result<()> {
+        let n_bits = BitStreamWriter::min_sbits(a).max(BitStreamWriter::min_sbits(b));
+        bs.write_ub(5, n_bits.into())?;
+        bs.write_sb(n_bits, a)?;
+        bs.write_sb(n_bits, b)
+    }
+}
+
+impl Default for ColorTransform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl ColorTransform {
+    pub const IDENTITY: Self = Self {
+        red_mult: Fixed8::ONE,
+        green_mult: Fixed8::ONE,
+        blue_mult: Fixed8::ONE,
+        alpha_mult: Fixed8::ONE,
+        red_add: 0,
+        green_add: 0,
+        blue_add: 0,
+        alpha_add: 0,
+    };
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn read(stream: &mut StreamReader) -> Result<Self> {
+        Self::read_terms(stream, false)
+    }
+    pub fn read_with_alpha(stream: &mut StreamReader) -> Result<Self> {
+        Self::read_terms(stream, true)
+    }
+    pub fn write(&self, stream: &mut StreamWriter) -> Result<()> {
+        self.write_terms(stream, false)
+    }
+    pub fn write_with_alpha(&self, stream: &mut StreamWriter) -> Result<()> {
+        self.write_terms(stream, true)
+    }
+
+    fn read_terms(stream: &mut StreamReader, with_alpha: bool) -> Result<Self> {
+        let mut bs = BitStreamReader::new(stream);
+        let mut cxform = Self::IDENTITY;
+        let has_add = bs.read_bit()?;
+        let has_mult = bs.read_bit()?;
+        let n_bits = bs.read_ub(4)? as u8;
+
+        if has_mult {
+            cxform.red_mult = Fixed8(bs.read_sb(n_bits)? as i16);
+            cxform.green_mult = Fixed8(bs.read_sb(n_bits)? as i16);
+            cxform.blue_mult = Fixed8(bs.read_sb(n_bits)? as i16);
+            if with_alpha {
+                cxform.alpha_mult = Fixed8(bs.read_sb(n_bits)? as i16);
+            }
+        }
+        if has_add {
+            cxform.red_add = bs.read_sb(n_bits)? as i16;
+            cxform.green_add = bs.read_sb(n_bits)? as i16;
+            cxform.blue_add = bs.read_sb(n_bits)? as i16;
+            if with_alpha {
+                cxform.alpha_add = bs.read_sb(n_bits)? as i16;
+            }
+        }
+        Ok(cxform)
+    }
+
+    fn write_terms(&self, stream: &mut StreamWriter, with_alpha: bool) -> Result<()> {
+        let channels = if with_alpha { 4 } else { 3 };
+        let mult = &[
+            self.red_mult.0,
+            self.green_mult.0,
+            self.blue_mult.0,
+            self.alpha_mult.0,
+        ][..channels];
+        let add = &[self.red_add, self.green_add, self.blue_add, self.alpha_add][..channels];
+        let has_mult = mult.iter().any(|&v| v != Fixed8::ONE.0);
+        let has_add = add.iter().any(|&v| v != 0);
+
+        let mut terms = Vec::new();
+        if has_mult {
+            terms.extend_from_slice(mult);
+        }
+        if has_add {
+            terms.extend_from_slice(add);
+        }
+        let n_bits = terms
+            .iter()
+            .map(|&v| BitStreamWriter::min_sbits(v.into()))
+            .max()
+            .unwrap_or(0);
+        if n_bits > 15 {
+            return Err(RabcError::ValueOutOfRange("color transform term"));
+        }
+
+        let mut bs = BitStreamWriter::new(stream);
+        bs.write_bit(has_add)?;
+        bs.write_bit(has_mult)?;
+        bs.write_ub(4, n_bits.into())?;
+        for term in terms {
+            bs.write_sb(n_bits, term.into())?;
+        }
+        bs.flush()
+    }
+}
+
 impl Rgb {
     pub fn read(stream: &mut StreamReader) -> Result<Self> {
         Ok(Self {
@@ -82,7 +292,6 @@ impl Rgb {
     }
 }
 impl Rgba {
-    #[allow(dead_code)]
     pub fn read(stream: &mut StreamReader) -> Result<Self> {
         let rgb = Rgb::read(stream)?;
         Ok(Self {
@@ -91,7 +300,6 @@ impl Rgba {
         })
     }
 
-    #[allow(dead_code)]
     pub fn write(&self, stream: &mut StreamWriter) -> Result<()> {
         self.rgb.write(stream)?;
         stream.write_u8(self.a)
@@ -126,3 +334,27 @@ impl fmt::Display for Rgba {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rect_round_trip() {
+        // Positive powers of two need a sign bit on top of their magnitude
+        for (x, y) in [(0, 0), (1, -1), (8, -8), (16384, -16384), (11000, 8000)] {
+            let rect = Rect {
+                min: Position { x: -y, y: -x },
+                max: Position { x, y },
+            };
+            let mut stream = StreamWriter::default();
+            rect.write(&mut stream).unwrap();
+            let mut reader = StreamReader::new(stream.buffer());
+            assert_eq!(Rect::read(&mut reader).unwrap(), rect);
+        }
+
+        let mut stream = StreamWriter::default();
+        Rect::new().write(&mut stream).unwrap();
+        assert_eq!(*stream.buffer(), [0x00]);
+    }
+}
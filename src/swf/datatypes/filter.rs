@@ -0,0 +1,512 @@
+use super::{Fixed16, Fixed8, Rgba};
+use crate::error::{RabcError, Result};
+use crate::stream::{StreamReader, StreamWriter};
+
+const INNER: u8 = 0x80;
+const KNOCKOUT: u8 = 0x40;
+const COMPOSITE_SOURCE: u8 = 0x20;
+const ON_TOP: u8 = 0x10;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum BlendMode {
+    #[default]
+    Normal = 1,
+    Layer = 2,
+    Multiply = 3,
+    Screen = 4,
+    Lighten = 5,
+    Darken = 6,
+    Difference = 7,
+    Add = 8,
+    Subtract = 9,
+    Invert = 10,
+    Alpha = 11,
+    Erase = 12,
+    Overlay = 13,
+    HardLight = 14,
+}
+
+/// Graphic filter applied to a display object.
+/// Angles are in radians, blur and distance values in pixels.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Filter {
+    DropShadow(DropShadowFilter),
+    Blur(BlurFilter),
+    Glow(GlowFilter),
+    Bevel(BevelFilter),
+    GradientGlow(GradientFilter),
+    Convolution(ConvolutionFilter),
+    ColorMatrix(ColorMatrixFilter),
+    GradientBevel(GradientFilter),
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct DropShadowFilter {
+    pub color: Rgba,
+    pub blur_x: Fixed16,
+    pub blur_y: Fixed16,
+    pub angle: Fixed16,
+    pub distance: Fixed16,
+    pub strength: Fixed8,
+    pub inner_shadow: bool,
+    pub knockout: bool,
+    pub composite_source: bool,
+    pub passes: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct BlurFilter {
+    pub blur_x: Fixed16,
+    pub blur_y: Fixed16,
+    pub passes: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct GlowFilter {
+    pub color: Rgba,
+    pub blur_x: Fixed16,
+    pub blur_y: Fixed16,
+    pub strength: Fixed8,
+    pub inner_glow: bool,
+    pub knockout: bool,
+    pub composite_source: bool,
+    pub passes: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct BevelFilter {
+    pub shadow_color: Rgba,
+    pub highlight_color: Rgba,
+    pub blur_x: Fixed16,
+    pub blur_y: Fixed16,
+    pub angle: Fixed16,
+    pub distance: Fixed16,
+    pub strength: Fixed8,
+    pub inner_shadow: bool,
+    pub knockout: bool,
+    pub composite_source: bool,
+    pub on_top: bool,
+    pub passes: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct GradientStop {
+    pub color: Rgba,
+    pub ratio: u8,
+}
+
+/// Gradient glow or gradient bevel filter
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct GradientFilter {
+    pub stops: Vec<GradientStop>,
+    pub blur_x: Fixed16,
+    pub blur_y: Fixed16,
+    pub angle: Fixed16,
+    pub distance: Fixed16,
+    pub strength: Fixed8,
+    pub inner_shadow: bool,
+    pub knockout: bool,
+    pub composite_source: bool,
+    pub on_top: bool,
+    pub passes: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct ConvolutionFilter {
+    pub matrix_x: u8,
+    pub matrix_y: u8,
+    pub divisor: f32,
+    pub bias: f32,
+    /// Row-major matrix of `matrix_x * matrix_y` values
+    pub matrix: Vec<f32>,
+    pub default_color: Rgba,
+    pub clamp: bool,
+    pub preserve_alpha: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct ColorMatrixFilter {
+    pub matrix: [f32; 20],
+}
+
+impl BlendMode {
+    pub fn from_u8(value: u8) -> Self {
+        match value {
+            2 => Self::Layer,
+            3 => Self::Multiply,
+            4 => Self::Screen,
+            5 => Self::Lighten,
+            6 => Self::Darken,
+            7 => Self::Difference,
+            8 => Self::Add,
+            9 => Self::Subtract,
+            10 => Self::Invert,
+            11 => Self::Alpha,
+            12 => Self::Erase,
+            13 => Self::Overlay,
+            14 => Self::HardLight,
+            _ => Self::Normal,
+        }
+    }
+}
+
+impl Filter {
+    pub fn read(stream: &mut StreamReader) -> Result<Self> {
+        let filter_id = stream.read_u8()?;
+        Ok(match filter_id {
+            0 => {
+                let color = Rgba::read(stream)?;
+                let blur_x = Fixed16::read(stream)?;
+                let blur_y = Fixed16::read(stream)?;
+                let angle = Fixed16::read(stream)?;
+                let distance = Fixed16::read(stream)?;
+                let strength = Fixed8::read(stream)?;
+                let flags = stream.read_u8()?;
+                Self::DropShadow(DropShadowFilter {
+                    color,
+                    blur_x,
+                    blur_y,
+                    angle,
+                    distance,
+                    strength,
+                    inner_shadow: flags & INNER != 0,
+                    knockout: flags & KNOCKOUT != 0,
+                    composite_source: flags & COMPOSITE_SOURCE != 0,
+                    passes: flags & 0x1F,
+                })
+            }
+            1 => {
+                let blur_x = Fixed16::read(stream)?;
+                let blur_y = Fixed16::read(stream)?;
+                let flags = stream.read_u8()?;
+                Self::Blur(BlurFilter {
+                    blur_x,
+                    blur_y,
+                    passes: flags >> 3,
+                })
+            }
+            2 => {
+                let color = Rgba::read(stream)?;
+                let blur_x = Fixed16::read(stream)?;
+                let blur_y = Fixed16::read(stream)?;
+                let strength = Fixed8::read(stream)?;
+                let flags = stream.read_u8()?;
+                Self::Glow(GlowFilter {
+                    color,
+                    blur_x,
+                    blur_y,
+                    strength,
+                    inner_glow: flags & INNER != 0,
+                    knockout: flags & KNOCKOUT != 0,
+                    composite_source: flags & COMPOSITE_SOURCE != 0,
+                    passes: flags & 0x1F,
+                })
+            }
+            3 => {
+                let shadow_color = Rgba::read(stream)?;
+                let highlight_color = Rgba::read(stream)?;
+                let blur_x = Fixed16::read(stream)?;
+                let blur_y = Fixed16::read(stream)?;
+                let angle = Fixed16::read(stream)?;
+                let distance = Fixed16::read(stream)?;
+                let strength = Fixed8::read(stream)?;
+                let flags = stream.read_u8()?;
+                Self::Bevel(BevelFilter {
+                    shadow_color,
+                    highlight_color,
+                    blur_x,
+                    blur_y,
+                    angle,
+                    distance,
+                    strength,
+                    inner_shadow: flags & INNER != 0,
+                    knockout: flags & KNOCKOUT != 0,
+                    composite_source: flags & COMPOSITE_SOURCE != 0,
+                    on_top: flags & ON_TOP != 0,
+                    passes: flags & 0x0F,
+                })
+            }
+            4 => Self::GradientGlow(GradientFilter::read(stream)?),
+            5 => {
+                let matrix_x = stream.read_u8()?;
+                let matrix_y = stream.read_u8()?;
+                let divisor = stream.read_float()?;
+                let bias = stream.read_float()?;
+                let count = usize::from(matrix_x) * usize::from(matrix_y);
+                let mut matrix = Vec::with_capacity(count);
+                for _ in 0..count {
+                    matrix.push(stream.read_float()?);
+                }
+                let default_color = Rgba::read(stream)?;
+                let flags = stream.read_u8()?;
+                Self::Convolution(ConvolutionFilter {
+                    matrix_x,
+                    matrix_y,
+                    divisor,
+                    bias,
+                    matrix,
+                    default_color,
+                    clamp: flags & 0x02 != 0,
+                    preserve_alpha: flags & 0x01 != 0,
+                })
+            }
+            6 => {
+                let mut matrix = [0.0; 20];
+                for value in &mut matrix {
+                    *value = stream.read_float()?;
+                }
+                Self::ColorMatrix(ColorMatrixFilter { matrix })
+            }
+            7 => Self::GradientBevel(GradientFilter::read(stream)?),
+            _ => return Err(RabcError::InvalidFilter(filter_id)),
+        })
+    }
+
+    pub fn write(&self, stream: &mut StreamWriter) -> Result<()> {
+        match self {
+            Self::DropShadow(f) => {
+                stream.write_u8(0)?;
+                f.color.write(stream)?;
+                f.blur_x.write(stream)?;
+                f.blur_y.write(stream)?;
+                f.angle.write(stream)?;
+                f.distance.write(stream)?;
+                f.strength.write(stream)?;
+                stream.write_u8(
+                    flags(f.inner_shadow, f.knockout, f.composite_source, false)
+                        | (f.passes & 0x1F),
+                )?;
+            }
+            Self::Blur(f) => {
+                stream.write_u8(1)?;
+                f.blur_x.write(stream)?;
+                f.blur_y.write(stream)?;
+                stream.write_u8((f.passes & 0x1F) << 3)?;
+            }
+            Self::Glow(f) => {
+                stream.write_u8(2)?;
+                f.color.write(stream)?;
+                f.blur_x.write(stream)?;
+                f.blur_y.write(stream)?;
+                f.strength.write(stream)?;
+                stream.write_u8(
+                    flags(f.inner_glow, f.knockout, f.composite_source, false) | (f.passes & 0x1F),
+                )?;
+            }
+            Self::Bevel(f) => {
+                stream.write_u8(3)?;
+                f.shadow_color.write(stream)?;
+                f.highlight_color.write(stream)?;
+                f.blur_x.write(stream)?;
+                f.blur_y.write(stream)?;
+                f.angle.write(stream)?;
+                f.distance.write(stream)?;
+                f.strength.write(stream)?;
+                stream.write_u8(
+                    flags(f.inner_shadow, f.knockout, f.composite_source, f.on_top)
+                        | (f.passes & 0x0F),
+                )?;
+            }
+            Self::GradientGlow(f) => {
+                stream.write_u8(4)?;
+                f.write(stream)?;
+            }
+            Self::Convolution(f) => {
+                stream.write_u8(5)?;
+                stream.write_u8(f.matrix_x)?;
+                stream.write_u8(f.matrix_y)?;
+                stream.write_float(f.divisor)?;
+                stream.write_float(f.bias)?;
+                for value in &f.matrix {
+                    stream.write_float(*value)?;
+                }
+                f.default_color.write(stream)?;
+                stream.write_u8((u8::from(f.clamp) << 1) | u8::from(f.preserve_alpha))?;
+            }
+            Self::ColorMatrix(f) => {
+                stream.write_u8(6)?;
+                for value in &f.matrix {
+                    stream.write_float(*value)?;
+                }
+            }
+            Self::GradientBevel(f) => {
+                stream.write_u8(7)?;
+                f.write(stream)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Read a list of filters, prefixed by its length
+    pub fn read_list(stream: &mut StreamReader) -> Result<Vec<Self>> {
+        let count = stream.read_u8()?;
+        let mut filters = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            filters.push(Self::read(stream)?);
+        }
+        Ok(filters)
+    }
+
+    /// Write a list of filters, prefixed by its length
+    pub fn write_list(filters: &[Self], stream: &mut StreamWriter) -> Result<()> {
+        let count =
+            u8::try_from(filters.len()).map_err(|_| RabcError::ValueOutOfRange("filter count"))?;
+        stream.write_u8(count)?;
+        for filter in filters {
+            filter.write(stream)?;
+        }
+        Ok(())
+    }
+}
+
+impl GradientFilter {
+    fn read(stream: &mut StreamReader) -> Result<Self> {
+        let count = stream.read_u8()?;
+        let mut stops = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            stops.push(GradientStop {
+                color: Rgba::read(stream)?,
+                ratio: 0,
+            });
+        }
+        for stop in &mut stops {
+            stop.ratio = stream.read_u8()?;
+        }
+        let blur_x = Fixed16::read(stream)?;
+        let blur_y = Fixed16::read(stream)?;
+        let angle = Fixed16::read(stream)?;
+        let distance = Fixed16::read(stream)?;
+        let strength = Fixed8::read(stream)?;
+        let flags = stream.read_u8()?;
+
+        Ok(Self {
+            stops,
+            blur_x,
+            blur_y,
+            angle,
+            distance,
+            strength,
+            inner_shadow: flags & INNER != 0,
+            knockout: flags & KNOCKOUT != 0,
+            composite_source: flags & COMPOSITE_SOURCE != 0,
+            on_top: flags & ON_TOP != 0,
+            passes: flags & 0x0F,
+        })
+    }
+
+    fn write(&self, stream: &mut StreamWriter) -> Result<()> {
+        let count = u8::try_from(self.stops.len())
+            .map_err(|_| RabcError::ValueOutOfRange("gradient stop count"))?;
+        stream.write_u8(count)?;
+        for stop in &self.stops {
+            stop.color.write(stream)?;
+        }
+        for stop in &self.stops {
+            stream.write_u8(stop.ratio)?;
+        }
+        self.blur_x.write(stream)?;
+        self.blur_y.write(stream)?;
+        self.angle.write(stream)?;
+        self.distance.write(stream)?;
+        self.strength.write(stream)?;
+        stream.write_u8(
+            flags(
+                self.inner_shadow,
+                self.knockout,
+                self.composite_source,
+                self.on_top,
+            ) | (self.passes & 0x0F),
+        )
+    }
+}
+
+fn flags(inner: bool, knockout: bool, composite_source: bool, on_top: bool) -> u8 {
+    let mut flags = 0;
+    if inner {
+        flags |= INNER;
+    }
+    if knockout {
+        flags |= KNOCKOUT;
+    }
+    if composite_source {
+        flags |= COMPOSITE_SOURCE;
+    }
+    if on_top {
+        flags |= ON_TOP;
+    }
+    flags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::swf::datatypes::Rgb;
+
+    #[test]
+    fn filters_round_trip() {
+        let filters = vec![
+            Filter::DropShadow(DropShadowFilter {
+                color: Rgba {
+                    rgb: Rgb { r: 0, g: 0, b: 0 },
+                    a: 0x80,
+                },
+                blur_x: Fixed16(4 << 16),
+                blur_y: Fixed16(4 << 16),
+                angle: Fixed16(51471),
+                distance: Fixed16(4 << 16),
+                strength: Fixed8::ONE,
+                knockout: true,
+                passes: 1,
+                ..Default::default()
+            }),
+            Filter::Blur(BlurFilter {
+                blur_x: Fixed16(2 << 16),
+                blur_y: Fixed16(8 << 16),
+                passes: 3,
+            }),
+            Filter::GradientBevel(GradientFilter {
+                stops: vec![
+                    GradientStop {
+                        color: Rgba::default(),
+                        ratio: 0,
+                    },
+                    GradientStop {
+                        color: Rgba {
+                            rgb: Rgb {
+                                r: 255,
+                                g: 255,
+                                b: 255,
+                            },
+                            a: 255,
+                        },
+                        ratio: 255,
+                    },
+                ],
+                on_top: true,
+                passes: 15,
+                ..Default::default()
+            }),
+            Filter::Convolution(ConvolutionFilter {
+                matrix_x: 3,
+                matrix_y: 1,
+                divisor: 1.0,
+                matrix: vec![0.5, 1.0, 0.5],
+                clamp: true,
+                ..Default::default()
+            }),
+            Filter::ColorMatrix(ColorMatrixFilter { matrix: [0.25; 20] }),
+        ];
+
+        let mut stream = StreamWriter::default();
+        Filter::write_list(&filters, &mut stream).unwrap();
+        let mut reader = StreamReader::new(stream.buffer());
+        assert_eq!(Filter::read_list(&mut reader).unwrap(), filters);
+        assert!(reader.finished());
+
+        let filters = vec![filters[0].clone(); 0x100];
+        assert!(matches!(
+            Filter::write_list(&filters, &mut StreamWriter::default()),
+            Err(RabcError::ValueOutOfRange("filter count"))
+        ));
+    }
+}
@@ -0,0 +1,208 @@
+use crate::error::Result;
+use crate::stream::{StreamReader, StreamWriter};
+use std::fmt;
+
+/// Signed 8.8 fixed-point number, stored as its raw bits
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Fixed8(pub i16);
+
+/// Unsigned 8.8 fixed-point number, stored as its raw bits
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct UFixed8(pub u16);
+
+/// Signed 16.16 fixed-point number, stored as its raw bits
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Fixed16(pub i32);
+
+/// Half-precision floating-point number, stored as its raw bits
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub struct Float16(pub u16);
+
+impl Fixed8 {
+    pub const ZERO: Self = Self(0);
+    pub const ONE: Self = Self(1 << 8);
+
+    /// Closest fixed-point value, saturating when out of range
+    pub fn from_f64(value: f64) -> Self {
+        Self((value * 256.0).round() as i16)
+    }
+    pub fn to_f64(self) -> f64 {
+        f64::from(self.0) / 256.0
+    }
+
+    pub fn read(stream: &mut StreamReader) -> Result<Self> {
+        Ok(Self(stream.read_i16()?))
+    }
+    pub fn write(&self, stream: &mut StreamWriter) -> Result<()> {
+        stream.write_i16(self.0)
+    }
+}
+
+impl UFixed8 {
+    pub const ZERO: Self = Self(0);
+    pub const ONE: Self = Self(1 << 8);
+
+    /// Closest fixed-point value, saturating when out of range
+    pub fn from_f64(value: f64) -> Self {
+        Self((value * 256.0).round() as u16)
+    }
+    pub fn to_f64(self) -> f64 {
+        f64::from(self.0) / 256.0
+    }
+
+    pub fn read(stream: &mut StreamReader) -> Result<Self> {
+        Ok(Self(stream.read_u16()?))
+    }
+    pub fn write(&self, stream: &mut StreamWriter) -> Result<()> {
+        stream.write_u16(self.0)
+    }
+}
+
+impl Fixed16 {
+    pub const ZERO: Self = Self(0);
+    pub const ONE: Self = Self(1 << 16);
+
+    /// Closest fixed-point value, saturating when out of range
+    pub fn from_f64(value: f64) -> Self {
+        Self((value * 65536.0).round() as i32)
+    }
+    pub fn to_f64(self) -> f64 {
+        f64::from(self.0) / 65536.0
+    }
+
+    pub fn read(stream: &mut StreamReader) -> Result<Self> {
+        Ok(Self(stream.read_i32()?))
+    }
+    pub fn write(&self, stream: &mut StreamWriter) -> Result<()> {
+        stream.write_i32(self.0)
+    }
+}
+
+impl Float16 {
+    /// Closest half-precision value, rounding to even
+    pub fn from_f32(value: f32) -> Self {
+        let bits = value.to_bits();
+        let sign = ((bits >> 16) & 0x8000) as u16;
+        let exponent = ((bits >> 23) & 0xFF) as i32;
+        let mantissa = bits & 0x7F_FFFF;
+
+        if exponent == 0xFF {
+            let nan = if mantissa != 0 { 0x200 } else { 0 };
+            return Self(sign | 0x7C00 | nan);
+        }
+        let exponent = exponent - 127 + 15;
+        if exponent >= 0x1F {
+            return Self(sign | 0x7C00);
+        }
+        let (half, rest, shift) = if exponent > 0 {
+            let half = ((exponent as u32) << 10) | (mantissa >> 13);
+            (half, mantissa & 0x1FFF, 13)
+        } else if exponent >= -10 {
+            // Subnormal, the implicit leading bit becomes explicit
+            let mantissa = mantissa | 0x80_0000;
+            let shift = (14 - exponent) as u32;
+            (mantissa >> shift, mantissa & ((1 << shift) - 1), shift)
+        } else {
+            return Self(sign);
+        };
+
+        let halfway = 1 << (shift - 1);
+        let round = rest > halfway || (rest == halfway && half & 1 != 0);
+        // A carry out of the mantissa correctly moves to the next exponent
+        Self(sign | (half + u32::from(round)) as u16)
+    }
+    pub fn to_f32(self) -> f32 {
+        let sign = if self.0 & 0x8000 != 0 { -1.0 } else { 1.0 };
+        let exponent = i32::from((self.0 >> 10) & 0x1F);
+        let mantissa = f32::from(self.0 & 0x3FF);
+        sign * match exponent {
+            0 => mantissa * 2f32.powi(-24),
+            0x1F if mantissa == 0.0 => f32::INFINITY,
+            0x1F => f32::NAN,
+            _ => (1024.0 + mantissa) * 2f32.powi(exponent - 25),
+        }
+    }
+    pub fn from_f64(value: f64) -> Self {
+        Self::from_f32(value as f32)
+    }
+    pub fn to_f64(self) -> f64 {
+        self.to_f32().into()
+    }
+
+    pub fn read(stream: &mut StreamReader) -> Result<Self> {
+        Ok(Self(stream.read_u16()?))
+    }
+    pub fn write(&self, stream: &mut StreamWriter) -> Result<()> {
+        stream.write_u16(self.0)
+    }
+}
+
+impl fmt::Display for Fixed8 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_f64())
+    }
+}
+
+impl fmt::Display for UFixed8 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_f64())
+    }
+}
+
+impl fmt::Display for Fixed16 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_f64())
+    }
+}
+
+impl fmt::Display for Float16 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_f32())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_point_conversions() {
+        assert_eq!(Fixed8::from_f64(29.97), Fixed8(29 << 8 | 248));
+        assert_eq!(Fixed8::from_f64(-1.5), Fixed8(-384));
+        assert_eq!(Fixed8(-384).to_f64(), -1.5);
+        assert_eq!(Fixed8::from_f64(1000.0), Fixed8(i16::MAX));
+        assert_eq!(UFixed8::from_f64(200.0), UFixed8(200 << 8));
+        assert_eq!(UFixed8(0xFF80).to_f64(), 255.5);
+        assert_eq!(UFixed8::from_f64(-1.0), UFixed8::ZERO);
+        assert_eq!(Fixed16::from_f64(0.5), Fixed16(0x8000));
+        assert_eq!(Fixed16(-0x18000).to_f64(), -1.5);
+
+        let mut stream = StreamWriter::default();
+        Fixed8(0x1E00).write(&mut stream).unwrap();
+        Fixed16::ONE.write(&mut stream).unwrap();
+        assert_eq!(*stream.buffer(), [0x00, 0x1E, 0x00, 0x00, 0x01, 0x00]);
+        let mut reader = StreamReader::new(stream.buffer());
+        assert_eq!(Fixed8::read(&mut reader).unwrap().to_f64(), 30.0);
+        assert_eq!(Fixed16::read(&mut reader).unwrap(), Fixed16::ONE);
+    }
+
+    #[test]
+    fn half_precision_conversions() {
+        for (value, bits) in [
+            (1.0, 0x3C00),
+            (-2.0, 0xC000),
+            (65504.0, 0x7BFF),
+            (2f32.powi(-24), 0x0001),
+            (2f32.powi(-14), 0x0400),
+            (0.0, 0x0000),
+            (f32::INFINITY, 0x7C00),
+        ] {
+            assert_eq!(Float16::from_f32(value), Float16(bits));
+            assert_eq!(Float16(bits).to_f32(), value);
+        }
+        assert_eq!(Float16::from_f32(0.1), Float16(0x2E66));
+        assert_eq!(Float16::from_f32(65520.0), Float16(0x7C00));
+        assert_eq!(Float16::from_f32(2f32.powi(-26)), Float16(0));
+        assert!(Float16::from_f32(f32::NAN).to_f32().is_nan());
+    }
+}
@@ -0,0 +1,322 @@
+use super::{FillStyle, LineStyle, Position, Rect};
+use crate::error::{RabcError, Result};
+use crate::stream::{BitStreamReader, BitStreamWriter, StreamReader, StreamWriter};
+
+/// A shape without any style definition, as used by font glyphs and morph shapes.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct Shape {
+    pub records: Vec<ShapeRecord>,
+}
+
+/// A shape with its fill and line styles, such as a morph shape at a given ratio.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct StyledShape {
+    pub bounds: Rect,
+    /// Bounds of the shape, without the stroke widths
+    pub edge_bounds: Rect,
+    pub fill_styles: Vec<FillStyle>,
+    pub line_styles: Vec<LineStyle>,
+    pub shape: Shape,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ShapeRecord {
+    StyleChange(StyleChangeRecord),
+    StraightEdge(StraightEdgeRecord),
+    CurvedEdge(CurvedEdgeRecord),
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct StyleChangeRecord {
+    /// Absolute position of the pen, relative to the shape's origin
+    pub move_to: Option<Position>,
+    pub fill_style0: Option<u32>,
+    pub fill_style1: Option<u32>,
+    pub line_style: Option<u32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct StraightEdgeRecord {
+    pub delta_x: i32,
+    pub delta_y: i32,
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct CurvedEdgeRecord {
+    pub control_delta_x: i32,
+    pub control_delta_y: i32,
+    pub anchor_delta_x: i32,
+    pub anchor_delta_y: i32,
+}
+
+const STATE_NEW_STYLES: u32 = 0x10;
+const STATE_LINE_STYLE: u32 = 0x08;
+const STATE_FILL_STYLE1: u32 = 0x04;
+const STATE_FILL_STYLE0: u32 = 0x02;
+const STATE_MOVE_TO: u32 = 0x01;
+
+impl Shape {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn read(stream: &mut StreamReader) -> Result<Self> {
+        let mut bs = BitStreamReader::new(stream);
+        let fill_bits = bs.read_ub(4)? as u8;
+        let line_bits = bs.read_ub(4)? as u8;
+        Ok(Self {
+            records: ShapeRecord::read_all(&mut bs, fill_bits, line_bits)?,
+        })
+    }
+
+    pub fn write(&self, stream: &mut StreamWriter) -> Result<()> {
+        let (fill_bits, line_bits) = self.style_bits();
+        let mut bs = BitStreamWriter::new(stream);
+        bs.write_ub(4, fill_bits.into())?;
+        bs.write_ub(4, line_bits.into())?;
+        ShapeRecord::write_all(&self.records, &mut bs, fill_bits, line_bits)?;
+        bs.flush()
+    }
+
+    /// Number of bits needed to store the fill and line style indices used by the records
+    pub fn style_bits(&self) -> (u8, u8) {
+        let mut max_fill = 0;
+        let mut max_line = 0;
+        for record in &self.records {
+            if let ShapeRecord::StyleChange(style) = record {
+                max_fill = max_fill
+                    .max(style.fill_style0.unwrap_or(0))
+                    .max(style.fill_style1.unwrap_or(0));
+                max_line = max_line.max(style.line_style.unwrap_or(0));
+            }
+        }
+        (
+            BitStreamWriter::min_ubits(max_fill),
+            BitStreamWriter::min_ubits(max_line),
+        )
+    }
+}
+
+impl ShapeRecord {
+    /// Read shape records until the end record, using the given style index sizes.
+    pub fn read_all(
+        bs: &mut BitStreamReader,
+        fill_bits: u8,
+        line_bits: u8,
+    ) -> Result<Vec<ShapeRecord>> {
+        let mut records = Vec::new();
+        loop {
+            let is_edge = bs.read_bit()?;
+            if is_edge {
+                records.push(Self::read_edge(bs)?);
+                continue;
+            }
+
+            let flags = bs.read_ub(5)?;
+            if flags == 0 {
+                // EndShapeRecord
+                break;
+            }
+            if flags & STATE_NEW_STYLES != 0 {
+                return Err(RabcError::InvalidShapeRecord(
+                    "new styles are not allowed in this shape",
+                ));
+            }
+
+            let mut style = StyleChangeRecord::default();
+            if flags & STATE_MOVE_TO != 0 {
+                let n_bits = bs.read_ub(5)? as u8;
+                style.move_to = Some(Position {
+                    x: bs.read_sb(n_bits)?,
+                    y: bs.read_sb(n_bits)?,
+                });
+            }
+            if flags & STATE_FILL_STYLE0 != 0 {
+                style.fill_style0 = Some(bs.read_ub(fill_bits)?);
+            }
+            if flags & STATE_FILL_STYLE1 != 0 {
+                style.fill_style1 = Some(bs.read_ub(fill_bits)?);
+            }
+            if flags & STATE_LINE_STYLE != 0 {
+                style.line_style = Some(bs.read_ub(line_bits)?);
+            }
+            records.push(Self::StyleChange(style));
+        }
+        Ok(records)
+    }
+
+    fn read_edge(bs: &mut BitStreamReader) -> Result<Self> {
+        let is_straight = bs.read_bit()?;
+        let n_bits = bs.read_ub(4)? as u8 + 2;
+        if !is_straight {
+            return Ok(Self::CurvedEdge(CurvedEdgeRecord {
+                control_delta_x: bs.read_sb(n_bits)?,
+                control_delta_y: bs.read_sb(n_bits)?,
+                anchor_delta_x: bs.read_sb(n_bits)?,
+                anchor_delta_y: bs.read_sb(n_bits)?,
+            }));
+        }
+
+        let mut edge = StraightEdgeRecord::default();
+        let is_general = bs.read_bit()?;
+        if is_general {
+            edge.delta_x = bs.read_sb(n_bits)?;
+            edge.delta_y = bs.read_sb(n_bits)?;
+        } else if bs.read_bit()? {
+            edge.delta_y = bs.read_sb(n_bits)?;
+        } else {
+            edge.delta_x = bs.read_sb(n_bits)?;
+        }
+        Ok(Self::StraightEdge(edge))
+    }
+
+    /// Write the shape records followed by an end record, using the given style index sizes.
+    pub fn write_all(
+        records: &[ShapeRecord],
+        bs: &mut BitStreamWriter,
+        fill_bits: u8,
+        line_bits: u8,
+    ) -> Result<()> {
+        for record in records {
+            record.write(bs, fill_bits, line_bits)?;
+        }
+        // EndShapeRecord
+        bs.write_ub(6, 0)
+    }
+
+    fn write(&self, bs: &mut BitStreamWriter, fill_bits: u8, line_bits: u8) -> Result<()> {
+        match self {
+            Self::StyleChange(style) => {
+                let mut flags = 0;
+                if style.move_to.is_some() {
+                    flags |= STATE_MOVE_TO;
+                }
+                if style.fill_style0.is_some() {
+                    flags |= STATE_FILL_STYLE0;
+                }
+                if style.fill_style1.is_some() {
+                    flags |= STATE_FILL_STYLE1;
+                }
+                if style.line_style.is_some() {
+                    flags |= STATE_LINE_STYLE;
+                }
+                if flags == 0 {
+                    // An empty style change record would be read back as an end record
+                    return Ok(());
+                }
+
+                bs.write_bit(false)?;
+                bs.write_ub(5, flags)?;
+                if let Some(pos) = &style.move_to {
+                    let n_bits =
+                        BitStreamWriter::min_sbits(pos.x).max(BitStreamWriter::min_sbits(pos.y));
+                    bs.write_ub(5, n_bits.into())?;
+                    bs.write_sb(n_bits, pos.x)?;
+                    bs.write_sb(n_bits, pos.y)?;
+                }
+                if let Some(fill) = style.fill_style0 {
+                    bs.write_ub(fill_bits, fill)?;
+                }
+                if let Some(fill) = style.fill_style1 {
+                    bs.write_ub(fill_bits, fill)?;
+                }
+                if let Some(line) = style.line_style {
+                    bs.write_ub(line_bits, line)?;
+                }
+            }
+            Self::StraightEdge(edge) => {
+                let n_bits = Self::edge_bits(&[edge.delta_x, edge.delta_y])?;
+                bs.write_bit(true)?;
+                bs.write_bit(true)?;
+                bs.write_ub(4, (n_bits - 2).into())?;
+                if edge.delta_x != 0 && edge.delta_y != 0 {
+                    bs.write_bit(true)?;
+                    bs.write_sb(n_bits, edge.delta_x)?;
+                    bs.write_sb(n_bits, edge.delta_y)?;
+                } else if edge.delta_x == 0 {
+                    // vertical line
+                    bs.write_bit(false)?;
+                    bs.write_bit(true)?;
+                    bs.write_sb(n_bits, edge.delta_y)?;
+                } else {
+                    // horizontal line
+                    bs.write_bit(false)?;
+                    bs.write_bit(false)?;
+                    bs.write_sb(n_bits, edge.delta_x)?;
+                }
+            }
+            Self::CurvedEdge(edge) => {
+                let n_bits = Self::edge_bits(&[
+                    edge.control_delta_x,
+                    edge.control_delta_y,
+                    edge.anchor_delta_x,
+                    edge.anchor_delta_y,
+                ])?;
+                bs.write_bit(true)?;
+                bs.write_bit(false)?;
+                bs.write_ub(4, (n_bits - 2).into())?;
+                bs.write_sb(n_bits, edge.control_delta_x)?;
+                bs.write_sb(n_bits, edge.control_delta_y)?;
+                bs.write_sb(n_bits, edge.anchor_delta_x)?;
+                bs.write_sb(n_bits, edge.anchor_delta_y)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Edges store their size minus 2 on 4 bits, so the deltas must fit in 2 to 17 bits
+    fn edge_bits(values: &[i32]) -> Result<u8> {
+        let n_bits = values
+            .iter()
+            .map(|v| BitStreamWriter::min_sbits(*v))
+            .max()
+            .unwrap_or(0)
+            .max(2);
+        if n_bits > 17 {
+            return Err(RabcError::InvalidShapeRecord("edge delta is too large"));
+        }
+        Ok(n_bits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shape_round_trip() {
+        let shape = Shape {
+            records: vec![
+                ShapeRecord::StyleChange(StyleChangeRecord {
+                    move_to: Some(Position { x: 120, y: -640 }),
+                    fill_style1: Some(1),
+                    ..Default::default()
+                }),
+                ShapeRecord::StraightEdge(StraightEdgeRecord {
+                    delta_x: 0,
+                    delta_y: 640,
+                }),
+                ShapeRecord::StraightEdge(StraightEdgeRecord {
+                    delta_x: -3,
+                    delta_y: 0,
+                }),
+                ShapeRecord::StraightEdge(StraightEdgeRecord {
+                    delta_x: 1000,
+                    delta_y: -20000,
+                }),
+                ShapeRecord::CurvedEdge(CurvedEdgeRecord {
+                    control_delta_x: 15,
+                    control_delta_y: -16,
+                    anchor_delta_x: 0,
+                    anchor_delta_y: 1,
+                }),
+            ],
+        };
+
+        let mut stream = StreamWriter::default();
+        shape.write(&mut stream).unwrap();
+        let mut reader = StreamReader::new(stream.buffer());
+        assert_eq!(Shape::read(&mut reader).unwrap(), shape);
+        assert!(reader.finished());
+    }
+}
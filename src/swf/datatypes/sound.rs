@@ -0,0 +1,293 @@
+use crate::error::{RabcError, Result};
+use crate::stream::{StreamReader, StreamWriter};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum AudioCompression {
+    /// Uncompressed samples, in the platform's endianness
+    #[default]
+    UncompressedNativeEndian,
+    Adpcm,
+    Mp3,
+    /// Uncompressed little-endian samples
+    Uncompressed,
+    Nellymoser16Khz,
+    Nellymoser8Khz,
+    Nellymoser,
+    Speex,
+    /// Format not defined by the specification, kept as is
+    Unknown(u8),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum SoundRate {
+    #[default]
+    Rate5512 = 0,
+    Rate11025 = 1,
+    Rate22050 = 2,
+    Rate44100 = 3,
+}
+
+/// Format of the samples of a sound, packed in a single byte
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct SoundFormat {
+    pub compression: AudioCompression,
+    pub rate: SoundRate,
+    /// 16-bit samples if set, 8-bit otherwise. Always 16-bit for compressed formats
+    pub is_16bit: bool,
+    pub is_stereo: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct SoundEnvelope {
+    /// Position in 44kHz samples
+    pub pos44: u32,
+    /// Volume of the left channel, from 0 to 32768
+    pub left_level: u16,
+    /// Volume of the right channel, from 0 to 32768
+    pub right_level: u16,
+}
+
+/// Playback settings of an event sound
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct SoundInfo {
+    /// Stop the sound instead of playing it
+    pub sync_stop: bool,
+    /// Don't start the sound if it's already playing
+    pub sync_no_multiple: bool,
+    /// Number of samples to skip at the beginning of the sound
+    pub in_point: Option<u32>,
+    /// Position in samples of the last sample to play
+    pub out_point: Option<u32>,
+    pub loop_count: Option<u16>,
+    pub envelope: Option<Vec<SoundEnvelope>>,
+}
+
+impl AudioCompression {
+    pub fn from_u8(value: u8) -> Self {
+        match value {
+            0 => Self::UncompressedNativeEndian,
+            1 => Self::Adpcm,
+            2 => Self::Mp3,
+            3 => Self::Uncompressed,
+            4 => Self::Nellymoser16Khz,
+            5 => Self::Nellymoser8Khz,
+            6 => Self::Nellymoser,
+            11 => Self::Speex,
+            _ => Self::Unknown(value),
+        }
+    }
+
+    pub fn to_u8(&self) -> u8 {
+        match self {
+            Self::UncompressedNativeEndian => 0,
+            Self::Adpcm => 1,
+            Self::Mp3 => 2,
+            Self::Uncompressed => 3,
+            Self::Nellymoser16Khz => 4,
+            Self::Nellymoser8Khz => 5,
+            Self::Nellymoser => 6,
+            Self::Speex => 11,
+            Self::Unknown(value) => *value,
+        }
+    }
+}
+
+impl SoundRate {
+    pub fn from_u8(value: u8) -> Self {
+        match value & 0x03 {
+            0 => Self::Rate5512,
+            1 => Self::Rate11025,
+            2 => Self::Rate22050,
+            _ => Self::Rate44100,
+        }
+    }
+
+    /// Sampling rate in Hz
+    pub fn hz(&self) -> u32 {
+        match self {
+            Self::Rate5512 => 5512,
+            Self::Rate11025 => 11025,
+            Self::Rate22050 => 22050,
+            Self::Rate44100 => 44100,
+        }
+    }
+}
+
+impl SoundFormat {
+    pub fn from_u8(value: u8) -> Self {
+        Self {
+            compression: AudioCompression::from_u8(value >> 4),
+            rate: SoundRate::from_u8(value >> 2),
+            is_16bit: value & 0x02 != 0,
+            is_stereo: value & 0x01 != 0,
+        }
+    }
+
+    pub fn to_u8(&self) -> u8 {
+        (self.compression.to_u8() << 4)
+            | ((self.rate as u8) << 2)
+            | (u8::from(self.is_16bit) << 1)
+            | u8::from(self.is_stereo)
+    }
+
+    pub fn read(stream: &mut StreamReader) -> Result<Self> {
+        Ok(Self::from_u8(stream.read_u8()?))
+    }
+    pub fn write(&self, stream: &mut StreamWriter) -> Result<()> {
+        stream.write_u8(self.to_u8())
+    }
+
+    /// Number of channels
+    pub fn channels(&self) -> u16 {
+        if self.is_stereo {
+            2
+        } else {
+            1
+        }
+    }
+    /// Number of bits per sample
+    pub fn bits_per_sample(&self) -> u16 {
+        if self.is_16bit {
+            16
+        } else {
+            8
+        }
+    }
+}
+
+const SYNC_STOP: u8 = 0x20;
+const SYNC_NO_MULTIPLE: u8 = 0x10;
+const HAS_ENVELOPE: u8 = 0x08;
+const HAS_LOOPS: u8 = 0x04;
+const HAS_OUT_POINT: u8 = 0x02;
+const HAS_IN_POINT: u8 = 0x01;
+
+impl SoundInfo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn read(stream: &mut StreamReader) -> Result<Self> {
+        let flags = stream.read_u8()?;
+        let in_point = if flags & HAS_IN_POINT != 0 {
+            Some(stream.read_u32()?)
+        } else {
+            None
+        };
+        let out_point = if flags & HAS_OUT_POINT != 0 {
+            Some(stream.read_u32()?)
+        } else {
+            None
+        };
+        let loop_count = if flags & HAS_LOOPS != 0 {
+            Some(stream.read_u16()?)
+        } else {
+            None
+        };
+        let envelope = if flags & HAS_ENVELOPE != 0 {
+            let count = stream.read_u8()?;
+            let mut points = Vec::with_capacity(count as usize);
+            for _ in 0..count {
+                points.push(SoundEnvelope {
+                    pos44: stream.read_u32()?,
+                    left_level: stream.read_u16()?,
+                    right_level: stream.read_u16()?,
+                });
+            }
+            Some(points)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            sync_stop: flags & SYNC_STOP != 0,
+            sync_no_multiple: flags & SYNC_NO_MULTIPLE != 0,
+            in_point,
+            out_point,
+            loop_count,
+            envelope,
+        })
+    }
+
+    pub fn write(&self, stream: &mut StreamWriter) -> Result<()> {
+        let mut flags = 0;
+        if self.sync_stop {
+            flags |= SYNC_STOP;
+        }
+        if self.sync_no_multiple {
+            flags |= SYNC_NO_MULTIPLE;
+        }
+        if self.envelope.is_some() {
+            flags |= HAS_ENVELOPE;
+        }
+        if self.loop_count.is_some() {
+            flags |= HAS_LOOPS;
+        }
+        if self.out_point.is_some() {
+            flags |= HAS_OUT_POINT;
+        }
+        if self.in_point.is_some() {
+            flags |= HAS_IN_POINT;
+        }
+
+        stream.write_u8(flags)?;
+        if let Some(in_point) = self.in_point {
+            stream.write_u32(in_point)?;
+        }
+        if let Some(out_point) = self.out_point {
+            stream.write_u32(out_point)?;
+        }
+        if let Some(loop_count) = self.loop_count {
+            stream.write_u16(loop_count)?;
+        }
+        if let Some(envelope) = &self.envelope {
+            let count = u8::try_from(envelope.len())
+                .map_err(|_| RabcError::ValueOutOfRange("envelope point count"))?;
+            stream.write_u8(count)?;
+            for point in envelope {
+                stream.write_u32(point.pos44)?;
+                stream.write_u16(point.left_level)?;
+                stream.write_u16(point.right_level)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sound_format() {
+        let format = SoundFormat::from_u8(0x2E);
+        assert_eq!(format.compression, AudioCompression::Mp3);
+        assert_eq!(format.rate, SoundRate::Rate44100);
+        assert!(format.is_16bit);
+        assert!(!format.is_stereo);
+        assert_eq!(format.to_u8(), 0x2E);
+        let format = SoundFormat::from_u8(0x7F);
+        assert_eq!(format.compression, AudioCompression::Unknown(7));
+        assert_eq!(format.to_u8(), 0x7F);
+    }
+
+    #[test]
+    fn sound_info_round_trip() {
+        let info = SoundInfo {
+            sync_no_multiple: true,
+            out_point: Some(44100),
+            loop_count: Some(3),
+            envelope: Some(vec![SoundEnvelope {
+                pos44: 0,
+                left_level: 32768,
+                right_level: 0,
+            }]),
+            ..Default::default()
+        };
+        let mut stream = StreamWriter::default();
+        info.write(&mut stream).unwrap();
+        let mut reader = StreamReader::new(stream.buffer());
+        assert_eq!(SoundInfo::read(&mut reader).unwrap(), info);
+        assert!(reader.finished());
+    }
+}
@@ -0,0 +1,119 @@
+use super::{Fixed8, GradientStop, Matrix, Rgba};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum SpreadMode {
+    #[default]
+    Pad = 0,
+    Reflect = 1,
+    Repeat = 2,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum InterpolationMode {
+    #[default]
+    Rgb = 0,
+    LinearRgb = 1,
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct Gradient {
+    pub spread: SpreadMode,
+    pub interpolation: InterpolationMode,
+    pub stops: Vec<GradientStop>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FillStyle {
+    Solid(Rgba),
+    LinearGradient {
+        matrix: Matrix,
+        gradient: Gradient,
+    },
+    RadialGradient {
+        matrix: Matrix,
+        gradient: Gradient,
+    },
+    /// Radial gradient with a focal point, from -1 to 1
+    FocalRadialGradient {
+        matrix: Matrix,
+        gradient: Gradient,
+        focal_point: Fixed8,
+    },
+    Bitmap {
+        bitmap_id: u16,
+        matrix: Matrix,
+        /// Repeat the bitmap to fill the area, clip it otherwise
+        repeat: bool,
+        smooth: bool,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum CapStyle {
+    #[default]
+    Round = 0,
+    None = 1,
+    Square = 2,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum JoinStyle {
+    #[default]
+    Round,
+    Bevel,
+    /// Miter join, with its limit factor
+    Miter(Fixed8),
+}
+
+/// Line style of a shape.
+/// Only the width and color are used by the first version of the line styles.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct LineStyle {
+    /// Width in twips
+    pub width: u16,
+    pub color: Rgba,
+    /// Fill of the line, used instead of the color
+    pub fill: Option<FillStyle>,
+    pub start_cap: CapStyle,
+    pub end_cap: CapStyle,
+    pub join: JoinStyle,
+    pub no_h_scale: bool,
+    pub no_v_scale: bool,
+    pub pixel_hinting: bool,
+    pub no_close: bool,
+}
+
+impl Default for FillStyle {
+    fn default() -> Self {
+        Self::Solid(Rgba::default())
+    }
+}
+
+impl SpreadMode {
+    pub fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::Reflect,
+            2 => Self::Repeat,
+            _ => Self::Pad,
+        }
+    }
+}
+
+impl InterpolationMode {
+    pub fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::LinearRgb,
+            _ => Self::Rgb,
+        }
+    }
+}
+
+impl CapStyle {
+    pub fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::None,
+            2 => Self::Square,
+            _ => Self::Round,
+        }
+    }
+}
@@ -0,0 +1,6 @@
+mod adpcm;
+mod sound;
+mod video;
+
+pub use adpcm::decode_adpcm;
+pub use sound::{SoundFile, SoundFileType};
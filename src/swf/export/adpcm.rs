@@ -0,0 +1,117 @@
+use crate::error::Result;
+use crate::stream::{BitStreamReader, StreamReader};
+
+/// Index adjustments by code size, indexed by the code without its sign bit
+const INDEX_TABLES: [&[i32]; 4] = [
+    &[-1, 2],
+    &[-1, -1, 2, 4],
+    &[-1, -1, -1, -1, 2, 4, 6, 8],
+    &[-1, -1, -1, -1, -1, -1, -1, -1, 1, 2, 4, 6, 8, 10, 13, 16],
+];
+
+const STEP_TABLE: [i32; 89] = [
+    7, 8, 9, 10, 11, 12, 13, 14, 16, 17, 19, 21, 23, 25, 28, 31, 34, 37, 41, 45, 50, 55, 60, 66,
+    73, 80, 88, 97, 107, 118, 130, 143, 157, 173, 190, 209, 230, 253, 279, 307, 337, 371, 408, 449,
+    494, 544, 598, 658, 724, 796, 876, 963, 1060, 1166, 1282, 1411, 1552, 1707, 1878, 2066, 2272,
+    2499, 2749, 3024, 3327, 3660, 4026, 4428, 4871, 5358, 5894, 6484, 7132, 7845, 8630, 9493,
+    10442, 11487, 12635, 13899, 15289, 16818, 18500, 20350, 22385, 24623, 27086, 29794, 32767,
+];
+
+/// Number of codes following the initial sample of each packet, per channel
+const PACKET_CODES: usize = 4095;
+/// Size of the initial sample (SB16) and step index (UB6) of each channel in a packet
+const CHANNEL_HEADER_BITS: usize = 22;
+
+#[derive(Clone, Copy, Default)]
+struct Channel {
+    sample: i32,
+    index: usize,
+}
+
+impl Channel {
+    fn decode(&mut self, code: u32, bits: u8) -> i16 {
+        let step = STEP_TABLE[self.index];
+        let sign_mask = 1 << (bits - 1);
+        let magnitude = code & (sign_mask - 1);
+
+        let mut diff = step >> (bits - 1);
+        for i in 0..bits - 1 {
+            if magnitude & (sign_mask >> (i + 1)) != 0 {
+                diff += step >> i;
+            }
+        }
+        if code & sign_mask != 0 {
+            self.sample -= diff;
+        } else {
+            self.sample += diff;
+        }
+        self.sample = self.sample.clamp(i16::MIN.into(), i16::MAX.into());
+
+        let adjust = INDEX_TABLES[usize::from(bits - 2)][magnitude as usize];
+        self.index = (self.index as i32 + adjust).clamp(0, 88) as usize;
+        self.sample as i16
+    }
+}
+
+/// Decode SWF ADPCM data to signed 16-bit samples.
+/// Stereo samples are interleaved, starting with the left channel.
+/// A truncated last packet is decoded up to its last complete sample.
+pub fn decode_adpcm(data: &[u8], is_stereo: bool) -> Result<Vec<i16>> {
+    let channels = if is_stereo { 2 } else { 1 };
+    let mut remaining = data.len() * 8;
+    if remaining < 2 {
+        return Ok(Vec::new());
+    }
+
+    let mut stream = StreamReader::new(data);
+    let mut bs = BitStreamReader::new(&mut stream);
+    let bits = bs.read_ub(2)? as u8 + 2;
+    remaining -= 2;
+
+    let header_bits = CHANNEL_HEADER_BITS * channels;
+    let frame_bits = usize::from(bits) * channels;
+    let mut state = [Channel::default(); 2];
+    let mut samples = Vec::new();
+    while remaining >= header_bits {
+        remaining -= header_bits;
+        for channel in &mut state[..channels] {
+            channel.sample = bs.read_sb(16)?;
+            channel.index = (bs.read_ub(6)? as usize).min(88);
+            samples.push(channel.sample as i16);
+        }
+
+        for _ in 0..PACKET_CODES {
+            if remaining < frame_bits {
+                break;
+            }
+            remaining -= frame_bits;
+            for channel in &mut state[..channels] {
+                samples.push(channel.decode(bs.read_ub(bits)?, bits));
+            }
+        }
+    }
+    Ok(samples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stream::{BitStreamWriter, StreamWriter};
+
+    #[test]
+    fn decode_4bit_mono() {
+        let mut stream = StreamWriter::default();
+        {
+            let mut bs = BitStreamWriter::new(&mut stream);
+            bs.write_ub(2, 2).unwrap();
+            bs.write_sb(16, 1000).unwrap();
+            bs.write_ub(6, 0).unwrap();
+            bs.write_ub(4, 0x7).unwrap();
+            bs.write_ub(4, 0xF).unwrap();
+            bs.flush().unwrap();
+        }
+
+        let samples = decode_adpcm(stream.buffer(), false).unwrap();
+        assert_eq!(samples, vec![1000, 1011, 981]);
+    }
+}
@@ -0,0 +1,160 @@
+use super::decode_adpcm;
+use crate::{
+    error::{RabcError, Result},
+    swf::{
+        datatypes::{AudioCompression, SoundFormat},
+        tags::{DefineSoundTag, SoundStream},
+    },
+    StreamWriter,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SoundFileType {
+    Mp3,
+    Wav,
+}
+
+/// A sound exported to a standalone audio file
+#[derive(Clone, Debug, PartialEq)]
+pub struct SoundFile {
+    pub file_type: SoundFileType,
+    pub data: Vec<u8>,
+}
+
+impl SoundFileType {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Mp3 => "mp3",
+            Self::Wav => "wav",
+        }
+    }
+}
+
+impl SoundFile {
+    /// Convert encoded samples to an audio file.
+    /// MP3 frames are kept as-is, uncompressed and ADPCM samples are stored in a WAV file.
+    /// Each ADPCM block is decoded on its own, as it starts with its own packet header.
+    pub fn from_blocks<'a>(
+        format: &SoundFormat,
+        blocks: impl IntoIterator<Item = &'a [u8]>,
+    ) -> Result<Self> {
+        match format.compression {
+            AudioCompression::Mp3 => Ok(Self {
+                file_type: SoundFileType::Mp3,
+                data: blocks.into_iter().flatten().copied().collect(),
+            }),
+            // Native endian samples are little-endian in practice
+            AudioCompression::Uncompressed | AudioCompression::UncompressedNativeEndian => {
+                let samples: Vec<u8> = blocks.into_iter().flatten().copied().collect();
+                Self::wav(format, format.bits_per_sample(), &samples)
+            }
+            AudioCompression::Adpcm => {
+                let mut samples = Vec::new();
+                for block in blocks {
+                    for sample in decode_adpcm(block, format.is_stereo)? {
+                        samples.extend_from_slice(&sample.to_le_bytes());
+                    }
+                }
+                Self::wav(format, 16, &samples)
+            }
+            compression => Err(RabcError::UnsupportedSoundCompression(compression)),
+        }
+    }
+
+    fn wav(format: &SoundFormat, bits_per_sample: u16, samples: &[u8]) -> Result<Self> {
+        let channels = format.channels();
+        let rate = format.rate.hz();
+        let block_align = channels * bits_per_sample / 8;
+        // RIFF chunks are padded to an even size
+        let padding = samples.len() % 2;
+
+        let mut stream = StreamWriter::default();
+        stream.write_exact(b"RIFF")?;
+        stream.write_u32((36 + samples.len() + padding) as u32)?;
+        stream.write_exact(b"WAVE")?;
+        stream.write_exact(b"fmt ")?;
+        stream.write_u32(16)?;
+        // PCM
+        stream.write_u16(1)?;
+        stream.write_u16(channels)?;
+        stream.write_u32(rate)?;
+        stream.write_u32(rate * u32::from(block_align))?;
+        stream.write_u16(block_align)?;
+        stream.write_u16(bits_per_sample)?;
+        stream.write_exact(b"data")?;
+        stream.write_u32(samples.len() as u32)?;
+        stream.write_exact(samples)?;
+        if padding != 0 {
+            stream.write_u8(0)?;
+        }
+
+        Ok(Self {
+            file_type: SoundFileType::Wav,
+            data: stream.move_buffer(),
+        })
+    }
+}
+
+impl DefineSoundTag {
+    /// Export the sound to an MP3 or WAV file, see [`SoundFile::from_blocks`]
+    pub fn export(&self) -> Result<SoundFile> {
+        let data = if self.format.compression == AudioCompression::Mp3 {
+            // Skip the seek samples
+            self.data.get(2..).unwrap_or_default()
+        } else {
+            &self.data
+        };
+        SoundFile::from_blocks(&self.format, [data])
+    }
+}
+
+impl SoundStream {
+    /// Export the stream to an MP3 or WAV file, see [`SoundFile::from_blocks`]
+    pub fn export(&self) -> Result<SoundFile> {
+        SoundFile::from_blocks(
+            &self.head.stream_format,
+            self.blocks.iter().map(Vec::as_slice),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::swf::datatypes::SoundRate;
+
+    #[test]
+    fn export_sounds() {
+        let mut sound = DefineSoundTag {
+            sound_id: 1,
+            format: SoundFormat {
+                compression: AudioCompression::Mp3,
+                rate: SoundRate::Rate44100,
+                is_16bit: true,
+                is_stereo: true,
+            },
+            sample_count: 1152,
+            data: vec![0x10, 0x00, 0xFF, 0xFB, 0x90, 0x64],
+        };
+        let file = sound.export().unwrap();
+        assert_eq!(file.file_type, SoundFileType::Mp3);
+        assert_eq!(file.data, vec![0xFF, 0xFB, 0x90, 0x64]);
+
+        sound.format.compression = AudioCompression::Uncompressed;
+        sound.format.rate = SoundRate::Rate22050;
+        sound.data = vec![0x01, 0x00, 0xFF, 0xFF, 0x02, 0x00];
+        let file = sound.export().unwrap();
+        assert_eq!(file.file_type, SoundFileType::Wav);
+        assert_eq!(file.data.len(), 44 + 6);
+        assert_eq!(&file.data[0..4], b"RIFF");
+        assert_eq!(&file.data[4..8], &42u32.to_le_bytes());
+        assert_eq!(&file.data[22..24], &2u16.to_le_bytes());
+        assert_eq!(&file.data[24..28], &22050u32.to_le_bytes());
+        assert_eq!(&file.data[28..32], &88200u32.to_le_bytes());
+        assert_eq!(&file.data[40..44], &6u32.to_le_bytes());
+        assert_eq!(&file.data[44..], &sound.data[..]);
+
+        sound.format.compression = AudioCompression::Speex;
+        sound.export().unwrap_err();
+    }
+}
@@ -0,0 +1,177 @@
+use crate::{
+    error::Result,
+    stream::{BitStreamReader, StreamReader},
+    swf::tags::{DefineVideoStreamTag, VideoCodec, VideoFrameTag},
+};
+
+const FLV_TAG_VIDEO: u8 = 9;
+const FLV_FRAME_KEY: u8 = 1;
+const FLV_FRAME_INTER: u8 = 2;
+
+impl DefineVideoStreamTag {
+    /// Remux the frames of the video stream into an FLV file, without transcoding.
+    /// The frames are timed according to their frame number and the movie's framerate.
+    pub fn export_flv<'a>(
+        &self,
+        frames: impl IntoIterator<Item = &'a VideoFrameTag>,
+        framerate: f64,
+    ) -> Result<Vec<u8>> {
+        let mut flv = Vec::new();
+        flv.extend_from_slice(b"FLV");
+        // Version 1, video only
+        flv.extend_from_slice(&[1, 0x01]);
+        flv.extend_from_slice(&9u32.to_be_bytes());
+        flv.extend_from_slice(&0u32.to_be_bytes());
+
+        for frame in frames {
+            if frame.stream_id != self.char_id {
+                continue;
+            }
+            let frame_type = if self.is_keyframe(frame)? {
+                FLV_FRAME_KEY
+            } else {
+                FLV_FRAME_INTER
+            };
+            let timestamp = if framerate > 0.0 {
+                (f64::from(frame.frame_num) * 1000.0 / framerate).round() as u32
+            } else {
+                0
+            };
+
+            let mut data = vec![(frame_type << 4) | self.codec.to_u8()];
+            if matches!(self.codec, VideoCodec::Vp6 | VideoCodec::Vp6Alpha) {
+                // FLV VP6 packets start with the size adjustment, which SWF doesn't store
+                data.push(0);
+            }
+            data.extend_from_slice(&frame.data);
+
+            flv.push(FLV_TAG_VIDEO);
+            flv.extend_from_slice(&(data.len() as u32).to_be_bytes()[1..]);
+            flv.extend_from_slice(&(timestamp & 0x00FF_FFFF).to_be_bytes()[1..]);
+            flv.push((timestamp >> 24) as u8);
+            // Stream id, always 0
+            flv.extend_from_slice(&[0, 0, 0]);
+            flv.extend_from_slice(&data);
+            flv.extend_from_slice(&(data.len() as u32 + 11).to_be_bytes());
+        }
+        Ok(flv)
+    }
+
+    fn is_keyframe(&self, frame: &VideoFrameTag) -> Result<bool> {
+        if frame.data.is_empty() {
+            return Ok(false);
+        }
+        match self.codec {
+            VideoCodec::SorensonH263 => {
+                let mut stream = StreamReader::new(&frame.data);
+                let mut bs = BitStreamReader::new(&mut stream);
+                // Picture start code, version and temporal reference
+                bs.read_ub(17)?;
+                bs.read_ub(5)?;
+                bs.read_ub(8)?;
+                match bs.read_ub(3)? {
+                    0 => bs.read_ub(16)?,
+                    1 => bs.read_ub(32)?,
+                    _ => 0,
+                };
+                // 0 for intra frames, 1 for inter frames and 2 for disposable inter frames
+                Ok(bs.read_ub(2)? == 0)
+            }
+            VideoCodec::Vp6 => Ok(frame.data[0] & 0x80 == 0),
+            // The VP6 data starts after the 24-bit offset to the alpha data
+            VideoCodec::Vp6Alpha => Ok(frame.data.get(3).is_some_and(|b| b & 0x80 == 0)),
+            VideoCodec::ScreenVideo => Self::is_screen_keyframe(&frame.data),
+            // The flags following the dimensions tell if the packet holds an intra frame image
+            VideoCodec::ScreenVideoV2 => Ok(frame.data.get(4).is_some_and(|b| b & 0x02 != 0)),
+            VideoCodec::Unknown(_) => Ok(false),
+        }
+    }
+
+    /// Screen video inter frames skip the blocks that didn't change, with an empty size
+    fn is_screen_keyframe(data: &[u8]) -> Result<bool> {
+        let mut stream = StreamReader::new(data);
+        let (columns, rows) = {
+            let mut bs = BitStreamReader::new(&mut stream);
+            let block_width = (bs.read_ub(4)? + 1) * 16;
+            let image_width = bs.read_ub(12)?;
+            let block_height = (bs.read_ub(4)? + 1) * 16;
+            let image_height = bs.read_ub(12)?;
+            (
+                image_width.div_ceil(block_width),
+                image_height.div_ceil(block_height),
+            )
+        };
+
+        for _ in 0..columns * rows {
+            let size = stream.read_u16()?.swap_bytes();
+            if size == 0 {
+                return Ok(false);
+            }
+            stream.skip(size.into())?;
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn export_flv() {
+        let video = DefineVideoStreamTag {
+            char_id: 4,
+            frame_count: 2,
+            width: 320,
+            height: 240,
+            codec: VideoCodec::Vp6,
+            ..Default::default()
+        };
+        let frames = [
+            VideoFrameTag {
+                stream_id: 4,
+                frame_num: 0,
+                data: vec![0x00, 0x11],
+            },
+            VideoFrameTag {
+                stream_id: 5,
+                frame_num: 0,
+                data: vec![0x00],
+            },
+            VideoFrameTag {
+                stream_id: 4,
+                frame_num: 1,
+                data: vec![0x80, 0x22],
+            },
+        ];
+
+        let flv = video.export_flv(&frames, 25.0).unwrap();
+        assert_eq!(&flv[..13], b"FLV\x01\x01\x00\x00\x00\x09\x00\x00\x00\x00");
+        let first = &flv[13..13 + 11 + 4 + 4];
+        assert_eq!(first[0], FLV_TAG_VIDEO);
+        assert_eq!(&first[1..4], &[0, 0, 4]);
+        assert_eq!(&first[4..8], &[0, 0, 0, 0]);
+        assert_eq!(&first[11..15], &[0x14, 0x00, 0x00, 0x11]);
+        assert_eq!(&first[15..19], &15u32.to_be_bytes());
+
+        let second = &flv[13 + 19..];
+        assert_eq!(second.len(), 19);
+        // 40ms
+        assert_eq!(&second[4..8], &[0, 0, 40, 0]);
+        assert_eq!(&second[11..15], &[0x24, 0x00, 0x80, 0x22]);
+
+        let video = DefineVideoStreamTag {
+            codec: VideoCodec::ScreenVideoV2,
+            ..video
+        };
+        let frame = |frame_num, flags| VideoFrameTag {
+            stream_id: 4,
+            frame_num,
+            data: vec![0x10, 0x20, 0x10, 0x20, flags],
+        };
+        assert!(!video.is_keyframe(&frame(0, 0x00)).unwrap());
+        assert!(video.is_keyframe(&frame(3, 0x02)).unwrap());
+        assert_eq!(VideoCodec::from_u8(9), VideoCodec::Unknown(9));
+        assert_eq!(VideoCodec::Unknown(9).to_u8(), 9);
+    }
+}
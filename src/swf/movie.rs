@@ -1,9 +1,19 @@
 use crate::{
     error::{RabcError, Result},
-    swf::{datatypes::Rect, tags::*},
+    stream::LZMA_DICT_SIZE,
+    swf::{
+        datatypes::{Rect, UFixed8},
+        tags::*,
+        TagHeader, TagReader,
+    },
     StreamReader, StreamWriter,
 };
-use std::{collections::HashMap, fmt};
+use std::{
+    collections::HashMap,
+    fmt,
+    hash::{DefaultHasher, Hash, Hasher},
+    io::{Read, Write},
+};
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Default)]
 pub enum Compression {
@@ -18,12 +28,82 @@ pub struct Movie {
     pub compression: Compression,
     pub version: u8,
     pub file_length: u32,
-    pub framerate: f64,
+    /// Unsigned in the header, see [`Movie::fps`]
+    pub framerate: UFixed8,
     pub framecount: u16,
     pub framesize: Rect,
 
     pub tags: Vec<Tag>,
-    pub symbols: HashMap<u16, String>,
+    /// Original encoding of the movie, only recorded with [`ReadOptions::preserve`]
+    pub preserved: Option<Preserved>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ReadOptions {
+    /// Record the original encoding of the movie, so the tags that are not modified are written
+    /// back identically, including their header. Preservation works per whole tag: a modified
+    /// tag is written canonically, e.g. editing one method re-encodes its whole DoABC tag.
+    /// The compressed data is only written back with [`WriteOptions::keep_compressed`].
+    pub preserve: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct WriteOptions {
+    /// Zlib compression level, from 0 (stored) to 9 (best)
+    pub zlib_level: u32,
+    /// LZMA dictionary size announced to the decoder
+    pub lzma_dict_size: u32,
+    /// Write back the original compressed data of a movie read with [`ReadOptions::preserve`],
+    /// when it is written unchanged and with the same compression.
+    /// Without it, the output only depends on the movie and these options.
+    pub keep_compressed: bool,
+}
+
+impl Default for WriteOptions {
+    fn default() -> Self {
+        Self {
+            zlib_level: 9,
+            lzma_dict_size: LZMA_DICT_SIZE,
+            keep_compressed: false,
+        }
+    }
+}
+
+/// Original encoding of a movie
+#[derive(Clone, Debug, PartialEq)]
+pub struct Preserved {
+    pub file_length: u32,
+    pub compression: Compression,
+    /// Data following the 8 bytes header of a compressed movie
+    pub compressed: Vec<u8>,
+    /// Frame size, rate and count, as read
+    pub header: (Rect, UFixed8, u16),
+    pub header_data: Vec<u8>,
+    pub tags: Vec<PreservedTag>,
+    /// Data after the End tag
+    pub trailing: Vec<u8>,
+}
+
+/// Original encoding of a tag, written back as long as the tag is encoded as when it was read
+#[derive(Clone, Debug, PartialEq)]
+pub struct PreservedTag {
+    pub header: TagHeader,
+    /// Hash of the id and canonical encoding of the tag as read
+    pub hash: u64,
+    /// Length of the canonical encoding
+    pub canonical_length: usize,
+    /// Canonical encoding, only kept when it differs from the original data
+    pub canonical: Option<Vec<u8>>,
+    pub data: Vec<u8>,
+}
+
+impl PreservedTag {
+    /// Whether a tag with the given id and canonical encoding is the one that was read
+    fn matches(&self, id: u16, canonical: &[u8]) -> bool {
+        self.header.id == id
+            && self.canonical_length == canonical.len()
+            && self.canonical.as_deref().unwrap_or(&self.data) == canonical
+    }
 }
 
 impl Default for Movie {
@@ -36,7 +116,7 @@ impl Default for Movie {
             framecount: Default::default(),
             framesize: Default::default(),
             tags: Default::default(),
-            symbols: Default::default(),
+            preserved: None,
         }
     }
 }
@@ -95,9 +175,18 @@ impl Movie {
         Self::default()
     }
 
-    pub fn read(mut stream: StreamReader) -> Result<Self> {
+    pub fn read(stream: StreamReader) -> Result<Self> {
+        Self::read_with_options(stream, &ReadOptions::default())
+    }
+
+    pub fn read_with_options(mut stream: StreamReader, options: &ReadOptions) -> Result<Self> {
         let header = Header::read(&mut stream)?;
         let size = header.file_length as usize;
+        let compressed = match header.compression {
+            Compression::None => Vec::new(),
+            _ if !options.preserve => Vec::new(),
+            _ => stream.buffer.get_ref()[stream.pos() as usize..].to_vec(),
+        };
         let buffer = match header.compression {
             Compression::Zlib => Some(stream.inflate_zlib(size - 8)?),
             Compression::Lzma => Some(stream.inflate_lzma(size - 8)?),
@@ -108,45 +197,43 @@ impl Movie {
             None => stream,
         };
 
+        let start = stream.pos() as usize;
         let framesize = Rect::read(&mut stream)?;
-        let framerate = (stream.read_u8()? as f64) / 256.0 + (stream.read_u8()? as f64);
+        let framerate = UFixed8::read(&mut stream)?;
         let framecount = stream.read_u16()?;
+        let header_data = stream.buffer.get_ref()[start..stream.pos() as usize].to_vec();
         let mut tags: Vec<Tag> = Vec::new();
+        let mut preserved_tags = Vec::new();
         let mut tag_type = TagID::Unknown;
-        let mut symbols = HashMap::new();
 
         while tag_type != TagID::End {
             let hdr = stream.read_u16()?;
             let tag_id = hdr >> 6;
             tag_type = TagID::from_u16(tag_id);
-            let length: u32 = match hdr & 0x3F {
-                0x3F => stream.read_u32()?,
-                _ => (hdr & 0x3F).into(),
+            let (length, long): (u32, bool) = match hdr & 0x3F {
+                0x3F => (stream.read_u32()?, true),
+                _ => ((hdr & 0x3F).into(), false),
             };
 
             let pos = stream.pos();
             stream.skip(length)?;
             let data = &stream.buffer.get_ref()[pos as usize..(pos + length) as usize];
-            let mut ts = StreamReader::new(data);
-
-            if tag_type == TagID::Unknown {
-                tags.push(Tag::Unknown(UnknownTag::read_with_id(data, tag_id)?));
-            } else {
-                let tag = Tag::read(tag_type, &mut ts)?;
-                if let Tag::SymbolClass(t) = &tag {
-                    t.symbols.clone_into(&mut symbols);
-                }
-                tags.push(tag);
-                assert_eq!(
-                    ts.remaining(),
-                    0,
-                    "Remaining data after tag 0x{:0>2x}.",
-                    tag_id
-                );
+
+            if options.preserve {
+                preserved_tags.push((
+                    TagHeader {
+                        id: tag_id,
+                        length,
+                        long,
+                    },
+                    data,
+                ));
             }
+
+            tags.push(Tag::read_data(tag_id, data));
         }
 
-        Ok(Self {
+        let mut movie = Self {
             compression: header.compression,
             version: header.version,
             file_length: header.file_length,
@@ -154,11 +241,72 @@ impl Movie {
             framecount,
             framesize,
             tags,
-            symbols,
+            preserved: None,
+        };
+        if options.preserve {
+            let mut tags = Vec::with_capacity(preserved_tags.len());
+            for ((header, data), tag) in preserved_tags.into_iter().zip(&movie.tags) {
+                let canonical = movie.write_tag(tag)?.move_buffer();
+                tags.push(PreservedTag {
+                    header,
+                    hash: hash_tag(tag.id(), &canonical),
+                    canonical_length: canonical.len(),
+                    canonical: (canonical != data).then_some(canonical),
+                    data: data.to_vec(),
+                });
+            }
+            movie.preserved = Some(Preserved {
+                file_length: header.file_length,
+                compression: header.compression,
+                compressed,
+                header: (movie.framesize.clone(), framerate, framecount),
+                header_data,
+                tags,
+                trailing: stream.buffer.get_ref()[stream.pos() as usize..].to_vec(),
+            });
+        }
+        Ok(movie)
+    }
+
+    /// Read a movie from any reader, such as a file or a socket.
+    /// The movie is decompressed as its tags are read with a [`TagReader`], so only the data of
+    /// the current tag is buffered. The tags are read until the End tag.
+    pub fn read_from<R: Read>(reader: R) -> Result<Self> {
+        let mut reader = TagReader::new(reader)?;
+        let mut tags = Vec::new();
+        while let Some(header) = reader.read_header()? {
+            tags.push(reader.read_tag(&header)?);
+        }
+
+        Ok(Self {
+            compression: reader.header.compression,
+            version: reader.header.version,
+            file_length: reader.header.file_length,
+            framerate: reader.framerate,
+            framecount: reader.framecount,
+            framesize: reader.framesize,
+            tags,
+            preserved: None,
         })
     }
 
+    /// Write the movie to any writer.
+    /// The movie is first written in memory since the header depends on its length.
+    pub fn write_to<W: Write>(&self, writer: W) -> Result<()> {
+        let mut stream = StreamWriter::default();
+        self.write(&mut stream)?;
+        stream.to_file(writer)
+    }
+
     pub fn write(&self, stream: &mut StreamWriter) -> Result<()> {
+        self.write_with_options(stream, &WriteOptions::default())
+    }
+
+    pub fn write_with_options(
+        &self,
+        stream: &mut StreamWriter,
+        options: &WriteOptions,
+    ) -> Result<()> {
         let header = Header {
             compression: self.compression,
             version: self.version,
@@ -166,39 +314,105 @@ impl Movie {
         };
 
         header.write(stream)?;
-        self.framesize.write(stream)?;
+        let preserved = self.preserved.as_ref();
+        // Whether the whole movie is written back as it was read
+        let mut unchanged = preserved.is_some_and(|p| p.tags.len() == self.tags.len());
 
-        let integral = self.framerate.ceil();
-        let fraction = self.framerate - integral;
-        stream.write_u8((fraction * 256.0) as u8)?;
-        stream.write_u8(integral as u8)?;
-        stream.write_u16(self.framecount)?;
+        let header = (self.framesize.clone(), self.framerate, self.framecount);
+        match preserved {
+            Some(p) if p.header == header => stream.write_exact(&p.header_data)?,
+            _ => {
+                unchanged = false;
+                self.framesize.write(stream)?;
+                self.framerate.write(stream)?;
+                stream.write_u16(self.framecount)?;
+            }
+        }
 
+        // Indices of the preserved tags, by the hash of their canonical encoding
+        let mut indices: HashMap<u64, Vec<usize>> = HashMap::new();
+        for (i, t) in preserved.iter().flat_map(|p| p.tags.iter().enumerate()) {
+            indices.entry(t.hash).or_default().push(i);
+        }
+        // Preserved tags are matched in order, skipping the ones that were removed.
+        // Tags with the same canonical encoding, such as ShowFrame, are matched in order.
+        let mut next = 0;
         for tag in &self.tags {
-            let mut stag = StreamWriter::new(Vec::new());
-            tag.write(&mut stag, self)?;
-
-            let length = stag.len();
-            let id = tag.id();
-
-            if length < 0x3F {
-                stream.write_u16((id << 6) | (length & 0x3F) as u16)?;
-            } else {
-                stream.write_u16((id << 6) | 0x3F)?;
-                stream.write_u32(length as u32)?;
+            let stag = self.write_tag(tag)?;
+            let original = preserved.zip(indices.get(&hash_tag(tag.id(), stag.buffer())));
+            let original = original.and_then(|(p, indices)| {
+                let start = indices.partition_point(|&i| i < next);
+                // The hash may collide, the encodings are compared
+                let index = *indices[start..]
+                    .iter()
+                    .find(|&&i| p.tags[i].matches(tag.id(), stag.buffer()))?;
+                unchanged &= index == next;
+                next = index + 1;
+                Some(&p.tags[index])
+            });
+            match original {
+                Some(PreservedTag { header, data, .. }) => {
+                    Self::write_tag_header(stream, header)?;
+                    stream.write_exact(data)?;
+                }
+                None => {
+                    unchanged = false;
+                    let header = TagHeader {
+                        id: tag.id(),
+                        length: stag.len() as u32,
+                        long: false,
+                    };
+                    Self::write_tag_header(stream, &header)?;
+                    stream.write_stream(&stag)?;
+                }
             }
-            stream.write_stream(&stag)?;
+        }
+        if let Some(p) = preserved {
+            stream.write_exact(&p.trailing)?;
         }
 
-        stream.write_u32_at(stream.len() as u32, 4)?;
-        match self.compression {
-            Compression::Zlib => stream.deflate_zlib(8, stream.len() - 8)?,
-            Compression::Lzma => stream.deflate_lzma(8, stream.len() - 8)?,
-            Compression::None => {}
+        let file_length = match preserved {
+            Some(p) if unchanged => p.file_length,
+            _ => stream.len() as u32,
+        };
+        stream.write_u32_at(file_length, 4)?;
+        let length = stream.len() - 8;
+        match (self.compression, preserved) {
+            (Compression::None, _) => {}
+            (compression, Some(p))
+                if unchanged && options.keep_compressed && p.compression == compression =>
+            {
+                let mut buffer = std::mem::take(stream).move_buffer();
+                buffer.truncate(8);
+                buffer.extend_from_slice(&p.compressed);
+                *stream = StreamWriter::new(buffer);
+            }
+            (Compression::Zlib, _) => {
+                stream.deflate_zlib_with_level(8, length, options.zlib_level)?
+            }
+            (Compression::Lzma, _) => {
+                stream.deflate_lzma_with_dict_size(8, length, options.lzma_dict_size)?
+            }
         };
         Ok(())
     }
 
+    fn write_tag(&self, tag: &Tag) -> Result<StreamWriter> {
+        let mut stream = StreamWriter::default();
+        tag.write(&mut stream, self)?;
+        Ok(stream)
+    }
+
+    fn write_tag_header(stream: &mut StreamWriter, header: &TagHeader) -> Result<()> {
+        if header.long || header.length >= 0x3F {
+            stream.write_u16((header.id << 6) | 0x3F)?;
+            stream.write_u32(header.length)?;
+        } else {
+            stream.write_u16((header.id << 6) | header.length as u16)?;
+        }
+        Ok(())
+    }
+
     pub fn frame1(&self) -> Option<&DoABCTag> {
         for tag in &self.tags {
             if let Tag::DoABC(doabc) = tag {
@@ -220,6 +434,57 @@ impl Movie {
         None
     }
 
+    /// Remove the tags of the given types, returning the number of tags removed
+    pub fn remove_tags(&mut self, ids: &[TagID]) -> usize {
+        let count = self.tags.len();
+        self.tags.retain(|t| !ids.contains(&t.into()));
+        count - self.tags.len()
+    }
+    /// Remove the Protect tag, allowing the movie to be imported in authoring tools
+    pub fn strip_protect(&mut self) -> usize {
+        self.remove_tags(&[TagID::Protect])
+    }
+    /// Remove the EnableDebugger, EnableDebugger2 and DebugID tags
+    pub fn strip_debugger(&mut self) -> usize {
+        self.remove_tags(&[
+            TagID::EnableDebugger,
+            TagID::EnableDebugger2,
+            TagID::DebugID,
+        ])
+    }
+    /// Remove the EnableTelemetry tag
+    pub fn strip_telemetry(&mut self) -> usize {
+        self.remove_tags(&[TagID::EnableTelemetry])
+    }
+
+    /// Insert a tag that must appear before the first frame, such as Protect or EnableDebugger2.
+    /// It replaces the existing tag of the same type, or is inserted after the FileAttributes
+    /// and Metadata tags.
+    pub fn insert_header_tag(&mut self, tag: Tag) {
+        let id = tag.id();
+        if let Some(existing) = self.tags.iter_mut().find(|t| t.id() == id) {
+            *existing = tag;
+            return;
+        }
+        let pos = self
+            .tags
+            .iter()
+            .position(|t| !matches!(t, Tag::FileAttributes(_) | Tag::Metadata(_)))
+            .unwrap_or(self.tags.len());
+        self.tags.insert(pos, tag);
+    }
+    /// Allow a debugger to attach to the movie, using an EnableDebugger2 tag
+    pub fn enable_debugger(&mut self, password_hash: String) {
+        self.insert_header_tag(Tag::EnableDebugger2(EnableDebuggerTag { password_hash }));
+    }
+    /// Identifier of the SWD debug file matching the movie
+    pub fn debug_id(&self) -> Option<[u8; 16]> {
+        self.tags.iter().find_map(|t| match t {
+            Tag::DebugID(t) => Some(t.uuid),
+            _ => None,
+        })
+    }
+
     pub fn binaries(&self) -> impl Iterator<Item = &'_ DefineBinaryDataTag> {
         self.tags.iter().filter_map(|t| match t {
             Tag::DefineBinaryData(t) => Some(t),
@@ -232,6 +497,226 @@ impl Movie {
             _ => None,
         })
     }
+
+    /// Fonts defined by DefineFont2 and DefineFont3 tags
+    pub fn fonts(&self) -> impl Iterator<Item = &'_ DefineFont2Tag> {
+        self.tags.iter().filter_map(|t| match t {
+            Tag::DefineFont2(t) | Tag::DefineFont3(t) => Some(t.as_ref()),
+            _ => None,
+        })
+    }
+    pub fn fonts_mut(&mut self) -> impl Iterator<Item = &'_ mut DefineFont2Tag> {
+        self.tags.iter_mut().filter_map(|t| match t {
+            Tag::DefineFont2(t) | Tag::DefineFont3(t) => Some(t.as_mut()),
+            _ => None,
+        })
+    }
+    /// Get a font defined by a DefineFont2 or DefineFont3 tag from its id
+    pub fn font(&self, font_id: u16) -> Option<&DefineFont2Tag> {
+        self.fonts().find(|f| f.font_id == font_id)
+    }
+
+    /// Static texts defined by DefineText and DefineText2 tags
+    pub fn texts(&self) -> impl Iterator<Item = &'_ DefineTextTag> {
+        self.tags.iter().filter_map(|t| match t {
+            Tag::DefineText(t) | Tag::DefineText2(t) => Some(t),
+            _ => None,
+        })
+    }
+    pub fn texts_mut(&mut self) -> impl Iterator<Item = &'_ mut DefineTextTag> {
+        self.tags.iter_mut().filter_map(|t| match t {
+            Tag::DefineText(t) | Tag::DefineText2(t) => Some(t),
+            _ => None,
+        })
+    }
+
+    /// Event sounds defined by DefineSound tags
+    pub fn sounds(&self) -> impl Iterator<Item = &'_ DefineSoundTag> {
+        self.tags.iter().filter_map(|t| match t {
+            Tag::DefineSound(t) => Some(t),
+            _ => None,
+        })
+    }
+    /// Streaming sound of the main timeline, if any
+    pub fn sound_stream(&self) -> Option<SoundStream> {
+        SoundStream::from_tags(&self.tags)
+    }
+
+    /// Video streams defined by DefineVideoStream tags
+    pub fn videos(&self) -> impl Iterator<Item = &'_ DefineVideoStreamTag> {
+        self.tags.iter().filter_map(|t| match t {
+            Tag::DefineVideoStream(t) => Some(t),
+            _ => None,
+        })
+    }
+    /// Frames of a video stream placed on the main timeline
+    pub fn video_frames(&self, stream_id: u16) -> impl Iterator<Item = &'_ VideoFrameTag> {
+        self.tags.iter().filter_map(move |t| match t {
+            Tag::VideoFrame(t) if t.stream_id == stream_id => Some(t),
+            _ => None,
+        })
+    }
+
+    /// Characters exported by ExportAssets tags
+    pub fn exports(&self) -> impl Iterator<Item = &'_ Asset> {
+        self.tags.iter().flat_map(|t| match t {
+            Tag::ExportAssets(t) => t.assets.as_slice(),
+            _ => &[],
+        })
+    }
+    /// Get the id of the character exported under the given name
+    pub fn export(&self, name: &str) -> Option<u16> {
+        self.exports().find(|a| a.name == name).map(|a| a.char_id)
+    }
+    /// ImportAssets and ImportAssets2 tags
+    pub fn imports(&self) -> impl Iterator<Item = &'_ ImportAssetsTag> {
+        self.tags.iter().filter_map(|t| match t {
+            Tag::ImportAssets(t) | Tag::ImportAssets2(t) => Some(t),
+            _ => None,
+        })
+    }
+
+    /// Frames per second
+    pub fn fps(&self) -> f64 {
+        self.framerate.to_f64()
+    }
+    /// Set the frames per second, rounded to the closest 1/256
+    pub fn set_fps(&mut self, fps: f64) {
+        self.framerate = UFixed8::from_f64(fps);
+    }
+
+    /// Symbols of every SymbolClass tag, in order
+    pub fn symbols(&self) -> impl Iterator<Item = &'_ Asset> {
+        self.tags.iter().flat_map(|t| match t {
+            Tag::SymbolClass(t) => t.symbols.as_slice(),
+            _ => &[],
+        })
+    }
+    fn symbols_mut(&mut self) -> impl Iterator<Item = &'_ mut Asset> {
+        self.tags.iter_mut().flat_map(|t| match t {
+            Tag::SymbolClass(t) => t.symbols.as_mut_slice(),
+            _ => &mut [],
+        })
+    }
+    /// Get the class linked to a character, the first one if there are duplicates
+    pub fn symbol_name(&self, char_id: u16) -> Option<&str> {
+        self.symbols()
+            .find(|s| s.char_id == char_id)
+            .map(|s| s.name.as_str())
+    }
+    /// Get the character linked to a class
+    pub fn symbol_id(&self, name: &str) -> Option<u16> {
+        self.symbols().find(|s| s.name == name).map(|s| s.char_id)
+    }
+
+    /// Link a character to a class, in the last SymbolClass tag.
+    /// A tag is inserted before the first ShowFrame tag if there is none.
+    pub fn add_symbol(&mut self, char_id: u16, name: String) {
+        let symbol = Asset { char_id, name };
+        let last = self.tags.iter_mut().rev().find_map(|t| match t {
+            Tag::SymbolClass(t) => Some(t),
+            _ => None,
+        });
+        if let Some(tag) = last {
+            tag.symbols.push(symbol);
+            return;
+        }
+
+        let index = self
+            .tags
+            .iter()
+            .position(|t| matches!(t, Tag::ShowFrame(_) | Tag::End(_)))
+            .unwrap_or(self.tags.len());
+        let tag = SymbolClassTag {
+            symbols: vec![symbol],
+        };
+        self.tags.insert(index, Tag::SymbolClass(tag));
+    }
+    /// Remove the links to a class, returns the number of symbols removed.
+    /// SymbolClass tags left empty are kept.
+    pub fn remove_symbol(&mut self, name: &str) -> usize {
+        let mut removed = 0;
+        for tag in &mut self.tags {
+            if let Tag::SymbolClass(t) = tag {
+                let count = t.symbols.len();
+                t.symbols.retain(|s| s.name != name);
+                removed += count - t.symbols.len();
+            }
+        }
+        removed
+    }
+    /// Rename the class linked to characters, returns the number of symbols renamed
+    pub fn rename_symbol(&mut self, name: &str, new_name: &str) -> usize {
+        let mut renamed = 0;
+        for symbol in self.symbols_mut().filter(|s| s.name == name) {
+            new_name.clone_into(&mut symbol.name);
+            renamed += 1;
+        }
+        renamed
+    }
+
+    /// Scenes of the main timeline, defined by the DefineSceneAndFrameLabelData tag
+    pub fn scenes(&self) -> &[Scene] {
+        self.tags
+            .iter()
+            .find_map(|t| match t {
+                Tag::DefineSceneAndFrameLabelData(t) => Some(t.scenes.as_slice()),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+    /// Get the scene containing the frame
+    pub fn scene_at(&self, frame: u32) -> Option<&Scene> {
+        self.scenes().iter().rev().find(|s| s.offset <= frame)
+    }
+
+    /// Labels of the main timeline frames, sorted by frame index.
+    /// Both FrameLabel and DefineSceneAndFrameLabelData tags are used, duplicates are removed.
+    pub fn frame_labels(&self) -> Vec<(u32, &str)> {
+        let mut labels = Vec::new();
+        let mut frame = 0;
+        for tag in &self.tags {
+            match tag {
+                Tag::ShowFrame(_) => frame += 1,
+                Tag::FrameLabel(t) => labels.push((frame, t.name.as_str())),
+                Tag::DefineSceneAndFrameLabelData(t) => {
+                    labels.extend(t.labels.iter().map(|l| (l.frame, l.name.as_str())))
+                }
+                _ => {}
+            }
+        }
+        labels.sort_unstable();
+        labels.dedup();
+        labels
+    }
+    /// Get the index of the frame with the given label
+    pub fn label_frame(&self, name: &str) -> Option<u32> {
+        self.frame_labels()
+            .into_iter()
+            .find(|(_, label)| *label == name)
+            .map(|(frame, _)| frame)
+    }
+
+    /// Dynamic and input text fields
+    pub fn edit_texts(&self) -> impl Iterator<Item = &'_ DefineEditTextTag> {
+        self.tags.iter().filter_map(|t| match t {
+            Tag::DefineEditText(t) => Some(t.as_ref()),
+            _ => None,
+        })
+    }
+    pub fn edit_texts_mut(&mut self) -> impl Iterator<Item = &'_ mut DefineEditTextTag> {
+        self.tags.iter_mut().filter_map(|t| match t {
+            Tag::DefineEditText(t) => Some(t.as_mut()),
+            _ => None,
+        })
+    }
+}
+
+fn hash_tag(id: u16, data: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    id.hash(&mut hasher);
+    data.hash(&mut hasher);
+    hasher.finish()
 }
 
 fn is_valid_compression(signature: u8) -> Result<Compression> {
@@ -259,8 +744,16 @@ impl fmt::Display for Compression {
 
 #[cfg(test)]
 mod tests {
-    use super::{is_valid_compression, Compression, Header};
-    use crate::StreamReader;
+    use super::{is_valid_compression, Compression, Header, ReadOptions, UFixed8};
+    use crate::{
+        swf::tags::{
+            Asset, DefineFontInfoTag, DefineScalingGridTag, DefineSceneAndFrameLabelDataTag,
+            EndTag, FontInfoFlag, FrameLabel, FrameLabelTag, MetadataTag, ProtectTag, Scene,
+            SetBackgroundColorTag, SetTabIndexTag, ShowFrameTag, SymbolClassTag, Tag, TagID,
+            UnknownTag,
+        },
+        Movie, StreamReader, StreamWriter,
+    };
 
     #[test]
     pub fn valid_compression() {
@@ -278,4 +771,354 @@ mod tests {
         assert_eq!(header.version, 14);
         assert_eq!(header.file_length, 69420);
     }
+
+    #[test]
+    pub fn scenes_and_labels() {
+        let movie = Movie {
+            tags: vec![
+                Tag::DefineSceneAndFrameLabelData(DefineSceneAndFrameLabelDataTag {
+                    scenes: vec![
+                        Scene {
+                            offset: 0,
+                            name: "Intro".to_string(),
+                        },
+                        Scene {
+                            offset: 2,
+                            name: "Main".to_string(),
+                        },
+                    ],
+                    labels: vec![FrameLabel {
+                        frame: 2,
+                        name: "start".to_string(),
+                    }],
+                }),
+                Tag::ShowFrame(ShowFrameTag {}),
+                Tag::ShowFrame(ShowFrameTag {}),
+                Tag::FrameLabel(FrameLabelTag {
+                    name: "start".to_string(),
+                    is_anchor: false,
+                }),
+                Tag::ShowFrame(ShowFrameTag {}),
+                Tag::FrameLabel(FrameLabelTag {
+                    name: "loop".to_string(),
+                    is_anchor: true,
+                }),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(movie.frame_labels(), vec![(2, "start"), (3, "loop")]);
+        assert_eq!(movie.label_frame("loop"), Some(3));
+        assert_eq!(movie.label_frame("end"), None);
+        assert_eq!(movie.scenes().len(), 2);
+        assert_eq!(movie.scene_at(1).unwrap().name, "Intro");
+        assert_eq!(movie.scene_at(5).unwrap().name, "Main");
+    }
+
+    #[test]
+    pub fn strip_and_insert_header_tags() {
+        let mut movie = Movie {
+            tags: vec![
+                Tag::Metadata(MetadataTag {
+                    metadata: String::new(),
+                }),
+                Tag::Protect(ProtectTag::default()),
+                Tag::SetBackgroundColor(SetBackgroundColorTag {
+                    color: Default::default(),
+                }),
+                Tag::ShowFrame(ShowFrameTag {}),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(movie.strip_protect(), 1);
+        assert_eq!(movie.strip_protect(), 0);
+        movie.enable_debugger("$1$ab$hash".to_string());
+        movie.enable_debugger("$1$cd$hash".to_string());
+        let ids: Vec<TagID> = movie.tags.iter().map(|t| t.into()).collect();
+        assert_eq!(
+            ids,
+            [
+                TagID::Metadata,
+                TagID::EnableDebugger2,
+                TagID::SetBackgroundColor,
+                TagID::ShowFrame
+            ]
+        );
+        assert!(
+            matches!(&movie.tags[1], Tag::EnableDebugger2(t) if t.password_hash == "$1$cd$hash")
+        );
+        assert_eq!(movie.strip_debugger(), 1);
+    }
+
+    #[test]
+    pub fn typed_tags_round_trip() {
+        let font_info = DefineFontInfoTag {
+            font_id: 1,
+            name: b"Arial".to_vec(),
+            flags: FontInfoFlag::WIDE_CODES,
+            language: 0,
+            codes: vec![0x41, 0x3042],
+        };
+        let movie = Movie {
+            tags: vec![
+                Tag::DefineFontInfo(font_info.clone()),
+                Tag::DefineFontInfo2(font_info),
+                Tag::DefineScalingGrid(DefineScalingGridTag {
+                    character_id: 2,
+                    splitter: Default::default(),
+                }),
+                Tag::SetTabIndex(SetTabIndexTag {
+                    depth: 1,
+                    tab_index: 4,
+                }),
+                Tag::End(EndTag {}),
+            ],
+            ..Default::default()
+        };
+
+        let mut stream = StreamWriter::default();
+        movie.write(&mut stream).unwrap();
+        let read = Movie::read(StreamReader::new(stream.buffer())).unwrap();
+        assert_eq!(read.tags, movie.tags);
+
+        let mut file = Vec::new();
+        movie.write_to(&mut file).unwrap();
+        assert_eq!(&file, stream.buffer());
+        let read = Movie::read_from(file.as_slice()).unwrap();
+        assert_eq!(read.tags, movie.tags);
+    }
+
+    #[test]
+    pub fn malformed_tags_as_unknown() {
+        #[rustfmt::skip]
+        let data = [
+            b'F', b'W', b'S', 10, 26, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x18, 0x01, 0x00,
+            0x85, 0x10, 0x01, 0x00, 0x04, 0x00, 0x00, // SetTabIndex with a padding byte
+            0x42, 0x02, 0xFF, 0xFF, // truncated SetBackgroundColor
+            0x00, 0x00, // End
+        ];
+        let unknown = [
+            Tag::Unknown(UnknownTag {
+                id: TagID::SetTabIndex as u16,
+                data: vec![0x01, 0x00, 0x04, 0x00, 0x00],
+            }),
+            Tag::Unknown(UnknownTag {
+                id: TagID::SetBackgroundColor as u16,
+                data: vec![0xFF, 0xFF],
+            }),
+            Tag::End(EndTag {}),
+        ];
+        let movie = Movie::read(StreamReader::new(&data)).unwrap();
+        assert_eq!(movie.tags, unknown);
+        let mut stream = StreamWriter::default();
+        movie.write(&mut stream).unwrap();
+        assert_eq!(*stream.buffer(), data);
+
+        let movie = Movie::read_from(data.as_slice()).unwrap();
+        assert_eq!(movie.tags, unknown);
+    }
+
+    #[test]
+    pub fn preserve_encoding() {
+        #[rustfmt::skip]
+        let data = [
+            b'F', b'W', b'S', 10, 0x34, 0x12, 0x00, 0x00, // wrong file length
+            0x10, 0x00, // empty rect, with 2 bits per value
+            0x00, 0x18, 0x01, 0x00,
+            0x7F, 0x00, 0x00, 0x00, 0x00, 0x00, // ShowFrame with a long header
+            0x0A, 0x13, 0x02, 0x00, 0x02, 0x00, b'B', 0x00, 0x01, 0x00, b'A', 0x00, // SymbolClass
+            0x00, 0x00, // End
+            0xAA, 0xBB,
+        ];
+        let options = ReadOptions { preserve: true };
+        let mut movie = Movie::read_with_options(StreamReader::new(&data), &options).unwrap();
+        let mut stream = StreamWriter::default();
+        movie.write(&mut stream).unwrap();
+        assert_eq!(*stream.buffer(), data);
+        // The canonical encoding is only kept when it differs from the data
+        let preserved = movie.preserved.as_ref().unwrap();
+        assert!(preserved.tags.iter().all(|t| t.canonical.is_none()));
+
+        // Only the modified parts are written canonically
+        movie.framecount = 2;
+        movie.tags.insert(1, Tag::ShowFrame(ShowFrameTag {}));
+        let mut stream = StreamWriter::default();
+        movie.write(&mut stream).unwrap();
+        assert_eq!(
+            stream.buffer()[4..13],
+            [37, 0, 0, 0, 0x00, 0x00, 0x18, 0x02, 0x00]
+        );
+        assert_eq!(stream.buffer()[13..19], data[14..20]);
+        assert_eq!(stream.buffer()[19..21], [0x40, 0x00]);
+        assert_eq!(stream.buffer()[21..], data[20..]);
+
+        // A new ShowFrame placed first takes the header of the original one, matched in order
+        movie.tags.swap(0, 1);
+        let mut stream = StreamWriter::default();
+        movie.write(&mut stream).unwrap();
+        assert_eq!(stream.buffer()[13..19], data[14..20]);
+        assert_eq!(stream.buffer()[19..21], [0x40, 0x00]);
+
+        // A hash collision with another tag isn't written back as that tag
+        let mut movie = Movie::read_with_options(StreamReader::new(&data), &options).unwrap();
+        let preserved = movie.preserved.as_mut().unwrap();
+        preserved.tags[0].hash = preserved.tags[1].hash;
+        let mut stream = StreamWriter::default();
+        movie.write(&mut stream).unwrap();
+        assert_eq!(stream.buffer()[14..16], [0x40, 0x00]);
+        assert_eq!(stream.buffer()[16..], data[20..]);
+    }
+
+    #[test]
+    pub fn symbols() {
+        let mut movie = Movie {
+            tags: vec![
+                Tag::SymbolClass(SymbolClassTag {
+                    symbols: vec![Asset {
+                        char_id: 1,
+                        name: "A".to_string(),
+                    }],
+                }),
+                Tag::ShowFrame(ShowFrameTag {}),
+                Tag::SymbolClass(SymbolClassTag::default()),
+                Tag::End(EndTag {}),
+            ],
+            ..Default::default()
+        };
+
+        movie.add_symbol(0, "Main".to_string());
+        movie.add_symbol(2, "A".to_string());
+        assert!(
+            matches!(&movie.tags[2], Tag::SymbolClass(t) if t.symbols.len() == 2 && t.symbols[0].name == "Main")
+        );
+        assert_eq!(movie.symbol_name(0), Some("Main"));
+        assert_eq!(movie.symbol_id("A"), Some(1));
+        assert_eq!(movie.rename_symbol("A", "B"), 2);
+        assert_eq!(movie.symbol_name(2), Some("B"));
+        assert_eq!(movie.remove_symbol("B"), 2);
+        let names: Vec<&str> = movie.symbols().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["Main"]);
+
+        let mut movie = Movie::default();
+        movie.tags.push(Tag::ShowFrame(ShowFrameTag {}));
+        movie.add_symbol(0, "Main".to_string());
+        assert!(matches!(movie.tags[0], Tag::SymbolClass(_)));
+    }
+
+    #[test]
+    pub fn fractional_framerate() {
+        let mut movie = Movie::default();
+        movie.set_fps(29.97);
+        assert_eq!(movie.framerate, UFixed8(29 << 8 | 248));
+        movie.tags.push(Tag::End(EndTag {}));
+
+        let mut stream = StreamWriter::default();
+        movie.write(&mut stream).unwrap();
+        let read = Movie::read(StreamReader::new(stream.buffer())).unwrap();
+        assert_eq!(read.framerate, movie.framerate);
+        assert_eq!(read.fps(), 29.96875);
+
+        movie.set_fps(200.0);
+        assert_eq!(movie.fps(), 200.0);
+        assert_eq!(movie.framerate.to_f64(), 200.0);
+    }
+
+    #[test]
+    #[cfg(any(feature = "flate2", feature = "miniz_oxide"))]
+    pub fn write_options() {
+        use super::WriteOptions;
+
+        let movie = Movie {
+            compression: Compression::Zlib,
+            tags: vec![
+                Tag::SetBackgroundColor(SetBackgroundColorTag {
+                    color: Default::default(),
+                }),
+                Tag::ShowFrame(ShowFrameTag {}),
+                Tag::End(EndTag {}),
+            ],
+            ..Default::default()
+        };
+        let write = |movie: &Movie, options: &WriteOptions| {
+            let mut stream = StreamWriter::default();
+            movie.write_with_options(&mut stream, options).unwrap();
+            stream.move_buffer()
+        };
+
+        let stored = write(
+            &movie,
+            &WriteOptions {
+                zlib_level: 0,
+                ..Default::default()
+            },
+        );
+        let best = write(&movie, &WriteOptions::default());
+        assert_ne!(stored, best);
+        assert_eq!(best, write(&movie, &WriteOptions::default()));
+        let read = Movie::read(StreamReader::new(&stored)).unwrap();
+        assert_eq!(read.tags, movie.tags);
+
+        let options = ReadOptions { preserve: true };
+        let mut read = Movie::read_with_options(StreamReader::new(&stored), &options).unwrap();
+        let keep = WriteOptions {
+            keep_compressed: true,
+            ..Default::default()
+        };
+        assert_eq!(write(&read, &keep), stored);
+        assert_eq!(write(&read, &WriteOptions::default()), best);
+        read.tags.remove(0);
+        assert_ne!(write(&read, &keep).len(), stored.len());
+
+        #[cfg(feature = "lzma-rs")]
+        {
+            let movie = Movie {
+                compression: Compression::Lzma,
+                ..movie
+            };
+            let lzma = write(
+                &movie,
+                &WriteOptions {
+                    lzma_dict_size: 0x10000,
+                    ..Default::default()
+                },
+            );
+            assert_eq!(lzma[13..17], 0x10000u32.to_le_bytes());
+            let read = Movie::read(StreamReader::new(&lzma)).unwrap();
+            assert_eq!(read.tags, movie.tags);
+        }
+    }
+
+    #[test]
+    #[cfg(not(any(feature = "flate2", feature = "miniz_oxide", feature = "lzma-rs")))]
+    pub fn unsupported_compression() {
+        use crate::error::RabcError;
+
+        for (compression, name) in [(Compression::Zlib, "zlib"), (Compression::Lzma, "lzma")] {
+            let movie = Movie {
+                compression,
+                tags: vec![Tag::End(EndTag {})],
+                ..Default::default()
+            };
+            let mut stream = StreamWriter::default();
+            let err = movie.write(&mut stream).unwrap_err();
+            assert!(matches!(err, RabcError::UnsupportedCompression(n) if n == name));
+        }
+
+        let movie = Movie {
+            tags: vec![Tag::End(EndTag {})],
+            ..Default::default()
+        };
+        let mut stream = StreamWriter::default();
+        movie.write(&mut stream).unwrap();
+        let mut data = stream.move_buffer();
+        assert_eq!(
+            Movie::read(StreamReader::new(&data)).unwrap().tags,
+            movie.tags
+        );
+        data[0] = b'C';
+        let err = Movie::read(StreamReader::new(&data)).unwrap_err();
+        assert!(matches!(err, RabcError::UnsupportedCompression("zlib")));
+    }
 }
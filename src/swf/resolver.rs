@@ -0,0 +1,155 @@
+use crate::Movie;
+use std::collections::BTreeMap;
+
+/// Link the characters imported by a movie to the movies exporting them.
+/// Movies are keyed by the URL or path used by the ImportAssets tags, which is matched exactly,
+/// and visited in the order of their URL.
+#[derive(Clone, Debug, Default)]
+pub struct ImportResolver<'a> {
+    movies: BTreeMap<&'a str, &'a Movie>,
+}
+
+/// A character imported by name, and where it's defined if it could be resolved
+#[derive(Clone, Debug)]
+pub struct ResolvedImport<'a> {
+    /// URL of the movie the character is imported from
+    pub url: &'a str,
+    pub name: &'a str,
+    /// Id of the character in the importing movie
+    pub char_id: u16,
+    /// The movie defining the character and its id in that movie.
+    /// Characters exported after being imported are followed to their definition.
+    pub target: Option<(&'a str, &'a Movie, u16)>,
+}
+
+impl<'a> ImportResolver<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, url: &'a str, movie: &'a Movie) {
+        self.movies.insert(url, movie);
+    }
+
+    pub fn movie(&self, url: &str) -> Option<&'a Movie> {
+        self.movies.get(url).copied()
+    }
+
+    /// Find the movie defining an exported character, and its id in that movie
+    pub fn lookup(&self, url: &str, name: &str) -> Option<(&'a str, &'a Movie, u16)> {
+        let mut url = url;
+        let mut name = name;
+        // An exported character can be imported from another movie, follow the chain but
+        // give up if it's longer than the number of movies to avoid cycles
+        for _ in 0..=self.movies.len() {
+            let (&key, &movie) = self.movies.get_key_value(url)?;
+            let char_id = movie.export(name)?;
+            let import = movie.imports().find_map(|tag| {
+                tag.assets
+                    .iter()
+                    .find(|a| a.char_id == char_id)
+                    .map(|a| (tag.url.as_str(), a.name.as_str()))
+            });
+            match import {
+                Some((next_url, next_name)) => (url, name) = (next_url, next_name),
+                None => return Some((key, movie, char_id)),
+            }
+        }
+        None
+    }
+
+    /// Resolve all the characters imported by the movie
+    pub fn resolve(&self, movie: &'a Movie) -> Vec<ResolvedImport<'a>> {
+        movie
+            .imports()
+            .flat_map(|tag| {
+                tag.assets.iter().map(|asset| ResolvedImport {
+                    url: &tag.url,
+                    name: &asset.name,
+                    char_id: asset.char_id,
+                    target: self.lookup(&tag.url, &asset.name),
+                })
+            })
+            .collect()
+    }
+
+    /// Imports of all the movies that can't be resolved, with the URL of the importing movie
+    pub fn unresolved(&self) -> Vec<(&'a str, ResolvedImport<'a>)> {
+        self.movies
+            .iter()
+            .flat_map(|(&url, &movie)| {
+                self.resolve(movie)
+                    .into_iter()
+                    .filter(|import| import.target.is_none())
+                    .map(move |import| (url, import))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::swf::tags::{Asset, ExportAssetsTag, ImportAssetsTag, Tag};
+
+    fn asset(char_id: u16, name: &str) -> Asset {
+        Asset {
+            char_id,
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn resolve_imports() {
+        let fonts = Movie {
+            tags: vec![Tag::ExportAssets(ExportAssetsTag {
+                assets: vec![asset(1, "Arial")],
+            })],
+            ..Default::default()
+        };
+        let library = Movie {
+            tags: vec![
+                Tag::ImportAssets2(ImportAssetsTag {
+                    url: "fonts.swf".to_string(),
+                    assets: vec![asset(5, "Arial")],
+                }),
+                Tag::ExportAssets(ExportAssetsTag {
+                    assets: vec![asset(2, "Button"), asset(5, "LibFont")],
+                }),
+                Tag::ImportAssets2(ImportAssetsTag {
+                    url: "missing.swf".to_string(),
+                    assets: vec![asset(6, "Gone")],
+                }),
+            ],
+            ..Default::default()
+        };
+        let main = Movie {
+            tags: vec![Tag::ImportAssets(ImportAssetsTag {
+                url: "library.swf".to_string(),
+                assets: vec![asset(1, "Button"), asset(2, "LibFont"), asset(3, "Missing")],
+            })],
+            ..Default::default()
+        };
+
+        let mut resolver = ImportResolver::new();
+        resolver.add("fonts.swf", &fonts);
+        resolver.add("library.swf", &library);
+        resolver.add("main.swf", &main);
+
+        let imports = resolver.resolve(&main);
+        assert_eq!(imports.len(), 3);
+        let (url, _, id) = imports[0].target.unwrap();
+        assert_eq!((url, id), ("library.swf", 2));
+        let (url, _, id) = imports[1].target.unwrap();
+        assert_eq!((url, id), ("fonts.swf", 1));
+        assert!(imports[2].target.is_none());
+
+        // Sorted by the URL of the importing movie
+        let unresolved = resolver.unresolved();
+        assert_eq!(unresolved.len(), 2);
+        assert_eq!(unresolved[0].0, "library.swf");
+        assert_eq!(unresolved[0].1.name, "Gone");
+        assert_eq!(unresolved[1].0, "main.swf");
+        assert_eq!(unresolved[1].1.name, "Missing");
+    }
+}
@@ -0,0 +1,362 @@
+//! SWD files contain the debug information of movies compiled in debug mode.
+//! They are matched with their movie using the DebugID tag.
+//! AVM1 actions are located with the offset records, while AVM2 methods carry their own
+//! debugfile and debugline instructions, the SWD providing the source of their scripts.
+use crate::{
+    abc::AbcFile,
+    error::{RabcError, Result},
+    Movie, StreamReader, StreamWriter,
+};
+
+const TAG_SCRIPT: u32 = 0;
+const TAG_OFFSET: u32 = 1;
+const TAG_BREAKPOINT: u32 = 2;
+const TAG_ID: u32 = 3;
+const TAG_REGISTERS: u32 = 5;
+
+/// Source file of the movie
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct SwdScript {
+    pub id: u32,
+    pub bitmap: u32,
+    pub name: String,
+    pub text: String,
+}
+
+/// Location in the movie of the action compiled from a line of a script
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct SwdOffset {
+    pub script_id: u32,
+    pub line: u32,
+    /// Offset of the action in the uncompressed movie
+    pub offset: u32,
+}
+
+/// Names of the registers used by a function
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct SwdRegisters {
+    pub offset: u32,
+    pub registers: Vec<(u8, String)>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SwdTag {
+    Script(SwdScript),
+    Offset(SwdOffset),
+    /// Offset of an action with a breakpoint
+    Breakpoint(u16),
+    DebugId([u8; 16]),
+    Registers(SwdRegisters),
+    /// Record of an unknown type, followed by the rest of the file.
+    /// The records don't have a length, so nothing can be read after it.
+    Unknown(u32, Vec<u8>),
+}
+
+/// Source location of an AVM2 instruction
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SourceLocation<'a> {
+    /// File name given by the debugfile instruction
+    pub file: &'a str,
+    pub line: u32,
+    /// Script of the debug file with the same name, holding its source
+    pub script: Option<&'a SwdScript>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct SwdFile {
+    pub version: u8,
+    pub tags: Vec<SwdTag>,
+}
+
+impl SwdFile {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn read(stream: &mut StreamReader) -> Result<Self> {
+        let mut signature = [0u8; 3];
+        stream.read_exact(&mut signature)?;
+        if &signature != b"FWD" {
+            return Err(RabcError::InvalidSignature(
+                String::from_utf8_lossy(&signature).into_owned(),
+            ));
+        }
+        let version = stream.read_u8()?;
+
+        let mut tags = Vec::new();
+        while !stream.finished() {
+            let tag = stream.read_u32()?;
+            tags.push(match tag {
+                TAG_SCRIPT => SwdTag::Script(SwdScript {
+                    id: stream.read_u32()?,
+                    bitmap: stream.read_u32()?,
+                    name: stream.read_null_string()?,
+                    text: stream.read_null_string()?,
+                }),
+                TAG_OFFSET => SwdTag::Offset(SwdOffset {
+                    script_id: stream.read_u32()?,
+                    line: stream.read_u32()?,
+                    offset: stream.read_u32()?,
+                }),
+                TAG_BREAKPOINT => SwdTag::Breakpoint(stream.read_u16()?),
+                TAG_ID => {
+                    let mut id = [0u8; 16];
+                    stream.read_exact(&mut id)?;
+                    SwdTag::DebugId(id)
+                }
+                TAG_REGISTERS => {
+                    let offset = stream.read_u32()?;
+                    let count = stream.read_u8()?;
+                    let mut registers = Vec::with_capacity(count as usize);
+                    for _ in 0..count {
+                        registers.push((stream.read_u8()?, stream.read_null_string()?));
+                    }
+                    SwdTag::Registers(SwdRegisters { offset, registers })
+                }
+                _ => {
+                    let mut data = vec![0u8; stream.remaining() as usize];
+                    stream.read_exact(&mut data)?;
+                    SwdTag::Unknown(tag, data)
+                }
+            });
+        }
+        Ok(Self { version, tags })
+    }
+
+    pub fn write(&self, stream: &mut StreamWriter) -> Result<()> {
+        stream.write_exact(b"FWD")?;
+        stream.write_u8(self.version)?;
+        for tag in &self.tags {
+            match tag {
+                SwdTag::Script(script) => {
+                    stream.write_u32(TAG_SCRIPT)?;
+                    stream.write_u32(script.id)?;
+                    stream.write_u32(script.bitmap)?;
+                    stream.write_null_string(&script.name)?;
+                    stream.write_null_string(&script.text)?;
+                }
+                SwdTag::Offset(offset) => {
+                    stream.write_u32(TAG_OFFSET)?;
+                    stream.write_u32(offset.script_id)?;
+                    stream.write_u32(offset.line)?;
+                    stream.write_u32(offset.offset)?;
+                }
+                SwdTag::Breakpoint(offset) => {
+                    stream.write_u32(TAG_BREAKPOINT)?;
+                    stream.write_u16(*offset)?;
+                }
+                SwdTag::DebugId(id) => {
+                    stream.write_u32(TAG_ID)?;
+                    stream.write_exact(id)?;
+                }
+                SwdTag::Registers(registers) => {
+                    stream.write_u32(TAG_REGISTERS)?;
+                    stream.write_u32(registers.offset)?;
+                    stream.write_u8(registers.registers.len() as u8)?;
+                    for (register, name) in &registers.registers {
+                        stream.write_u8(*register)?;
+                        stream.write_null_string(name)?;
+                    }
+                }
+                SwdTag::Unknown(tag, data) => {
+                    stream.write_u32(*tag)?;
+                    stream.write_exact(data)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn debug_id(&self) -> Option<[u8; 16]> {
+        self.tags.iter().find_map(|t| match t {
+            SwdTag::DebugId(id) => Some(*id),
+            _ => None,
+        })
+    }
+    /// Whether the debug file was generated for this movie, using their DebugID
+    pub fn matches(&self, movie: &Movie) -> bool {
+        self.debug_id()
+            .is_some_and(|id| movie.debug_id() == Some(id))
+    }
+
+    /// Select the debug file generated for the movie among `files`, using their DebugID
+    pub fn select<'a>(files: &'a [SwdFile], movie: &Movie) -> Option<&'a SwdFile> {
+        files.iter().find(|f| f.matches(movie))
+    }
+
+    pub fn scripts(&self) -> impl Iterator<Item = &'_ SwdScript> {
+        self.tags.iter().filter_map(|t| match t {
+            SwdTag::Script(t) => Some(t),
+            _ => None,
+        })
+    }
+    pub fn script(&self, id: u32) -> Option<&SwdScript> {
+        self.scripts().find(|s| s.id == id)
+    }
+
+    /// Find the script and line of the action at the given offset in the movie.
+    /// Actions without an entry are attributed to the closest previous one.
+    pub fn lookup(&self, offset: u32) -> Option<(&SwdScript, u32)> {
+        let entry = self
+            .tags
+            .iter()
+            .filter_map(|t| match t {
+                SwdTag::Offset(t) if t.offset <= offset => Some(t),
+                _ => None,
+            })
+            .max_by_key(|t| t.offset)?;
+        Some((self.script(entry.script_id)?, entry.line))
+    }
+
+    /// Find the source location of the instruction at `addr` in the method `method` of an ABC
+    /// file, from its debugfile and debugline instructions.
+    /// The script is found by name, the path separators of the debugfile being ignored.
+    pub fn locate<'a>(
+        &'a self,
+        abc: &'a AbcFile,
+        method: u32,
+        addr: u32,
+    ) -> Result<Option<SourceLocation<'a>>> {
+        let methods = &abc.abc.methods;
+        let method = methods
+            .get(method as usize)
+            .ok_or(RabcError::IndexOutOfBounds(
+                "methods",
+                method as usize,
+                methods.len(),
+            ))?;
+        let table = method.line_table()?;
+        let Some(entry) = table.lookup(addr) else {
+            return Ok(None);
+        };
+        let file = abc.cpool.get_str(entry.file)?.as_str();
+        let script = self.scripts().find(|s| same_source(&s.name, file));
+        Ok(Some(SourceLocation {
+            file,
+            line: entry.line,
+            script,
+        }))
+    }
+}
+
+/// Compare two source paths, whatever their separators.
+/// The debugfile instructions separate the source directory, package and file with `;`.
+fn same_source(a: &str, b: &str) -> bool {
+    let parts = |s: &'_ str| {
+        s.split([';', '/', '\\'])
+            .filter(|p| !p.is_empty())
+            .map(str::to_owned)
+            .collect::<Vec<_>>()
+    };
+    parts(a) == parts(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        abc::Method,
+        swf::tags::{DebugIdTag, Tag},
+    };
+
+    #[test]
+    fn round_trip_and_lookup() {
+        let id = *b"0123456789abcdef";
+        let swd = SwdFile {
+            version: 7,
+            tags: vec![
+                SwdTag::DebugId(id),
+                SwdTag::Script(SwdScript {
+                    id: 1,
+                    bitmap: 0,
+                    name: "Scene 1: Frame 1".to_string(),
+                    text: "trace(1);\ntrace(2);".to_string(),
+                }),
+                SwdTag::Offset(SwdOffset {
+                    script_id: 1,
+                    line: 1,
+                    offset: 40,
+                }),
+                SwdTag::Offset(SwdOffset {
+                    script_id: 1,
+                    line: 2,
+                    offset: 52,
+                }),
+                SwdTag::Breakpoint(52),
+                SwdTag::Registers(SwdRegisters {
+                    offset: 40,
+                    registers: vec![(1, "this".to_string())],
+                }),
+            ],
+        };
+
+        let mut stream = StreamWriter::default();
+        swd.write(&mut stream).unwrap();
+        let mut reader = StreamReader::new(stream.buffer());
+        assert_eq!(SwdFile::read(&mut reader).unwrap(), swd);
+
+        assert!(swd.lookup(39).is_none());
+        assert_eq!(swd.lookup(45).unwrap().1, 1);
+        let (script, line) = swd.lookup(60).unwrap();
+        assert_eq!((script.id, line), (1, 2));
+
+        let mut movie = Movie::default();
+        assert!(!swd.matches(&movie));
+        movie.tags.push(Tag::DebugID(DebugIdTag { uuid: id }));
+        assert!(swd.matches(&movie));
+    }
+
+    #[test]
+    fn unknown_record() {
+        #[rustfmt::skip]
+        let data = [
+            b'F', b'W', b'D', 7,
+            2, 0, 0, 0, 0x34, 0x00, // breakpoint
+            9, 0, 0, 0, 1, 2, 3, // unknown record
+        ];
+        let swd = SwdFile::read(&mut StreamReader::new(&data)).unwrap();
+        assert_eq!(swd.tags[1], SwdTag::Unknown(9, vec![1, 2, 3]));
+        let mut stream = StreamWriter::default();
+        swd.write(&mut stream).unwrap();
+        assert_eq!(*stream.buffer(), data);
+    }
+
+    #[test]
+    fn locate_method() {
+        let id = *b"0123456789abcdef";
+        let swd = SwdFile {
+            version: 10,
+            tags: vec![
+                SwdTag::DebugId(id),
+                SwdTag::Script(SwdScript {
+                    id: 1,
+                    bitmap: 0,
+                    name: "C:\\src\\com\\Main.as".to_string(),
+                    text: "package com {}".to_string(),
+                }),
+            ],
+        };
+        let mut abc = AbcFile::new();
+        abc.cpool.strings = vec![String::new(), "C:\\src;com;Main.as".to_string()];
+        abc.abc.methods.push(Method {
+            #[rustfmt::skip]
+            code: vec![
+                0xF1, 0x01, // debugfile 1
+                0xF0, 0x07, // debugline 7
+                0x47, // returnvoid
+            ],
+            ..Default::default()
+        });
+
+        let mut movie = Movie::default();
+        movie.tags.push(Tag::DebugID(DebugIdTag { uuid: id }));
+        let files = [SwdFile::new(), swd];
+        let swd = SwdFile::select(&files, &movie).unwrap();
+        let location = swd.locate(&abc, 0, 4).unwrap().unwrap();
+        assert_eq!(location.file, "C:\\src;com;Main.as");
+        assert_eq!(location.line, 7);
+        assert_eq!(location.script.unwrap().id, 1);
+        assert_eq!(swd.locate(&abc, 0, 0).unwrap(), None);
+        swd.locate(&abc, 1, 0).unwrap_err();
+    }
+}
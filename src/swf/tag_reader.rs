@@ -0,0 +1,432 @@
+use super::{
+    datatypes::{Rect, UFixed8},
+    movie::{Compression, Header},
+    tags::{Tag, TagID},
+};
+use crate::{error::Result, StreamReader};
+use std::io::{ErrorKind, Read};
+
+/// Header of a tag, read before its data
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TagHeader {
+    pub id: u16,
+    /// Length of the tag's data, without the header
+    pub length: u32,
+    /// Whether the length was stored on 4 bytes, even if it is smaller than 0x3F
+    pub long: bool,
+}
+
+/// Read the tags of a movie one at a time, from any reader.
+/// The movie is decompressed as the tags are read, so only the current tag is kept in memory.
+/// LZMA movies are the exception: lzma-rs only outputs data once its dictionary is full, and
+/// it is usually larger than the movie, so they are decompressed whole before the first tag.
+pub struct TagReader<'a> {
+    reader: Box<dyn Read + 'a>,
+    pub header: Header,
+    pub framesize: Rect,
+    pub framerate: UFixed8,
+    pub framecount: u16,
+    finished: bool,
+}
+
+impl<'a> TagReader<'a> {
+    /// Read the movie's header
+    pub fn new<R: Read + 'a>(mut reader: R) -> Result<Self> {
+        let mut data = [0u8; 8];
+        reader.read_exact(&mut data)?;
+        let header = Header::read(&mut StreamReader::new(&data))?;
+        let size = header.file_length.saturating_sub(8) as usize;
+        let mut reader: Box<dyn Read + 'a> = match header.compression {
+            Compression::None => Box::new(reader),
+            Compression::Zlib => Self::zlib_reader(reader)?,
+            Compression::Lzma => Self::lzma_reader(reader, size)?,
+        };
+
+        // The rect's length depends on its first 5 bits
+        let mut data = vec![0u8; 1];
+        reader.read_exact(&mut data)?;
+        let n_bits = (data[0] >> 3) as usize;
+        data.resize((5 + 4 * n_bits).div_ceil(8), 0);
+        reader.read_exact(&mut data[1..])?;
+        let framesize = Rect::read(&mut StreamReader::new(&data))?;
+
+        let mut data = [0u8; 4];
+        reader.read_exact(&mut data)?;
+        let mut stream = StreamReader::new(&data);
+        let framerate = UFixed8::read(&mut stream)?;
+        let framecount = stream.read_u16()?;
+
+        Ok(Self {
+            reader,
+            header,
+            framesize,
+            framerate,
+            framecount,
+            finished: false,
+        })
+    }
+
+    #[cfg(feature = "flate2")]
+    fn zlib_reader<R: Read + 'a>(reader: R) -> Result<Box<dyn Read + 'a>> {
+        Ok(Box::new(flate2::read::ZlibDecoder::new(reader)))
+    }
+    #[cfg(all(feature = "miniz_oxide", not(feature = "flate2")))]
+    fn zlib_reader<R: Read + 'a>(reader: R) -> Result<Box<dyn Read + 'a>> {
+        Ok(Box::new(MinizReader::new(reader)))
+    }
+    #[cfg(not(any(feature = "flate2", feature = "miniz_oxide")))]
+    fn zlib_reader<R: Read + 'a>(_reader: R) -> Result<Box<dyn Read + 'a>> {
+        Err(crate::error::RabcError::UnsupportedCompression("zlib"))
+    }
+
+    #[cfg(feature = "lzma-rs")]
+    fn lzma_reader<R: Read + 'a>(mut reader: R, size: usize) -> Result<Box<dyn Read + 'a>> {
+        // Skip the compressed length, see StreamReader::inflate_lzma
+        reader.read_exact(&mut [0u8; 4])?;
+        Ok(Box::new(LzmaReader::new(reader, size)))
+    }
+    #[cfg(not(feature = "lzma-rs"))]
+    fn lzma_reader<R: Read + 'a>(_reader: R, _size: usize) -> Result<Box<dyn Read + 'a>> {
+        Err(crate::error::RabcError::UnsupportedCompression("lzma"))
+    }
+
+    /// Read the header of the next tag, or `None` after the End tag.
+    /// Its data must then be read with [`TagReader::read_tag`] or skipped with [`TagReader::skip_tag`].
+    pub fn read_header(&mut self) -> Result<Option<TagHeader>> {
+        if self.finished {
+            return Ok(None);
+        }
+        let mut data = [0u8; 2];
+        match self.reader.read_exact(&mut data) {
+            // Some movies don't have an End tag
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => {
+                self.finished = true;
+                return Ok(None);
+            }
+            result => result?,
+        }
+        let hdr = u16::from_le_bytes(data);
+        let id = hdr >> 6;
+        let (length, long) = match hdr & 0x3F {
+            0x3F => {
+                let mut data = [0u8; 4];
+                self.reader.read_exact(&mut data)?;
+                (u32::from_le_bytes(data), true)
+            }
+            length => (length.into(), false),
+        };
+        self.finished = id == TagID::End as u16;
+        Ok(Some(TagHeader { id, length, long }))
+    }
+
+    /// Read and decode the data of the tag
+    pub fn read_tag(&mut self, header: &TagHeader) -> Result<Tag> {
+        let mut data = vec![0u8; header.length as usize];
+        self.reader.read_exact(&mut data)?;
+        Ok(Tag::read_data(header.id, &data))
+    }
+
+    /// Skip the data of the tag, without decoding it
+    pub fn skip_tag(&mut self, header: &TagHeader) -> Result<()> {
+        let skipped = std::io::copy(
+            &mut self.reader.by_ref().take(header.length.into()),
+            &mut std::io::sink(),
+        )?;
+        if skipped < header.length.into() {
+            return Err(std::io::Error::from(ErrorKind::UnexpectedEof).into());
+        }
+        Ok(())
+    }
+
+    /// Find the next tag with the given id, without decoding the other tags
+    pub fn find(&mut self, tag_id: TagID) -> Result<Option<Tag>> {
+        while let Some(header) = self.read_header()? {
+            if TagID::from_u16(header.id) == tag_id {
+                return self.read_tag(&header).map(Some);
+            }
+            self.skip_tag(&header)?;
+        }
+        Ok(None)
+    }
+}
+
+impl Iterator for TagReader<'_> {
+    type Item = Result<(TagID, TagHeader, Tag)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let header = match self.read_header() {
+            Ok(header) => header?,
+            Err(e) => {
+                self.finished = true;
+                return Some(Err(e));
+            }
+        };
+        let tag = self.read_tag(&header);
+        if tag.is_err() {
+            self.finished = true;
+        }
+        Some(tag.map(|tag| (TagID::from_u16(header.id), header, tag)))
+    }
+}
+
+/// Incremental zlib decompression, when miniz_oxide is used without flate2
+#[cfg(all(feature = "miniz_oxide", not(feature = "flate2")))]
+struct MinizReader<R: Read> {
+    reader: R,
+    state: Box<miniz_oxide::inflate::stream::InflateState>,
+    input: Vec<u8>,
+    pos: usize,
+    finished: bool,
+}
+
+#[cfg(all(feature = "miniz_oxide", not(feature = "flate2")))]
+impl<R: Read> MinizReader<R> {
+    fn new(reader: R) -> Self {
+        use miniz_oxide::{inflate::stream::InflateState, DataFormat};
+
+        Self {
+            reader,
+            state: InflateState::new_boxed(DataFormat::Zlib),
+            input: Vec::new(),
+            pos: 0,
+            finished: false,
+        }
+    }
+}
+
+#[cfg(all(feature = "miniz_oxide", not(feature = "flate2")))]
+impl<R: Read> Read for MinizReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        use miniz_oxide::{inflate::stream::inflate, MZError, MZFlush, MZStatus};
+
+        while !self.finished && !buf.is_empty() {
+            let mut eof = false;
+            if self.pos == self.input.len() {
+                self.input.resize(0x4000, 0);
+                let n = self.reader.read(&mut self.input)?;
+                self.input.truncate(n);
+                self.pos = 0;
+                eof = n == 0;
+            }
+
+            let result = inflate(&mut self.state, &self.input[self.pos..], buf, MZFlush::None);
+            self.pos += result.bytes_consumed;
+            match result.status {
+                Ok(MZStatus::StreamEnd) => self.finished = true,
+                Ok(_) => {}
+                // More input is needed
+                Err(MZError::Buf) if !eof => {}
+                Err(e) => {
+                    return Err(std::io::Error::new(
+                        ErrorKind::InvalidData,
+                        format!("Invalid deflate stream: {:?}", e),
+                    ))
+                }
+            }
+            if result.bytes_written > 0 {
+                return Ok(result.bytes_written);
+            }
+        }
+        Ok(0)
+    }
+}
+
+/// LZMA decompression through the writer interface of lzma-rs.
+/// It only outputs data once its dictionary is full or the stream is finished, and the
+/// dictionary of a movie is usually larger than the movie, so this rarely returns anything
+/// before all of it is decompressed.
+#[cfg(feature = "lzma-rs")]
+struct LzmaReader<R: Read> {
+    reader: R,
+    stream: Option<lzma_rs::decompress::Stream<Vec<u8>>>,
+    output: Vec<u8>,
+    pos: usize,
+}
+
+#[cfg(feature = "lzma-rs")]
+impl<R: Read> LzmaReader<R> {
+    fn new(reader: R, size: usize) -> Self {
+        use lzma_rs::decompress::{Options, Stream, UnpackedSize::UseProvided};
+
+        let options = Options {
+            unpacked_size: UseProvided(Some(size as u64)),
+            allow_incomplete: true,
+            memlimit: None,
+        };
+        Self {
+            reader,
+            stream: Some(Stream::new_with_options(&options, Vec::new())),
+            output: Vec::new(),
+            pos: 0,
+        }
+    }
+}
+
+#[cfg(feature = "lzma-rs")]
+impl<R: Read> Read for LzmaReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        use std::io::Write;
+
+        loop {
+            if self.pos < self.output.len() {
+                let n = buf.len().min(self.output.len() - self.pos);
+                buf[..n].copy_from_slice(&self.output[self.pos..self.pos + n]);
+                self.pos += n;
+                return Ok(n);
+            }
+            let Some(stream) = &mut self.stream else {
+                return Ok(0);
+            };
+
+            self.pos = 0;
+            self.output.clear();
+            let mut chunk = [0u8; 0x4000];
+            let n = self.reader.read(&mut chunk)?;
+            let mut input = &chunk[..n];
+            while !input.is_empty() {
+                match stream.write(input)? {
+                    0 => break,
+                    written => input = &input[written..],
+                }
+            }
+            // The decompressor stops consuming data once the unpacked size is reached
+            if n == 0 || !input.is_empty() {
+                let stream = self.stream.take().unwrap();
+                self.output = stream
+                    .finish()
+                    .map_err(|e| std::io::Error::new(ErrorKind::InvalidData, e.to_string()))?;
+            } else if let Some(output) = stream.get_output_mut() {
+                std::mem::swap(output, &mut self.output);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        swf::tags::{Asset, EndTag, FrameLabelTag, ShowFrameTag, SymbolClassTag},
+        Movie, StreamWriter,
+    };
+
+    fn movie(compression: Compression) -> Vec<u8> {
+        let movie = Movie {
+            compression,
+            framerate: UFixed8::from_f64(200.0),
+            framecount: 2,
+            tags: vec![
+                Tag::FrameLabel(FrameLabelTag {
+                    name: "a".repeat(100),
+                    is_anchor: false,
+                }),
+                Tag::ShowFrame(ShowFrameTag {}),
+                Tag::SymbolClass(SymbolClassTag {
+                    symbols: vec![Asset {
+                        char_id: 0,
+                        name: "Main".to_string(),
+                    }],
+                }),
+                Tag::End(EndTag {}),
+            ],
+            ..Default::default()
+        };
+        let mut stream = StreamWriter::default();
+        movie.write(&mut stream).unwrap();
+        stream.move_buffer()
+    }
+
+    #[test]
+    fn read_tags() {
+        let data = movie(Compression::None);
+        let mut reader = TagReader::new(data.as_slice()).unwrap();
+        assert_eq!(reader.header.compression, Compression::None);
+        assert_eq!(reader.framerate.to_f64(), 200.0);
+        assert_eq!(reader.framecount, 2);
+
+        let (id, header, tag) = reader.next().unwrap().unwrap();
+        assert_eq!(id, TagID::FrameLabel);
+        assert_eq!((header.length, header.long), (101, true));
+        assert!(matches!(tag, Tag::FrameLabel(t) if t.name.len() == 100));
+        let ids: Vec<TagID> = reader.map(|t| t.unwrap().0).collect();
+        assert_eq!(ids, [TagID::ShowFrame, TagID::SymbolClass, TagID::End]);
+    }
+
+    #[test]
+    #[cfg(all(any(feature = "flate2", feature = "miniz_oxide"), feature = "lzma-rs"))]
+    fn buffered_input() {
+        use crate::swf::tags::DefineBinaryDataTag;
+        use std::{cell::Cell, rc::Rc};
+
+        /// Count the bytes read from the source
+        struct CountingReader<'a> {
+            data: &'a [u8],
+            count: Rc<Cell<usize>>,
+        }
+
+        impl Read for CountingReader<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                let n = self.data.read(buf)?;
+                self.count.set(self.count.get() + n);
+                Ok(n)
+            }
+        }
+
+        // Incompressible data, so the compressed movie is as large
+        let mut seed = 1u32;
+        let mut tags: Vec<Tag> = (0..8)
+            .map(|char_id| {
+                let data = (0..0x8000)
+                    .map(|_| {
+                        seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                        (seed >> 16) as u8
+                    })
+                    .collect();
+                Tag::DefineBinaryData(DefineBinaryDataTag { char_id, data })
+            })
+            .chain([Tag::End(EndTag {})])
+            .collect();
+
+        for compression in [Compression::Zlib, Compression::Lzma] {
+            let movie = Movie {
+                compression,
+                tags,
+                ..Default::default()
+            };
+            let mut stream = StreamWriter::default();
+            movie.write(&mut stream).unwrap();
+            let data = stream.move_buffer();
+
+            let count = Rc::new(Cell::new(0));
+            let mut reader = TagReader::new(CountingReader {
+                data: &data,
+                count: count.clone(),
+            })
+            .unwrap();
+            assert!(matches!(
+                reader.next(),
+                Some(Ok((TagID::DefineBinaryData, _, _)))
+            ));
+            match compression {
+                // Only the start of the movie is decompressed
+                Compression::Zlib => assert!(count.get() < data.len() / 2),
+                // lzma-rs decompresses the whole movie first
+                _ => assert_eq!(count.get(), data.len()),
+            }
+            tags = movie.tags;
+        }
+    }
+
+    #[test]
+    #[cfg(all(any(feature = "flate2", feature = "miniz_oxide"), feature = "lzma-rs"))]
+    fn find_in_compressed_movies() {
+        for compression in [Compression::Zlib, Compression::Lzma] {
+            let data = movie(compression);
+            let mut reader = TagReader::new(data.as_slice()).unwrap();
+            assert_eq!(reader.header.compression, compression);
+            let tag = reader.find(TagID::SymbolClass).unwrap().unwrap();
+            assert!(matches!(tag, Tag::SymbolClass(t) if t.symbols[0].name == "Main"));
+            assert!(reader.find(TagID::SymbolClass).unwrap().is_none());
+        }
+    }
+}
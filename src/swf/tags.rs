@@ -1,40 +1,150 @@
+mod csm_text_settings_tag;
+mod debug_id_tag;
 mod define_binary_data_tag;
+mod define_button2_tag;
+mod define_button_cxform_tag;
+mod define_button_sound_tag;
+mod define_button_tag;
+mod define_edit_text_tag;
+mod define_font2_tag;
+mod define_font4_tag;
+mod define_font_align_zones_tag;
+mod define_font_info_tag;
+mod define_font_name_tag;
+mod define_morph_shape_tag;
+mod define_scaling_grid_tag;
+mod define_scene_and_frame_label_data_tag;
+mod define_sound_tag;
+mod define_text_tag;
+mod define_video_stream_tag;
 mod do_abc_tag;
+mod do_action_tag;
+mod do_init_action_tag;
+mod enable_debugger_tag;
+mod enable_telemetry_tag;
 mod end_tag;
+mod export_assets_tag;
 mod file_attributes_tag;
+mod frame_label_tag;
+mod import_assets_tag;
 mod metadata_tag;
 mod product_info_tag;
+mod protect_tag;
 mod script_limits_tag;
 mod set_background_color_tag;
+mod set_tab_index_tag;
+mod show_frame_tag;
+mod sound_stream_block_tag;
+mod sound_stream_head_tag;
+mod start_sound2_tag;
+mod start_sound_tag;
 mod symbol_class_tag;
 mod unknown_tag;
+mod video_frame_tag;
 
 use std::fmt::Display;
 
+pub use csm_text_settings_tag::{CSMTextSettingsTag, GridFit};
+pub use debug_id_tag::DebugIdTag;
 pub use define_binary_data_tag::DefineBinaryDataTag;
+pub use define_button2_tag::{ButtonCondAction, ButtonCondition, DefineButton2Tag};
+pub use define_button_cxform_tag::DefineButtonCxformTag;
+pub use define_button_sound_tag::{ButtonSound, DefineButtonSoundTag};
+pub use define_button_tag::{ButtonRecord, ButtonState, DefineButtonTag};
+pub use define_edit_text_tag::{DefineEditTextTag, EditTextFlag, TextAlign, TextLayout};
+pub use define_font2_tag::{DefineFont2Tag, FontFlag, FontLayout, Glyph, KerningRecord};
+pub use define_font4_tag::{DefineFont4Tag, Font4Flag};
+pub use define_font_align_zones_tag::{
+    CsmTableHint, DefineFontAlignZonesTag, ZoneData, ZoneRecord,
+};
+pub use define_font_info_tag::{DefineFontInfoTag, FontInfoFlag};
+pub use define_font_name_tag::DefineFontNameTag;
+pub use define_morph_shape_tag::{DefineMorphShapeTag, MorphFillStyle, MorphLineStyle};
+pub use define_scaling_grid_tag::DefineScalingGridTag;
+pub use define_scene_and_frame_label_data_tag::{
+    DefineSceneAndFrameLabelDataTag, FrameLabel, Scene,
+};
+pub use define_sound_tag::DefineSoundTag;
+pub use define_text_tag::{DefineTextTag, GlyphEntry, TextRecord};
+pub use define_video_stream_tag::{DefineVideoStreamTag, VideoCodec, VideoDeblocking};
 pub use do_abc_tag::DoABCTag;
+pub use do_action_tag::DoActionTag;
+pub use do_init_action_tag::DoInitActionTag;
+pub use enable_debugger_tag::EnableDebuggerTag;
+pub use enable_telemetry_tag::EnableTelemetryTag;
 pub use end_tag::EndTag;
+pub use export_assets_tag::{Asset, ExportAssetsTag};
 pub use file_attributes_tag::{FileAttributes, FileAttributesTag};
+pub use frame_label_tag::FrameLabelTag;
+pub use import_assets_tag::ImportAssetsTag;
 pub use metadata_tag::MetadataTag;
 pub use product_info_tag::ProductInfoTag;
+pub use protect_tag::ProtectTag;
 pub use script_limits_tag::ScriptLimitsTag;
 pub use set_background_color_tag::SetBackgroundColorTag;
+pub use set_tab_index_tag::SetTabIndexTag;
+pub use show_frame_tag::ShowFrameTag;
+pub use sound_stream_block_tag::{SoundStream, SoundStreamBlockTag};
+pub use sound_stream_head_tag::SoundStreamHeadTag;
+pub use start_sound2_tag::StartSound2Tag;
+pub use start_sound_tag::StartSoundTag;
 pub use symbol_class_tag::SymbolClassTag;
 pub use unknown_tag::UnknownTag;
+pub use video_frame_tag::VideoFrameTag;
 
 use crate::{error::Result, Movie, StreamReader, StreamWriter};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum TagID {
     End = 0x00,
+    ShowFrame = 0x01,
+    DefineButton = 0x07,
     SetBackgroundColor = 0x09,
+    DefineText = 0x0B,
+    DoAction = 0x0C,
+    DefineFontInfo = 0x0D,
+    DefineSound = 0x0E,
+    StartSound = 0x0F,
+    DefineButtonSound = 0x11,
+    SoundStreamHead = 0x12,
+    SoundStreamBlock = 0x13,
+    DefineButtonCxform = 0x17,
+    Protect = 0x18,
+    DefineText2 = 0x21,
+    DefineButton2 = 0x22,
+    DefineEditText = 0x25,
     ProductInfo = 0x29,
+    FrameLabel = 0x2B,
+    SoundStreamHead2 = 0x2D,
+    DefineMorphShape = 0x2E,
+    DefineFont2 = 0x30,
+    ExportAssets = 0x38,
+    ImportAssets = 0x39,
+    EnableDebugger = 0x3A,
+    DoInitAction = 0x3B,
+    DefineVideoStream = 0x3C,
+    VideoFrame = 0x3D,
+    DefineFontInfo2 = 0x3E,
+    DebugID = 0x3F,
+    EnableDebugger2 = 0x40,
     ScriptLimits = 0x41,
+    SetTabIndex = 0x42,
     FileAttributes = 0x45,
+    ImportAssets2 = 0x47,
+    DefineFontAlignZones = 0x49,
+    CSMTextSettings = 0x4A,
+    DefineFont3 = 0x4B,
     SymbolClass = 0x4C,
     Metadata = 0x4D,
+    DefineScalingGrid = 0x4E,
     DoABC = 0x52,
+    DefineMorphShape2 = 0x54,
+    DefineSceneAndFrameLabelData = 0x56,
     DefineBinaryData = 0x57,
+    DefineFontName = 0x58,
+    StartSound2 = 0x59,
+    DefineFont4 = 0x5B,
+    EnableTelemetry = 0x5D,
     Unknown = 0x3ff,
 }
 
@@ -42,14 +152,54 @@ impl TagID {
     pub fn from_u16(id: u16) -> Self {
         match id {
             0x00 => Self::End,
+            0x01 => Self::ShowFrame,
+            0x07 => Self::DefineButton,
             0x09 => Self::SetBackgroundColor,
+            0x0B => Self::DefineText,
+            0x0C => Self::DoAction,
+            0x0D => Self::DefineFontInfo,
+            0x0E => Self::DefineSound,
+            0x0F => Self::StartSound,
+            0x11 => Self::DefineButtonSound,
+            0x12 => Self::SoundStreamHead,
+            0x13 => Self::SoundStreamBlock,
+            0x17 => Self::DefineButtonCxform,
+            0x18 => Self::Protect,
+            0x21 => Self::DefineText2,
+            0x22 => Self::DefineButton2,
+            0x25 => Self::DefineEditText,
             0x29 => Self::ProductInfo,
+            0x2B => Self::FrameLabel,
+            0x2D => Self::SoundStreamHead2,
+            0x2E => Self::DefineMorphShape,
+            0x30 => Self::DefineFont2,
+            0x38 => Self::ExportAssets,
+            0x39 => Self::ImportAssets,
+            0x3A => Self::EnableDebugger,
+            0x3B => Self::DoInitAction,
+            0x3C => Self::DefineVideoStream,
+            0x3D => Self::VideoFrame,
+            0x3E => Self::DefineFontInfo2,
+            0x3F => Self::DebugID,
+            0x40 => Self::EnableDebugger2,
             0x41 => Self::ScriptLimits,
+            0x42 => Self::SetTabIndex,
             0x45 => Self::FileAttributes,
+            0x47 => Self::ImportAssets2,
+            0x49 => Self::DefineFontAlignZones,
+            0x4A => Self::CSMTextSettings,
+            0x4B => Self::DefineFont3,
             0x4C => Self::SymbolClass,
             0x4D => Self::Metadata,
+            0x4E => Self::DefineScalingGrid,
             0x52 => Self::DoABC,
+            0x54 => Self::DefineMorphShape2,
+            0x56 => Self::DefineSceneAndFrameLabelData,
             0x57 => Self::DefineBinaryData,
+            0x58 => Self::DefineFontName,
+            0x59 => Self::StartSound2,
+            0x5B => Self::DefineFont4,
+            0x5D => Self::EnableTelemetry,
             _ => Self::Unknown,
         }
     }
@@ -68,14 +218,54 @@ impl Display for TagID {
             "{}",
             match self {
                 Self::End => "EndTag",
+                Self::ShowFrame => "ShowFrameTag",
+                Self::DefineButton => "DefineButtonTag",
                 Self::SetBackgroundColor => "SetBackgroundColorTag",
+                Self::DefineText => "DefineTextTag",
+                Self::DoAction => "DoActionTag",
+                Self::DefineFontInfo => "DefineFontInfoTag",
+                Self::DefineSound => "DefineSoundTag",
+                Self::StartSound => "StartSoundTag",
+                Self::DefineButtonSound => "DefineButtonSoundTag",
+                Self::SoundStreamHead => "SoundStreamHeadTag",
+                Self::SoundStreamBlock => "SoundStreamBlockTag",
+                Self::DefineButtonCxform => "DefineButtonCxformTag",
+                Self::Protect => "ProtectTag",
+                Self::DefineText2 => "DefineText2Tag",
+                Self::DefineButton2 => "DefineButton2Tag",
+                Self::DefineEditText => "DefineEditTextTag",
                 Self::ProductInfo => "ProductInfoTag",
+                Self::FrameLabel => "FrameLabelTag",
+                Self::SoundStreamHead2 => "SoundStreamHead2Tag",
+                Self::DefineMorphShape => "DefineMorphShapeTag",
+                Self::DefineFont2 => "DefineFont2Tag",
+                Self::ExportAssets => "ExportAssetsTag",
+                Self::ImportAssets => "ImportAssetsTag",
+                Self::EnableDebugger => "EnableDebuggerTag",
+                Self::DoInitAction => "DoInitActionTag",
+                Self::DefineVideoStream => "DefineVideoStreamTag",
+                Self::VideoFrame => "VideoFrameTag",
+                Self::DefineFontInfo2 => "DefineFontInfo2Tag",
+                Self::DebugID => "DebugIDTag",
+                Self::EnableDebugger2 => "EnableDebugger2Tag",
                 Self::ScriptLimits => "ScriptLimitsTag",
+                Self::SetTabIndex => "SetTabIndexTag",
                 Self::FileAttributes => "FileAttributesTag",
+                Self::ImportAssets2 => "ImportAssets2Tag",
+                Self::DefineFontAlignZones => "DefineFontAlignZonesTag",
+                Self::CSMTextSettings => "CSMTextSettingsTag",
+                Self::DefineFont3 => "DefineFont3Tag",
                 Self::SymbolClass => "SymbolClassTag",
                 Self::Metadata => "MetadataTag",
+                Self::DefineScalingGrid => "DefineScalingGridTag",
                 Self::DoABC => "DoABCTag",
+                Self::DefineMorphShape2 => "DefineMorphShape2Tag",
+                Self::DefineSceneAndFrameLabelData => "DefineSceneAndFrameLabelDataTag",
                 Self::DefineBinaryData => "DefineBinaryDataTag",
+                Self::DefineFontName => "DefineFontNameTag",
+                Self::StartSound2 => "StartSound2Tag",
+                Self::DefineFont4 => "DefineFont4Tag",
+                Self::EnableTelemetry => "EnableTelemetryTag",
                 Self::Unknown => "UnknownTag",
             }
         )
@@ -92,14 +282,54 @@ pub trait ITag {
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum Tag {
+    CSMTextSettings(CSMTextSettingsTag),
+    DebugID(DebugIdTag),
     DefineBinaryData(DefineBinaryDataTag),
+    DefineButton(DefineButtonTag),
+    DefineButton2(DefineButton2Tag),
+    DefineButtonCxform(DefineButtonCxformTag),
+    DefineButtonSound(DefineButtonSoundTag),
+    DefineEditText(Box<DefineEditTextTag>),
+    DefineFont2(Box<DefineFont2Tag>),
+    DefineFont3(Box<DefineFont2Tag>),
+    DefineFont4(DefineFont4Tag),
+    DefineFontAlignZones(DefineFontAlignZonesTag),
+    DefineFontInfo(DefineFontInfoTag),
+    DefineFontInfo2(DefineFontInfoTag),
+    DefineFontName(DefineFontNameTag),
+    DefineMorphShape(Box<DefineMorphShapeTag>),
+    DefineMorphShape2(Box<DefineMorphShapeTag>),
+    DefineScalingGrid(DefineScalingGridTag),
+    DefineSceneAndFrameLabelData(DefineSceneAndFrameLabelDataTag),
+    DefineSound(DefineSoundTag),
+    DefineText(DefineTextTag),
+    DefineText2(DefineTextTag),
+    DefineVideoStream(DefineVideoStreamTag),
+    DoAction(DoActionTag),
+    DoInitAction(DoInitActionTag),
+    EnableDebugger(EnableDebuggerTag),
+    EnableDebugger2(EnableDebuggerTag),
+    EnableTelemetry(EnableTelemetryTag),
     End(EndTag),
+    ExportAssets(ExportAssetsTag),
     FileAttributes(FileAttributesTag),
+    FrameLabel(FrameLabelTag),
+    ImportAssets(ImportAssetsTag),
+    ImportAssets2(ImportAssetsTag),
     Metadata(MetadataTag),
     ProductInfo(ProductInfoTag),
+    Protect(ProtectTag),
     ScriptLimits(ScriptLimitsTag),
     SetBackgroundColor(SetBackgroundColorTag),
+    SetTabIndex(SetTabIndexTag),
+    ShowFrame(ShowFrameTag),
+    SoundStreamBlock(SoundStreamBlockTag),
+    SoundStreamHead(SoundStreamHeadTag),
+    SoundStreamHead2(SoundStreamHeadTag),
+    StartSound(StartSoundTag),
+    StartSound2(StartSound2Tag),
     SymbolClass(SymbolClassTag),
+    VideoFrame(VideoFrameTag),
     DoABC(Box<DoABCTag>),
     Unknown(UnknownTag),
 }
@@ -109,34 +339,148 @@ impl Tag {
         self.into()
     }
 
+    /// Decode the data of a tag. Data that its parser rejects or doesn't fully read is kept
+    /// as an [`UnknownTag`].
+    pub fn read_data(tag_id: u16, data: &[u8]) -> Self {
+        let tag_type = TagID::from_u16(tag_id);
+        if !tag_type.is_unknown() {
+            let mut stream = StreamReader::new(data);
+            if let Ok(tag) = Self::read(tag_type, &mut stream) {
+                if stream.finished() {
+                    return tag;
+                }
+            }
+        }
+        Tag::Unknown(UnknownTag {
+            id: tag_id,
+            data: data.to_vec(),
+        })
+    }
+
     pub fn read(tag_type: TagID, stream: &mut StreamReader) -> Result<Self> {
         Ok(match tag_type {
+            TagID::CSMTextSettings => Tag::CSMTextSettings(CSMTextSettingsTag::read(stream)?),
+            TagID::DebugID => Tag::DebugID(DebugIdTag::read(stream)?),
             TagID::DefineBinaryData => Tag::DefineBinaryData(DefineBinaryDataTag::read(stream)?),
+            TagID::DefineButton => Tag::DefineButton(DefineButtonTag::read(stream)?),
+            TagID::DefineButton2 => Tag::DefineButton2(DefineButton2Tag::read(stream)?),
+            TagID::DefineButtonCxform => {
+                Tag::DefineButtonCxform(DefineButtonCxformTag::read(stream)?)
+            }
+            TagID::DefineButtonSound => Tag::DefineButtonSound(DefineButtonSoundTag::read(stream)?),
+            TagID::DefineEditText => {
+                Tag::DefineEditText(Box::new(DefineEditTextTag::read(stream)?))
+            }
+            TagID::DefineFont2 => Tag::DefineFont2(Box::new(DefineFont2Tag::read(stream)?)),
+            TagID::DefineFont3 => Tag::DefineFont3(Box::new(DefineFont2Tag::read(stream)?)),
+            TagID::DefineFont4 => Tag::DefineFont4(DefineFont4Tag::read(stream)?),
+            TagID::DefineFontAlignZones => {
+                Tag::DefineFontAlignZones(DefineFontAlignZonesTag::read(stream)?)
+            }
+            TagID::DefineFontInfo => Tag::DefineFontInfo(DefineFontInfoTag::read(stream)?),
+            TagID::DefineFontInfo2 => {
+                Tag::DefineFontInfo2(DefineFontInfoTag::read_with_version(stream, 2)?)
+            }
+            TagID::DefineFontName => Tag::DefineFontName(DefineFontNameTag::read(stream)?),
+            TagID::DefineMorphShape => {
+                Tag::DefineMorphShape(Box::new(DefineMorphShapeTag::read(stream)?))
+            }
+            TagID::DefineMorphShape2 => {
+                Tag::DefineMorphShape2(Box::new(DefineMorphShapeTag::read_with_version(stream, 2)?))
+            }
+            TagID::DefineScalingGrid => Tag::DefineScalingGrid(DefineScalingGridTag::read(stream)?),
+            TagID::DefineSceneAndFrameLabelData => {
+                Tag::DefineSceneAndFrameLabelData(DefineSceneAndFrameLabelDataTag::read(stream)?)
+            }
+            TagID::DefineSound => Tag::DefineSound(DefineSoundTag::read(stream)?),
+            TagID::DefineText => Tag::DefineText(DefineTextTag::read(stream)?),
+            TagID::DefineText2 => Tag::DefineText2(DefineTextTag::read_with_version(stream, 2)?),
+            TagID::DefineVideoStream => Tag::DefineVideoStream(DefineVideoStreamTag::read(stream)?),
             TagID::DoABC => Tag::DoABC(Box::new(DoABCTag::read(stream)?)),
+            TagID::DoAction => Tag::DoAction(DoActionTag::read(stream)?),
+            TagID::DoInitAction => Tag::DoInitAction(DoInitActionTag::read(stream)?),
+            TagID::EnableDebugger => Tag::EnableDebugger(EnableDebuggerTag::read(stream)?),
+            TagID::EnableDebugger2 => {
+                Tag::EnableDebugger2(EnableDebuggerTag::read_with_version(stream, 2)?)
+            }
+            TagID::EnableTelemetry => Tag::EnableTelemetry(EnableTelemetryTag::read(stream)?),
             TagID::End => Tag::End(EndTag::read(stream)?),
+            TagID::ExportAssets => Tag::ExportAssets(ExportAssetsTag::read(stream)?),
             TagID::FileAttributes => Tag::FileAttributes(FileAttributesTag::read(stream)?),
+            TagID::FrameLabel => Tag::FrameLabel(FrameLabelTag::read(stream)?),
+            TagID::ImportAssets => Tag::ImportAssets(ImportAssetsTag::read(stream)?),
+            TagID::ImportAssets2 => {
+                Tag::ImportAssets2(ImportAssetsTag::read_with_version(stream, 2)?)
+            }
             TagID::Metadata => Tag::Metadata(MetadataTag::read(stream)?),
             TagID::ProductInfo => Tag::ProductInfo(ProductInfoTag::read(stream)?),
+            TagID::Protect => Tag::Protect(ProtectTag::read(stream)?),
             TagID::ScriptLimits => Tag::ScriptLimits(ScriptLimitsTag::read(stream)?),
             TagID::SetBackgroundColor => {
                 Tag::SetBackgroundColor(SetBackgroundColorTag::read(stream)?)
             }
+            TagID::SetTabIndex => Tag::SetTabIndex(SetTabIndexTag::read(stream)?),
+            TagID::ShowFrame => Tag::ShowFrame(ShowFrameTag::read(stream)?),
+            TagID::SoundStreamBlock => Tag::SoundStreamBlock(SoundStreamBlockTag::read(stream)?),
+            TagID::SoundStreamHead => Tag::SoundStreamHead(SoundStreamHeadTag::read(stream)?),
+            TagID::SoundStreamHead2 => Tag::SoundStreamHead2(SoundStreamHeadTag::read(stream)?),
+            TagID::StartSound => Tag::StartSound(StartSoundTag::read(stream)?),
+            TagID::StartSound2 => Tag::StartSound2(StartSound2Tag::read(stream)?),
             TagID::SymbolClass => Tag::SymbolClass(SymbolClassTag::read(stream)?),
+            TagID::VideoFrame => Tag::VideoFrame(VideoFrameTag::read(stream)?),
             _ => unreachable!(),
         })
     }
 
     pub fn write(&self, stream: &mut StreamWriter, movie: &Movie) -> Result<()> {
         match self {
+            Tag::CSMTextSettings(t) => t.write(stream, movie)?,
+            Tag::DebugID(t) => t.write(stream, movie)?,
             Tag::DefineBinaryData(t) => t.write(stream, movie)?,
+            Tag::DefineButton(t) => t.write(stream, movie)?,
+            Tag::DefineButton2(t) => t.write(stream, movie)?,
+            Tag::DefineButtonCxform(t) => t.write(stream, movie)?,
+            Tag::DefineButtonSound(t) => t.write(stream, movie)?,
+            Tag::DefineEditText(t) => t.write(stream, movie)?,
+            Tag::DefineFont2(t) | Tag::DefineFont3(t) => t.write(stream, movie)?,
+            Tag::DefineFont4(t) => t.write(stream, movie)?,
+            Tag::DefineFontAlignZones(t) => t.write(stream, movie)?,
+            Tag::DefineFontInfo(t) => t.write(stream, movie)?,
+            Tag::DefineFontInfo2(t) => t.write_with_version(stream, 2)?,
+            Tag::DefineFontName(t) => t.write(stream, movie)?,
+            Tag::DefineMorphShape(t) => t.write(stream, movie)?,
+            Tag::DefineMorphShape2(t) => t.write_with_version(stream, 2)?,
+            Tag::DefineScalingGrid(t) => t.write(stream, movie)?,
+            Tag::DefineSceneAndFrameLabelData(t) => t.write(stream, movie)?,
+            Tag::DefineSound(t) => t.write(stream, movie)?,
+            Tag::DefineText(t) => t.write(stream, movie)?,
+            Tag::DefineText2(t) => t.write_with_version(stream, 2)?,
+            Tag::DefineVideoStream(t) => t.write(stream, movie)?,
             Tag::DoABC(t) => t.write(stream, movie)?,
+            Tag::DoAction(t) => t.write(stream, movie)?,
+            Tag::DoInitAction(t) => t.write(stream, movie)?,
+            Tag::EnableDebugger(t) => t.write(stream, movie)?,
+            Tag::EnableDebugger2(t) => t.write_with_version(stream, 2)?,
+            Tag::EnableTelemetry(t) => t.write(stream, movie)?,
             Tag::End(t) => t.write(stream, movie)?,
+            Tag::ExportAssets(t) => t.write(stream, movie)?,
             Tag::FileAttributes(t) => t.write(stream, movie)?,
+            Tag::FrameLabel(t) => t.write(stream, movie)?,
+            Tag::ImportAssets(t) => t.write(stream, movie)?,
+            Tag::ImportAssets2(t) => t.write_with_version(stream, 2)?,
             Tag::Metadata(t) => t.write(stream, movie)?,
             Tag::ProductInfo(t) => t.write(stream, movie)?,
+            Tag::Protect(t) => t.write(stream, movie)?,
             Tag::ScriptLimits(t) => t.write(stream, movie)?,
             Tag::SetBackgroundColor(t) => t.write(stream, movie)?,
+            Tag::SetTabIndex(t) => t.write(stream, movie)?,
+            Tag::ShowFrame(t) => t.write(stream, movie)?,
+            Tag::SoundStreamBlock(t) => t.write(stream, movie)?,
+            Tag::SoundStreamHead(t) | Tag::SoundStreamHead2(t) => t.write(stream, movie)?,
+            Tag::StartSound(t) => t.write(stream, movie)?,
+            Tag::StartSound2(t) => t.write(stream, movie)?,
             Tag::SymbolClass(t) => t.write(stream, movie)?,
+            Tag::VideoFrame(t) => t.write(stream, movie)?,
             Tag::Unknown(t) => t.write(stream, movie)?,
         };
         Ok(())
@@ -148,13 +492,53 @@ impl From<&Tag> for TagID {
         match tag {
             Tag::End(_) => Self::End,
             Tag::SetBackgroundColor(_) => Self::SetBackgroundColor,
+            Tag::DefineText(_) => Self::DefineText,
+            Tag::DefineSound(_) => Self::DefineSound,
+            Tag::StartSound(_) => Self::StartSound,
+            Tag::SoundStreamHead(_) => Self::SoundStreamHead,
+            Tag::SoundStreamBlock(_) => Self::SoundStreamBlock,
+            Tag::DefineText2(_) => Self::DefineText2,
+            Tag::DefineEditText(_) => Self::DefineEditText,
             Tag::ProductInfo(_) => Self::ProductInfo,
+            Tag::SoundStreamHead2(_) => Self::SoundStreamHead2,
             Tag::ScriptLimits(_) => Self::ScriptLimits,
             Tag::FileAttributes(_) => Self::FileAttributes,
             Tag::SymbolClass(_) => Self::SymbolClass,
             Tag::Metadata(_) => Self::Metadata,
             Tag::DoABC(_) => Self::DoABC,
             Tag::DefineBinaryData(_) => Self::DefineBinaryData,
+            Tag::DefineFont2(_) => Self::DefineFont2,
+            Tag::DefineFont3(_) => Self::DefineFont3,
+            Tag::DefineFont4(_) => Self::DefineFont4,
+            Tag::DefineFontAlignZones(_) => Self::DefineFontAlignZones,
+            Tag::DefineFontName(_) => Self::DefineFontName,
+            Tag::StartSound2(_) => Self::StartSound2,
+            Tag::CSMTextSettings(_) => Self::CSMTextSettings,
+            Tag::DefineVideoStream(_) => Self::DefineVideoStream,
+            Tag::VideoFrame(_) => Self::VideoFrame,
+            Tag::DefineButton(_) => Self::DefineButton,
+            Tag::DefineButtonSound(_) => Self::DefineButtonSound,
+            Tag::DefineButtonCxform(_) => Self::DefineButtonCxform,
+            Tag::DefineButton2(_) => Self::DefineButton2,
+            Tag::DefineMorphShape(_) => Self::DefineMorphShape,
+            Tag::DefineMorphShape2(_) => Self::DefineMorphShape2,
+            Tag::DoAction(_) => Self::DoAction,
+            Tag::DoInitAction(_) => Self::DoInitAction,
+            Tag::ExportAssets(_) => Self::ExportAssets,
+            Tag::ImportAssets(_) => Self::ImportAssets,
+            Tag::ImportAssets2(_) => Self::ImportAssets2,
+            Tag::ShowFrame(_) => Self::ShowFrame,
+            Tag::FrameLabel(_) => Self::FrameLabel,
+            Tag::DefineSceneAndFrameLabelData(_) => Self::DefineSceneAndFrameLabelData,
+            Tag::Protect(_) => Self::Protect,
+            Tag::EnableDebugger(_) => Self::EnableDebugger,
+            Tag::DebugID(_) => Self::DebugID,
+            Tag::EnableDebugger2(_) => Self::EnableDebugger2,
+            Tag::EnableTelemetry(_) => Self::EnableTelemetry,
+            Tag::DefineFontInfo(_) => Self::DefineFontInfo,
+            Tag::DefineFontInfo2(_) => Self::DefineFontInfo2,
+            Tag::SetTabIndex(_) => Self::SetTabIndex,
+            Tag::DefineScalingGrid(_) => Self::DefineScalingGrid,
             _ => Self::Unknown,
         }
     }
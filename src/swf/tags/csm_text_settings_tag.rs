@@ -0,0 +1,61 @@
+use super::ITag;
+use crate::{error::Result, Movie, StreamReader, StreamWriter};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum GridFit {
+    /// Do not use grid fitting
+    #[default]
+    None = 0,
+    /// Pixel grid fit, only for left-aligned dynamic text fields
+    Pixel = 1,
+    /// Sub-pixel grid fit, for right and center aligned dynamic text fields
+    SubPixel = 2,
+}
+
+/// Advanced anti-aliasing settings of a DefineText or DefineEditText tag
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct CSMTextSettingsTag {
+    pub text_id: u16,
+    /// Use the advanced anti-aliasing engine (FlashType)
+    pub use_flash_type: bool,
+    pub grid_fit: GridFit,
+    pub thickness: f32,
+    pub sharpness: f32,
+}
+
+impl GridFit {
+    pub fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::Pixel,
+            2 => Self::SubPixel,
+            _ => Self::None,
+        }
+    }
+}
+
+impl ITag for CSMTextSettingsTag {
+    fn read(stream: &mut StreamReader) -> Result<Self> {
+        let text_id = stream.read_u16()?;
+        let flags = stream.read_u8()?;
+        let thickness = stream.read_float()?;
+        let sharpness = stream.read_float()?;
+        let _reserved = stream.read_u8()?;
+
+        Ok(Self {
+            text_id,
+            use_flash_type: flags >> 6 == 1,
+            grid_fit: GridFit::from_u8((flags >> 3) & 0x07),
+            thickness,
+            sharpness,
+        })
+    }
+
+    fn write(&self, stream: &mut StreamWriter, _movie: &Movie) -> Result<()> {
+        stream.write_u16(self.text_id)?;
+        stream.write_u8((u8::from(self.use_flash_type) << 6) | ((self.grid_fit as u8) << 3))?;
+        stream.write_float(self.thickness)?;
+        stream.write_float(self.sharpness)?;
+        stream.write_u8(0)?;
+        Ok(())
+    }
+}
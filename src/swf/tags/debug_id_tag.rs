@@ -0,0 +1,20 @@
+use super::ITag;
+use crate::{error::Result, Movie, StreamReader, StreamWriter};
+
+/// Identifier matching the movie with its SWD debug file
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct DebugIdTag {
+    pub uuid: [u8; 16],
+}
+
+impl ITag for DebugIdTag {
+    fn read(stream: &mut StreamReader) -> Result<Self> {
+        let mut uuid = [0u8; 16];
+        stream.read_exact(&mut uuid)?;
+        Ok(Self { uuid })
+    }
+
+    fn write(&self, stream: &mut StreamWriter, _movie: &Movie) -> Result<()> {
+        stream.write_exact(&self.uuid)
+    }
+}
@@ -0,0 +1,220 @@
+use super::{ButtonRecord, ITag};
+use crate::{
+    avm1::{self, Action},
+    error::{RabcError, Result},
+    Movie, StreamReader, StreamWriter,
+};
+use bitflags::bitflags;
+
+bitflags! {
+    /// Button state transitions triggering a condition action.
+    /// The bits 1 to 7 are used by the key code of the action.
+    #[derive(Clone, Copy, Debug, PartialEq, Default)]
+    pub struct ButtonCondition : u16 {
+        const OVER_DOWN_TO_IDLE = 0x0001;
+        const IDLE_TO_OVER_UP = 0x0100;
+        const OVER_UP_TO_IDLE = 0x0200;
+        const OVER_UP_TO_OVER_DOWN = 0x0400;
+        const OVER_DOWN_TO_OVER_UP = 0x0800;
+        const OVER_DOWN_TO_OUT_DOWN = 0x1000;
+        const OUT_DOWN_TO_OVER_DOWN = 0x2000;
+        const OUT_DOWN_TO_IDLE = 0x4000;
+        const IDLE_TO_OVER_DOWN = 0x8000;
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct ButtonCondAction {
+    pub conditions: ButtonCondition,
+    /// Key code triggering the action, 0 if none
+    pub key_press: u8,
+    /// AVM1 bytecode, ending with an ActionEnd
+    pub actions: Vec<u8>,
+}
+
+/// Button with actions triggered by state transitions or key presses
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct DefineButton2Tag {
+    pub button_id: u16,
+    pub track_as_menu: bool,
+    pub records: Vec<ButtonRecord>,
+    pub actions: Vec<ButtonCondAction>,
+}
+
+impl ButtonCondAction {
+    pub fn parse_actions(&self) -> Result<Vec<Action>> {
+        avm1::parse_actions(&self.actions)
+    }
+
+    pub fn save_actions(&mut self, actions: &[Action]) -> Result<()> {
+        self.actions = avm1::write_actions(actions)?;
+        Ok(())
+    }
+
+    /// Save actions that may have been inserted or removed since they were parsed,
+    /// see [`avm1::write_relocated_actions`]
+    pub fn save_relocated_actions(&mut self, actions: &[Action]) -> Result<()> {
+        self.actions = avm1::write_relocated_actions(actions)?;
+        Ok(())
+    }
+
+    fn read_flags(stream: &mut StreamReader) -> Result<(ButtonCondition, u8)> {
+        // The first byte holds the most significant flags
+        let flags = stream.read_u16()?.swap_bytes();
+        Ok((
+            ButtonCondition::from_bits_truncate(flags),
+            ((flags >> 1) & 0x7F) as u8,
+        ))
+    }
+
+    fn write_flags(&self, stream: &mut StreamWriter) -> Result<()> {
+        let flags = self.conditions.bits() | (u16::from(self.key_press & 0x7F) << 1);
+        stream.write_u16(flags.swap_bytes())
+    }
+}
+
+impl ITag for DefineButton2Tag {
+    fn read(stream: &mut StreamReader) -> Result<Self> {
+        let button_id = stream.read_u16()?;
+        let track_as_menu = stream.read_u8()? & 0x01 != 0;
+        let action_offset = stream.read_u16()?;
+        let records = ButtonRecord::read_list(stream, 2)?;
+
+        let mut actions = Vec::new();
+        if action_offset != 0 {
+            loop {
+                let size = stream.read_u16()?;
+                let (conditions, key_press) = ButtonCondAction::read_flags(stream)?;
+                // The last action has a size of 0 and runs until the end of the tag
+                let length = if size == 0 {
+                    stream.remaining()
+                } else {
+                    u32::from(size.saturating_sub(4))
+                };
+                let mut bytecode = vec![0u8; length as usize];
+                stream.read_exact(&mut bytecode)?;
+                actions.push(ButtonCondAction {
+                    conditions,
+                    key_press,
+                    actions: bytecode,
+                });
+                if size == 0 {
+                    break;
+                }
+            }
+        }
+
+        Ok(Self {
+            button_id,
+            track_as_menu,
+            records,
+            actions,
+        })
+    }
+
+    fn write(&self, stream: &mut StreamWriter, _movie: &Movie) -> Result<()> {
+        let mut records = StreamWriter::default();
+        ButtonRecord::write_list(&self.records, &mut records, 2)?;
+
+        stream.write_u16(self.button_id)?;
+        stream.write_u8(self.track_as_menu.into())?;
+        // Offset from the start of this field to the first condition action
+        let action_offset = if self.actions.is_empty() {
+            0
+        } else {
+            records.len() + 2
+        };
+        let action_offset = u16::try_from(action_offset)
+            .map_err(|_| RabcError::ValueOutOfRange("button action offset"))?;
+        stream.write_u16(action_offset)?;
+        stream.write_stream(&records)?;
+
+        for (i, action) in self.actions.iter().enumerate() {
+            let size = if i + 1 == self.actions.len() {
+                0
+            } else {
+                action.actions.len() + 4
+            };
+            let size = u16::try_from(size)
+                .map_err(|_| RabcError::ValueOutOfRange("button action size"))?;
+            stream.write_u16(size)?;
+            action.write_flags(stream)?;
+            stream.write_exact(&action.actions)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        avm1::Op,
+        swf::{
+            datatypes::{BlendMode, BlurFilter, ColorTransform, Filter, Fixed16, Fixed8, Matrix},
+            tags::ButtonState,
+        },
+    };
+
+    #[test]
+    fn round_trip() {
+        let button = DefineButton2Tag {
+            button_id: 10,
+            track_as_menu: true,
+            records: vec![
+                ButtonRecord {
+                    states: ButtonState::UP | ButtonState::OVER,
+                    char_id: 3,
+                    depth: 1,
+                    matrix: Matrix {
+                        translate_x: 200,
+                        ..Default::default()
+                    },
+                    color_transform: ColorTransform {
+                        alpha_mult: Fixed8(128),
+                        red_add: -20,
+                        ..Default::default()
+                    },
+                    filters: Some(vec![Filter::Blur(BlurFilter {
+                        blur_x: Fixed16::ONE,
+                        blur_y: Fixed16::ONE,
+                        passes: 1,
+                    })]),
+                    blend_mode: Some(BlendMode::Screen),
+                },
+                ButtonRecord {
+                    states: ButtonState::HIT_TEST,
+                    char_id: 4,
+                    depth: 2,
+                    ..Default::default()
+                },
+            ],
+            actions: vec![
+                ButtonCondAction {
+                    conditions: ButtonCondition::OVER_DOWN_TO_OVER_UP,
+                    key_press: 0,
+                    actions: vec![0x07, 0x00],
+                },
+                ButtonCondAction {
+                    conditions: ButtonCondition::OVER_DOWN_TO_IDLE,
+                    key_press: 13,
+                    actions: vec![0x06, 0x00],
+                },
+            ],
+        };
+
+        let mut stream = StreamWriter::default();
+        button.write(&mut stream, &Movie::default()).unwrap();
+        let mut reader = StreamReader::new(stream.buffer());
+        let mut read = DefineButton2Tag::read(&mut reader).unwrap();
+        assert_eq!(read, button);
+        assert!(reader.finished());
+
+        let action = &mut read.actions[0];
+        let mut actions = action.parse_actions().unwrap();
+        assert_eq!(actions[0].op, Op::Stop());
+        actions.insert(0, actions[0].clone());
+        action.save_relocated_actions(&actions).unwrap();
+        assert_eq!(action.actions, [0x07, 0x07, 0x00]);
+    }
+}
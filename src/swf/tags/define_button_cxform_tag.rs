@@ -0,0 +1,46 @@
+use super::ITag;
+use crate::{error::Result, swf::datatypes::ColorTransform, Movie, StreamReader, StreamWriter};
+
+/// Color transform applied to the characters of a DefineButton tag
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct DefineButtonCxformTag {
+    pub button_id: u16,
+    pub color_transform: ColorTransform,
+}
+
+impl ITag for DefineButtonCxformTag {
+    fn read(stream: &mut StreamReader) -> Result<Self> {
+        Ok(Self {
+            button_id: stream.read_u16()?,
+            color_transform: ColorTransform::read(stream)?,
+        })
+    }
+
+    fn write(&self, stream: &mut StreamWriter, _movie: &Movie) -> Result<()> {
+        stream.write_u16(self.button_id)?;
+        self.color_transform.write(stream)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::swf::datatypes::Fixed8;
+
+    #[test]
+    fn round_trip() {
+        let tag = DefineButtonCxformTag {
+            button_id: 4,
+            color_transform: ColorTransform {
+                red_mult: Fixed8(128),
+                blue_add: 30,
+                ..ColorTransform::IDENTITY
+            },
+        };
+        let mut stream = StreamWriter::default();
+        tag.write(&mut stream, &Movie::default()).unwrap();
+        let mut reader = StreamReader::new(stream.buffer());
+        assert_eq!(DefineButtonCxformTag::read(&mut reader).unwrap(), tag);
+        assert!(reader.finished());
+    }
+}
@@ -0,0 +1,94 @@
+use super::ITag;
+use crate::{error::Result, swf::datatypes::SoundInfo, Movie, StreamReader, StreamWriter};
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct ButtonSound {
+    pub sound_id: u16,
+    pub info: SoundInfo,
+}
+
+/// Sounds played on the state transitions of a button
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct DefineButtonSoundTag {
+    pub button_id: u16,
+    pub over_up_to_idle: Option<ButtonSound>,
+    pub idle_to_over_up: Option<ButtonSound>,
+    pub over_up_to_over_down: Option<ButtonSound>,
+    pub over_down_to_over_up: Option<ButtonSound>,
+}
+
+impl ButtonSound {
+    /// A sound id of 0 means there's no sound for the transition
+    fn read(stream: &mut StreamReader) -> Result<Option<Self>> {
+        let sound_id = stream.read_u16()?;
+        if sound_id == 0 {
+            return Ok(None);
+        }
+        Ok(Some(Self {
+            sound_id,
+            info: SoundInfo::read(stream)?,
+        }))
+    }
+
+    fn write(sound: &Option<Self>, stream: &mut StreamWriter) -> Result<()> {
+        match sound {
+            Some(sound) => {
+                stream.write_u16(sound.sound_id)?;
+                sound.info.write(stream)
+            }
+            None => stream.write_u16(0),
+        }
+    }
+}
+
+impl ITag for DefineButtonSoundTag {
+    fn read(stream: &mut StreamReader) -> Result<Self> {
+        Ok(Self {
+            button_id: stream.read_u16()?,
+            over_up_to_idle: ButtonSound::read(stream)?,
+            idle_to_over_up: ButtonSound::read(stream)?,
+            over_up_to_over_down: ButtonSound::read(stream)?,
+            over_down_to_over_up: ButtonSound::read(stream)?,
+        })
+    }
+
+    fn write(&self, stream: &mut StreamWriter, _movie: &Movie) -> Result<()> {
+        stream.write_u16(self.button_id)?;
+        ButtonSound::write(&self.over_up_to_idle, stream)?;
+        ButtonSound::write(&self.idle_to_over_up, stream)?;
+        ButtonSound::write(&self.over_up_to_over_down, stream)?;
+        ButtonSound::write(&self.over_down_to_over_up, stream)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let tag = DefineButtonSoundTag {
+            button_id: 4,
+            idle_to_over_up: Some(ButtonSound {
+                sound_id: 7,
+                info: SoundInfo {
+                    sync_no_multiple: true,
+                    loop_count: Some(2),
+                    ..Default::default()
+                },
+            }),
+            over_down_to_over_up: Some(ButtonSound {
+                sound_id: 8,
+                info: SoundInfo::default(),
+            }),
+            ..Default::default()
+        };
+        let mut stream = StreamWriter::default();
+        tag.write(&mut stream, &Movie::default()).unwrap();
+        // The transitions without a sound only take their empty id
+        assert_eq!(stream.buffer()[2..4], [0, 0]);
+        let mut reader = StreamReader::new(stream.buffer());
+        assert_eq!(DefineButtonSoundTag::read(&mut reader).unwrap(), tag);
+        assert!(reader.finished());
+    }
+}
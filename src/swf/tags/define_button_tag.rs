@@ -0,0 +1,208 @@
+use super::ITag;
+use crate::{
+    avm1::{self, Action},
+    error::Result,
+    swf::datatypes::{BlendMode, ColorTransform, Filter, Matrix},
+    Movie, StreamReader, StreamWriter,
+};
+use bitflags::bitflags;
+
+const HAS_BLEND_MODE: u8 = 0x20;
+const HAS_FILTER_LIST: u8 = 0x10;
+
+bitflags! {
+    /// States of the button in which a character is displayed
+    #[derive(Clone, Copy, Debug, PartialEq, Default)]
+    pub struct ButtonState : u8 {
+        const UP = 0x01;
+        const OVER = 0x02;
+        const DOWN = 0x04;
+        const HIT_TEST = 0x08;
+    }
+}
+
+/// Character displayed by a button, used by both DefineButton and DefineButton2.
+/// The color transform, filters and blend mode are only written in DefineButton2 tags.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct ButtonRecord {
+    pub states: ButtonState,
+    pub char_id: u16,
+    pub depth: u16,
+    pub matrix: Matrix,
+    pub color_transform: ColorTransform,
+    pub filters: Option<Vec<Filter>>,
+    pub blend_mode: Option<BlendMode>,
+}
+
+/// Button with a single action list, run when the button is clicked
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct DefineButtonTag {
+    pub button_id: u16,
+    pub records: Vec<ButtonRecord>,
+    /// AVM1 bytecode, ending with an ActionEnd
+    pub actions: Vec<u8>,
+}
+
+impl ButtonRecord {
+    /// Read button records until the end flag
+    pub fn read_list(stream: &mut StreamReader, version: u8) -> Result<Vec<Self>> {
+        let mut records = Vec::new();
+        loop {
+            let flags = stream.read_u8()?;
+            if flags == 0 {
+                // CharacterEndFlag
+                break;
+            }
+            records.push(Self::read(stream, flags, version)?);
+        }
+        Ok(records)
+    }
+
+    fn read(stream: &mut StreamReader, flags: u8, version: u8) -> Result<Self> {
+        let char_id = stream.read_u16()?;
+        let depth = stream.read_u16()?;
+        let matrix = Matrix::read(stream)?;
+        if version < 2 {
+            return Ok(Self {
+                states: ButtonState::from_bits_truncate(flags),
+                char_id,
+                depth,
+                matrix,
+                ..Default::default()
+            });
+        }
+
+        let color_transform = ColorTransform::read_with_alpha(stream)?;
+        let filters = if flags & HAS_FILTER_LIST != 0 {
+            Some(Filter::read_list(stream)?)
+        } else {
+            None
+        };
+        let blend_mode = if flags & HAS_BLEND_MODE != 0 {
+            Some(BlendMode::from_u8(stream.read_u8()?))
+        } else {
+            None
+        };
+
+        Ok(Self {
+            states: ButtonState::from_bits_truncate(flags),
+            char_id,
+            depth,
+            matrix,
+            color_transform,
+            filters,
+            blend_mode,
+        })
+    }
+
+    /// Write the button records followed by the end flag
+    pub fn write_list(records: &[Self], stream: &mut StreamWriter, version: u8) -> Result<()> {
+        for record in records {
+            record.write(stream, version)?;
+        }
+        stream.write_u8(0)
+    }
+
+    fn write(&self, stream: &mut StreamWriter, version: u8) -> Result<()> {
+        let mut flags = self.states.bits();
+        if version >= 2 {
+            if self.filters.is_some() {
+                flags |= HAS_FILTER_LIST;
+            }
+            if self.blend_mode.is_some() {
+                flags |= HAS_BLEND_MODE;
+            }
+        }
+
+        stream.write_u8(flags)?;
+        stream.write_u16(self.char_id)?;
+        stream.write_u16(self.depth)?;
+        self.matrix.write(stream)?;
+        if version >= 2 {
+            self.color_transform.write_with_alpha(stream)?;
+            if let Some(filters) = &self.filters {
+                Filter::write_list(filters, stream)?;
+            }
+            if let Some(blend_mode) = self.blend_mode {
+                stream.write_u8(blend_mode as u8)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl DefineButtonTag {
+    pub fn parse_actions(&self) -> Result<Vec<Action>> {
+        avm1::parse_actions(&self.actions)
+    }
+
+    pub fn save_actions(&mut self, actions: &[Action]) -> Result<()> {
+        self.actions = avm1::write_actions(actions)?;
+        Ok(())
+    }
+
+    /// Save actions that may have been inserted or removed since they were parsed,
+    /// see [`avm1::write_relocated_actions`]
+    pub fn save_relocated_actions(&mut self, actions: &[Action]) -> Result<()> {
+        self.actions = avm1::write_relocated_actions(actions)?;
+        Ok(())
+    }
+}
+
+impl ITag for DefineButtonTag {
+    fn read(stream: &mut StreamReader) -> Result<Self> {
+        let button_id = stream.read_u16()?;
+        let records = ButtonRecord::read_list(stream, 1)?;
+        let mut actions = vec![0u8; stream.remaining() as usize];
+        stream.read_exact(&mut actions)?;
+
+        Ok(Self {
+            button_id,
+            records,
+            actions,
+        })
+    }
+
+    fn write(&self, stream: &mut StreamWriter, _movie: &Movie) -> Result<()> {
+        stream.write_u16(self.button_id)?;
+        ButtonRecord::write_list(&self.records, stream, 1)?;
+        stream.write_exact(&self.actions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::Op;
+
+    #[test]
+    fn round_trip() {
+        let mut button = DefineButtonTag {
+            button_id: 4,
+            records: vec![ButtonRecord {
+                states: ButtonState::UP | ButtonState::HIT_TEST,
+                char_id: 2,
+                depth: 1,
+                matrix: Matrix {
+                    translate_y: -40,
+                    ..Default::default()
+                },
+                // Only written in DefineButton2
+                color_transform: ColorTransform::IDENTITY,
+                ..Default::default()
+            }],
+            actions: vec![0x06, 0x00],
+        };
+        let mut stream = StreamWriter::default();
+        button.write(&mut stream, &Movie::default()).unwrap();
+        let mut reader = StreamReader::new(stream.buffer());
+        assert_eq!(DefineButtonTag::read(&mut reader).unwrap(), button);
+        assert!(reader.finished());
+
+        let mut actions = button.parse_actions().unwrap();
+        assert_eq!(actions[0].op, Op::Play());
+        actions.remove(0);
+        button.save_relocated_actions(&actions).unwrap();
+        assert_eq!(button.actions, [0x00]);
+    }
+}
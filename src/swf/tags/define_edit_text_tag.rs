@@ -0,0 +1,261 @@
+use super::ITag;
+use crate::{
+    error::Result,
+    swf::datatypes::{Rect, Rgba},
+    Movie, StreamReader, StreamWriter,
+};
+use bitflags::bitflags;
+
+bitflags! {
+    #[derive(Clone, Copy, Debug, PartialEq, Default)]
+    pub struct EditTextFlag : u16 {
+        const USE_OUTLINES = 0x0001;
+        const HTML = 0x0002;
+        const WAS_STATIC = 0x0004;
+        const BORDER = 0x0008;
+        const NO_SELECT = 0x0010;
+        const HAS_LAYOUT = 0x0020;
+        const AUTO_SIZE = 0x0040;
+        const HAS_FONT_CLASS = 0x0080;
+        const HAS_FONT = 0x0100;
+        const HAS_MAX_LENGTH = 0x0200;
+        const HAS_TEXT_COLOR = 0x0400;
+        const READ_ONLY = 0x0800;
+        const PASSWORD = 0x1000;
+        const MULTILINE = 0x2000;
+        const WORD_WRAP = 0x4000;
+        const HAS_TEXT = 0x8000;
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum TextAlign {
+    #[default]
+    Left = 0,
+    Right = 1,
+    Center = 2,
+    Justify = 3,
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct TextLayout {
+    pub align: TextAlign,
+    pub left_margin: u16,
+    pub right_margin: u16,
+    pub indent: u16,
+    pub leading: i16,
+}
+
+/// Dynamic or input text field
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct DefineEditTextTag {
+    pub char_id: u16,
+    pub bounds: Rect,
+    /// The HAS_* flags are ignored when writing, they are set according to the fields' presence
+    pub flags: EditTextFlag,
+    pub font_id: Option<u16>,
+    /// Class name of a font, used instead of the font id
+    pub font_class: Option<String>,
+    /// Font height in twips, only written if a font is set
+    pub font_height: u16,
+    pub color: Option<Rgba>,
+    pub max_length: Option<u16>,
+    pub layout: Option<TextLayout>,
+    /// Name of the variable bound to the text field's content
+    pub variable_name: String,
+    /// Initial content of the text field, in HTML if the HTML flag is set
+    pub initial_text: Option<String>,
+}
+
+impl TextAlign {
+    pub fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::Right,
+            2 => Self::Center,
+            3 => Self::Justify,
+            _ => Self::Left,
+        }
+    }
+}
+
+impl DefineEditTextTag {
+    pub fn is_html(&self) -> bool {
+        self.flags.contains(EditTextFlag::HTML)
+    }
+    pub fn is_read_only(&self) -> bool {
+        self.flags.contains(EditTextFlag::READ_ONLY)
+    }
+    pub fn is_password(&self) -> bool {
+        self.flags.contains(EditTextFlag::PASSWORD)
+    }
+    pub fn is_multiline(&self) -> bool {
+        self.flags.contains(EditTextFlag::MULTILINE)
+    }
+    pub fn use_outlines(&self) -> bool {
+        self.flags.contains(EditTextFlag::USE_OUTLINES)
+    }
+
+    pub fn set_html(&mut self, enable: bool) {
+        self.flags.set(EditTextFlag::HTML, enable);
+    }
+    pub fn set_read_only(&mut self, enable: bool) {
+        self.flags.set(EditTextFlag::READ_ONLY, enable);
+    }
+    pub fn set_password(&mut self, enable: bool) {
+        self.flags.set(EditTextFlag::PASSWORD, enable);
+    }
+    pub fn set_multiline(&mut self, enable: bool) {
+        self.flags.set(EditTextFlag::MULTILINE, enable);
+    }
+    pub fn set_use_outlines(&mut self, enable: bool) {
+        self.flags.set(EditTextFlag::USE_OUTLINES, enable);
+    }
+}
+
+impl ITag for DefineEditTextTag {
+    fn read(stream: &mut StreamReader) -> Result<Self> {
+        let char_id = stream.read_u16()?;
+        let bounds = Rect::read(stream)?;
+        // The first byte holds the most significant flags
+        let flags = EditTextFlag::from_bits_retain(stream.read_u16()?.swap_bytes());
+
+        let font_id = if flags.contains(EditTextFlag::HAS_FONT) {
+            Some(stream.read_u16()?)
+        } else {
+            None
+        };
+        let font_class = if flags.contains(EditTextFlag::HAS_FONT_CLASS) {
+            Some(stream.read_null_string()?)
+        } else {
+            None
+        };
+        let font_height = if font_id.is_some() || font_class.is_some() {
+            stream.read_u16()?
+        } else {
+            0
+        };
+        let color = if flags.contains(EditTextFlag::HAS_TEXT_COLOR) {
+            Some(Rgba::read(stream)?)
+        } else {
+            None
+        };
+        let max_length = if flags.contains(EditTextFlag::HAS_MAX_LENGTH) {
+            Some(stream.read_u16()?)
+        } else {
+            None
+        };
+        let layout = if flags.contains(EditTextFlag::HAS_LAYOUT) {
+            Some(TextLayout {
+                align: TextAlign::from_u8(stream.read_u8()?),
+                left_margin: stream.read_u16()?,
+                right_margin: stream.read_u16()?,
+                indent: stream.read_u16()?,
+                leading: stream.read_i16()?,
+            })
+        } else {
+            None
+        };
+        let variable_name = stream.read_null_string()?;
+        let initial_text = if flags.contains(EditTextFlag::HAS_TEXT) {
+            Some(stream.read_null_string()?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            char_id,
+            bounds,
+            flags,
+            font_id,
+            font_class,
+            font_height,
+            color,
+            max_length,
+            layout,
+            variable_name,
+            initial_text,
+        })
+    }
+
+    fn write(&self, stream: &mut StreamWriter, _movie: &Movie) -> Result<()> {
+        let mut flags = self.flags;
+        flags.set(EditTextFlag::HAS_FONT, self.font_id.is_some());
+        flags.set(EditTextFlag::HAS_FONT_CLASS, self.font_class.is_some());
+        flags.set(EditTextFlag::HAS_TEXT_COLOR, self.color.is_some());
+        flags.set(EditTextFlag::HAS_MAX_LENGTH, self.max_length.is_some());
+        flags.set(EditTextFlag::HAS_LAYOUT, self.layout.is_some());
+        flags.set(EditTextFlag::HAS_TEXT, self.initial_text.is_some());
+
+        stream.write_u16(self.char_id)?;
+        self.bounds.write(stream)?;
+        stream.write_u16(flags.bits().swap_bytes())?;
+        if let Some(font_id) = self.font_id {
+            stream.write_u16(font_id)?;
+        }
+        if let Some(font_class) = &self.font_class {
+            stream.write_null_string(font_class)?;
+        }
+        if self.font_id.is_some() || self.font_class.is_some() {
+            stream.write_u16(self.font_height)?;
+        }
+        if let Some(color) = &self.color {
+            color.write(stream)?;
+        }
+        if let Some(max_length) = self.max_length {
+            stream.write_u16(max_length)?;
+        }
+        if let Some(layout) = &self.layout {
+            stream.write_u8(layout.align as u8)?;
+            stream.write_u16(layout.left_margin)?;
+            stream.write_u16(layout.right_margin)?;
+            stream.write_u16(layout.indent)?;
+            stream.write_i16(layout.leading)?;
+        }
+        stream.write_null_string(&self.variable_name)?;
+        if let Some(text) = &self.initial_text {
+            stream.write_null_string(text)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::swf::datatypes::{Position, Rgb};
+
+    #[test]
+    fn round_trip() {
+        let mut text = DefineEditTextTag {
+            char_id: 12,
+            bounds: Rect {
+                min: Position { x: -40, y: -40 },
+                max: Position { x: 4000, y: 600 },
+            },
+            flags: EditTextFlag::HTML | EditTextFlag::WORD_WRAP | EditTextFlag::NO_SELECT,
+            font_id: Some(3),
+            font_height: 240,
+            color: Some(Rgba {
+                rgb: Rgb { r: 255, g: 0, b: 0 },
+                a: 255,
+            }),
+            layout: Some(TextLayout {
+                align: TextAlign::Center,
+                leading: 2,
+                ..Default::default()
+            }),
+            variable_name: "_root.title".to_owned(),
+            initial_text: Some("<p>Hello</p>".to_owned()),
+            ..Default::default()
+        };
+
+        let mut stream = StreamWriter::default();
+        text.write(&mut stream, &Movie::default()).unwrap();
+        let mut reader = StreamReader::new(stream.buffer());
+        let read = DefineEditTextTag::read(&mut reader).unwrap();
+        assert!(reader.finished());
+        assert!(read.is_html());
+        text.flags = read.flags;
+        assert_eq!(read, text);
+    }
+}
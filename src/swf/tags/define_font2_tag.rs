@@ -0,0 +1,348 @@
+use super::ITag;
+use crate::{
+    error::{RabcError, Result},
+    swf::datatypes::{Rect, Shape},
+    Movie, StreamReader, StreamWriter,
+};
+use bitflags::bitflags;
+
+bitflags! {
+    #[derive(Clone, Copy, Debug, PartialEq, Default)]
+    pub struct FontFlag : u8 {
+        const BOLD = 0x01;
+        const ITALIC = 0x02;
+        const WIDE_CODES = 0x04;
+        const WIDE_OFFSETS = 0x08;
+        const ANSI = 0x10;
+        const SMALL_TEXT = 0x20;
+        const SHIFT_JIS = 0x40;
+        const HAS_LAYOUT = 0x80;
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct Glyph {
+    /// Character code of the glyph, in UCS-2 if the font uses wide codes
+    pub code: u16,
+    pub shape: Shape,
+    /// Advance value, only written if the font has a layout
+    pub advance: i16,
+    /// Bounding box, only written if the font has a layout
+    pub bounds: Rect,
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct KerningRecord {
+    pub left_code: u16,
+    pub right_code: u16,
+    pub adjustment: i16,
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct FontLayout {
+    pub ascent: u16,
+    pub descent: u16,
+    pub leading: i16,
+    pub kerning: Vec<KerningRecord>,
+}
+
+/// Embedded font, used by both DefineFont2 and DefineFont3.
+/// DefineFont3 glyphs use a 20 times more precise EM square (20480 units instead of 1024).
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct DefineFont2Tag {
+    pub font_id: u16,
+    /// The HAS_LAYOUT flag is ignored when writing. WIDE_OFFSETS and WIDE_CODES are set if required.
+    pub flags: FontFlag,
+    pub language: u8,
+    /// Raw font name, may contain a trailing null byte
+    pub name: Vec<u8>,
+    pub glyphs: Vec<Glyph>,
+    pub layout: Option<FontLayout>,
+}
+
+impl DefineFont2Tag {
+    /// Get the font's name, without the trailing null byte
+    pub fn name(&self) -> String {
+        String::from_utf8_lossy(&self.name)
+            .trim_end_matches('\0')
+            .to_owned()
+    }
+
+    pub fn is_bold(&self) -> bool {
+        self.flags.contains(FontFlag::BOLD)
+    }
+    pub fn is_italic(&self) -> bool {
+        self.flags.contains(FontFlag::ITALIC)
+    }
+    pub fn is_small_text(&self) -> bool {
+        self.flags.contains(FontFlag::SMALL_TEXT)
+    }
+
+    /// Get the glyph associated with a character code
+    pub fn glyph(&self, code: u16) -> Option<&Glyph> {
+        self.glyphs.iter().find(|g| g.code == code)
+    }
+    /// Get the index of the glyph associated with a character code.
+    /// Text records refer to glyphs by their index.
+    pub fn glyph_index(&self, code: u16) -> Option<usize> {
+        self.glyphs.iter().position(|g| g.code == code)
+    }
+
+    /// Only keep the glyphs whose code matches the predicate, and their kerning pairs.
+    /// Glyph indices change, so text records and align zones using this font must be updated accordingly.
+    pub fn retain_glyphs<F: FnMut(u16) -> bool>(&mut self, mut f: F) {
+        self.glyphs.retain(|g| f(g.code));
+        let glyphs = &self.glyphs;
+        if let Some(layout) = &mut self.layout {
+            layout.kerning.retain(|k| {
+                glyphs.iter().any(|g| g.code == k.left_code)
+                    && glyphs.iter().any(|g| g.code == k.right_code)
+            });
+        }
+    }
+
+    fn read_code(stream: &mut StreamReader, wide: bool) -> Result<u16> {
+        if wide {
+            stream.read_u16()
+        } else {
+            Ok(stream.read_u8()?.into())
+        }
+    }
+    fn write_code(stream: &mut StreamWriter, wide: bool, code: u16) -> Result<()> {
+        if wide {
+            stream.write_u16(code)
+        } else {
+            let code = u8::try_from(code).map_err(|_| RabcError::ValueOutOfRange("glyph code"))?;
+            stream.write_u8(code)
+        }
+    }
+}
+
+impl ITag for DefineFont2Tag {
+    fn read(stream: &mut StreamReader) -> Result<Self> {
+        let font_id = stream.read_u16()?;
+        let flags = FontFlag::from_bits_retain(stream.read_u8()?);
+        let language = stream.read_u8()?;
+        let mut name = vec![0u8; stream.read_u8()? as usize];
+        stream.read_exact(&mut name)?;
+
+        let wide_offsets = flags.contains(FontFlag::WIDE_OFFSETS);
+        let wide_codes = flags.contains(FontFlag::WIDE_CODES);
+        let count = stream.read_u16()? as usize;
+
+        // The shapes are read sequentially, the offsets are only needed to write them back
+        let offset_size = if wide_offsets { 4 } else { 2 };
+        stream.skip((count * offset_size) as u32)?;
+        // Some compilers omit the code table offset for empty fonts
+        if count > 0 || !stream.finished() {
+            stream.skip(offset_size as u32)?;
+        }
+
+        let mut glyphs = Vec::with_capacity(count);
+        for _ in 0..count {
+            glyphs.push(Glyph {
+                shape: Shape::read(stream)?,
+                ..Default::default()
+            });
+        }
+        for glyph in &mut glyphs {
+            glyph.code = Self::read_code(stream, wide_codes)?;
+        }
+
+        let layout = if flags.contains(FontFlag::HAS_LAYOUT) {
+            let ascent = stream.read_u16()?;
+            let descent = stream.read_u16()?;
+            let leading = stream.read_i16()?;
+            for glyph in &mut glyphs {
+                glyph.advance = stream.read_i16()?;
+            }
+            for glyph in &mut glyphs {
+                glyph.bounds = Rect::read(stream)?;
+            }
+
+            let count = stream.read_u16()?;
+            let mut kerning = Vec::with_capacity(count as usize);
+            for _ in 0..count {
+                kerning.push(KerningRecord {
+                    left_code: Self::read_code(stream, wide_codes)?,
+                    right_code: Self::read_code(stream, wide_codes)?,
+                    adjustment: stream.read_i16()?,
+                });
+            }
+            Some(FontLayout {
+                ascent,
+                descent,
+                leading,
+                kerning,
+            })
+        } else {
+            None
+        };
+
+        Ok(Self {
+            font_id,
+            flags,
+            language,
+            name,
+            glyphs,
+            layout,
+        })
+    }
+
+    fn write(&self, stream: &mut StreamWriter, _movie: &Movie) -> Result<()> {
+        let mut shapes = StreamWriter::default();
+        let mut offsets = Vec::with_capacity(self.glyphs.len());
+        for glyph in &self.glyphs {
+            offsets.push(shapes.len());
+            glyph.shape.write(&mut shapes)?;
+        }
+
+        let mut flags = self.flags;
+        let kerning = self.layout.iter().flat_map(|l| &l.kerning);
+        let max_code = self
+            .glyphs
+            .iter()
+            .map(|g| g.code)
+            .chain(kerning.flat_map(|k| [k.left_code, k.right_code]))
+            .max()
+            .unwrap_or(0);
+        let table_size = (self.glyphs.len() + 1) * 2;
+        if max_code > 0xFF {
+            flags.insert(FontFlag::WIDE_CODES);
+        }
+        if shapes.len() + table_size > 0xFFFF {
+            flags.insert(FontFlag::WIDE_OFFSETS);
+        }
+        flags.set(FontFlag::HAS_LAYOUT, self.layout.is_some());
+        let wide_offsets = flags.contains(FontFlag::WIDE_OFFSETS);
+        let wide_codes = flags.contains(FontFlag::WIDE_CODES);
+
+        stream.write_u16(self.font_id)?;
+        stream.write_u8(flags.bits())?;
+        stream.write_u8(self.language)?;
+        let name_len = u8::try_from(self.name.len())
+            .map_err(|_| RabcError::ValueOutOfRange("font name length"))?;
+        let glyph_count = u16::try_from(self.glyphs.len())
+            .map_err(|_| RabcError::ValueOutOfRange("glyph count"))?;
+        stream.write_u8(name_len)?;
+        stream.write_exact(&self.name)?;
+        stream.write_u16(glyph_count)?;
+
+        // Offsets are relative to the start of the offset table
+        let table_size = if wide_offsets {
+            table_size * 2
+        } else {
+            table_size
+        };
+        for offset in offsets.iter().chain([&shapes.len()]) {
+            if wide_offsets {
+                stream.write_u32((table_size + offset) as u32)?;
+            } else {
+                stream.write_u16((table_size + offset) as u16)?;
+            }
+        }
+        stream.write_stream(&shapes)?;
+        for glyph in &self.glyphs {
+            Self::write_code(stream, wide_codes, glyph.code)?;
+        }
+
+        if let Some(layout) = &self.layout {
+            stream.write_u16(layout.ascent)?;
+            stream.write_u16(layout.descent)?;
+            stream.write_i16(layout.leading)?;
+            for glyph in &self.glyphs {
+                stream.write_i16(glyph.advance)?;
+            }
+            for glyph in &self.glyphs {
+                glyph.bounds.write(stream)?;
+            }
+            let kerning_count = u16::try_from(layout.kerning.len())
+                .map_err(|_| RabcError::ValueOutOfRange("kerning count"))?;
+            stream.write_u16(kerning_count)?;
+            for record in &layout.kerning {
+                Self::write_code(stream, wide_codes, record.left_code)?;
+                Self::write_code(stream, wide_codes, record.right_code)?;
+                stream.write_i16(record.adjustment)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::swf::datatypes::{Position, ShapeRecord, StraightEdgeRecord, StyleChangeRecord};
+
+    fn glyph(code: u16) -> Glyph {
+        Glyph {
+            code,
+            shape: Shape {
+                records: vec![
+                    ShapeRecord::StyleChange(StyleChangeRecord {
+                        move_to: Some(Position { x: 10, y: -700 }),
+                        fill_style1: Some(1),
+                        ..Default::default()
+                    }),
+                    ShapeRecord::StraightEdge(StraightEdgeRecord {
+                        delta_x: 0,
+                        delta_y: 700,
+                    }),
+                ],
+            },
+            advance: 550,
+            bounds: Rect {
+                min: Position { x: 10, y: -700 },
+                max: Position { x: 60, y: 0 },
+            },
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut font = DefineFont2Tag {
+            font_id: 3,
+            flags: FontFlag::BOLD | FontFlag::HAS_LAYOUT,
+            language: 1,
+            name: b"Verdana\0".to_vec(),
+            glyphs: vec![glyph(0x41), glyph(0x42), glyph(0x263A)],
+            layout: Some(FontLayout {
+                ascent: 1000,
+                descent: 200,
+                leading: -10,
+                kerning: vec![KerningRecord {
+                    left_code: 0x41,
+                    right_code: 0x42,
+                    adjustment: -40,
+                }],
+            }),
+        };
+
+        let mut stream = StreamWriter::default();
+        font.write(&mut stream, &Movie::default()).unwrap();
+        let mut reader = StreamReader::new(stream.buffer());
+        let read = DefineFont2Tag::read(&mut reader).unwrap();
+        assert!(reader.finished());
+        assert_eq!(read.name(), "Verdana");
+        assert_eq!(read.flags, font.flags | FontFlag::WIDE_CODES);
+        font.flags = read.flags;
+        assert_eq!(read, font);
+
+        font.retain_glyphs(|code| code != 0x42);
+        assert_eq!(font.glyph_index(0x263A), Some(1));
+        assert!(font.layout.unwrap().kerning.is_empty());
+    }
+
+    #[test]
+    fn name_too_long() {
+        let font = DefineFont2Tag {
+            name: vec![b'a'; 0x100],
+            ..Default::default()
+        };
+        let mut stream = StreamWriter::default();
+        assert!(matches!(
+            font.write(&mut stream, &Movie::default()),
+            Err(RabcError::ValueOutOfRange("font name length"))
+        ));
+    }
+}
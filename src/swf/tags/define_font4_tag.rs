@@ -0,0 +1,94 @@
+use super::ITag;
+use crate::{error::Result, Movie, StreamReader, StreamWriter};
+use bitflags::bitflags;
+
+bitflags! {
+    #[derive(Clone, Copy, Debug, PartialEq, Default)]
+    pub struct Font4Flag : u8 {
+        const BOLD = 0x01;
+        const ITALIC = 0x02;
+        const HAS_FONT_DATA = 0x04;
+    }
+}
+
+/// Embedded font in the Compact Font Format (CFF), used by the Text Layout Framework
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct DefineFont4Tag {
+    pub font_id: u16,
+    /// The HAS_FONT_DATA flag is ignored when writing
+    pub flags: Font4Flag,
+    pub name: String,
+    /// OpenType CFF font, empty if the font isn't embedded
+    pub data: Vec<u8>,
+}
+
+impl DefineFont4Tag {
+    pub fn is_bold(&self) -> bool {
+        self.flags.contains(Font4Flag::BOLD)
+    }
+    pub fn is_italic(&self) -> bool {
+        self.flags.contains(Font4Flag::ITALIC)
+    }
+    pub fn has_font_data(&self) -> bool {
+        !self.data.is_empty()
+    }
+}
+
+impl ITag for DefineFont4Tag {
+    fn read(stream: &mut StreamReader) -> Result<Self> {
+        let font_id = stream.read_u16()?;
+        let flags = Font4Flag::from_bits_retain(stream.read_u8()?);
+        let name = stream.read_null_string()?;
+        let mut data = vec![0u8; stream.remaining() as usize];
+        stream.read_exact(&mut data)?;
+
+        Ok(Self {
+            font_id,
+            flags,
+            name,
+            data,
+        })
+    }
+
+    fn write(&self, stream: &mut StreamWriter, _movie: &Movie) -> Result<()> {
+        let mut flags = self.flags;
+        flags.set(Font4Flag::HAS_FONT_DATA, self.has_font_data());
+
+        stream.write_u16(self.font_id)?;
+        stream.write_u8(flags.bits())?;
+        stream.write_null_string(&self.name)?;
+        stream.write_exact(&self.data)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let tag = DefineFont4Tag {
+            font_id: 5,
+            flags: Font4Flag::ITALIC | Font4Flag::HAS_FONT_DATA,
+            name: "Myriad".to_string(),
+            data: b"OTTO".to_vec(),
+        };
+        let mut stream = StreamWriter::default();
+        tag.write(&mut stream, &Movie::default()).unwrap();
+        let mut reader = StreamReader::new(stream.buffer());
+        assert_eq!(DefineFont4Tag::read(&mut reader).unwrap(), tag);
+        assert!(tag.is_italic() && !tag.is_bold());
+
+        // The flag follows the data
+        let tag = DefineFont4Tag {
+            data: Vec::new(),
+            ..tag
+        };
+        let mut stream = StreamWriter::default();
+        tag.write(&mut stream, &Movie::default()).unwrap();
+        let read = DefineFont4Tag::read(&mut StreamReader::new(stream.buffer())).unwrap();
+        assert_eq!(read.flags, Font4Flag::ITALIC);
+        assert!(!read.has_font_data());
+    }
+}
@@ -0,0 +1,138 @@
+use super::ITag;
+use crate::{
+    error::{RabcError, Result},
+    swf::datatypes::Float16,
+    Movie, StreamReader, StreamWriter,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum CsmTableHint {
+    #[default]
+    Thin = 0,
+    Medium = 1,
+    Thick = 2,
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct ZoneData {
+    pub alignment_coordinate: Float16,
+    pub range: Float16,
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct ZoneRecord {
+    pub zone_data: Vec<ZoneData>,
+    pub mask_x: bool,
+    pub mask_y: bool,
+}
+
+/// Advanced anti-aliasing hints of a DefineFont3 font, with a zone record for each glyph
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct DefineFontAlignZonesTag {
+    pub font_id: u16,
+    pub csm_table_hint: CsmTableHint,
+    pub zones: Vec<ZoneRecord>,
+}
+
+impl CsmTableHint {
+    pub fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::Medium,
+            2 => Self::Thick,
+            _ => Self::Thin,
+        }
+    }
+}
+
+impl ITag for DefineFontAlignZonesTag {
+    fn read(stream: &mut StreamReader) -> Result<Self> {
+        let font_id = stream.read_u16()?;
+        let csm_table_hint = CsmTableHint::from_u8(stream.read_u8()? >> 6);
+
+        let mut zones = Vec::new();
+        while !stream.finished() {
+            let count = stream.read_u8()?;
+            let mut zone_data = Vec::with_capacity(count as usize);
+            for _ in 0..count {
+                zone_data.push(ZoneData {
+                    alignment_coordinate: Float16::read(stream)?,
+                    range: Float16::read(stream)?,
+                });
+            }
+            let mask = stream.read_u8()?;
+            zones.push(ZoneRecord {
+                zone_data,
+                mask_x: mask & 0x01 != 0,
+                mask_y: mask & 0x02 != 0,
+            });
+        }
+
+        Ok(Self {
+            font_id,
+            csm_table_hint,
+            zones,
+        })
+    }
+
+    fn write(&self, stream: &mut StreamWriter, _movie: &Movie) -> Result<()> {
+        stream.write_u16(self.font_id)?;
+        stream.write_u8((self.csm_table_hint as u8) << 6)?;
+        for zone in &self.zones {
+            let count = u8::try_from(zone.zone_data.len())
+                .map_err(|_| RabcError::ValueOutOfRange("zone data count"))?;
+            stream.write_u8(count)?;
+            for data in &zone.zone_data {
+                data.alignment_coordinate.write(stream)?;
+                data.range.write(stream)?;
+            }
+            stream.write_u8(u8::from(zone.mask_x) | (u8::from(zone.mask_y) << 1))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let zone = |coordinate: f64| ZoneData {
+            alignment_coordinate: Float16::from_f64(coordinate),
+            range: Float16::from_f64(0.5),
+        };
+        let tag = DefineFontAlignZonesTag {
+            font_id: 2,
+            csm_table_hint: CsmTableHint::Medium,
+            zones: vec![
+                ZoneRecord {
+                    zone_data: vec![zone(0.25), zone(-1.0)],
+                    mask_x: true,
+                    mask_y: true,
+                },
+                ZoneRecord {
+                    zone_data: vec![zone(2.0)],
+                    mask_x: false,
+                    mask_y: true,
+                },
+            ],
+        };
+        let mut stream = StreamWriter::default();
+        tag.write(&mut stream, &Movie::default()).unwrap();
+        assert_eq!(stream.buffer()[2], 0x40);
+        let mut reader = StreamReader::new(stream.buffer());
+        assert_eq!(DefineFontAlignZonesTag::read(&mut reader).unwrap(), tag);
+
+        let tag = DefineFontAlignZonesTag {
+            zones: vec![ZoneRecord {
+                zone_data: vec![zone(0.0); 0x100],
+                ..Default::default()
+            }],
+            ..tag
+        };
+        assert!(matches!(
+            tag.write(&mut StreamWriter::default(), &Movie::default()),
+            Err(RabcError::ValueOutOfRange("zone data count"))
+        ));
+    }
+}
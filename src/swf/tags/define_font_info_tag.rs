@@ -0,0 +1,155 @@
+use super::ITag;
+use crate::{
+    error::{RabcError, Result},
+    Movie, StreamReader, StreamWriter,
+};
+use bitflags::bitflags;
+
+bitflags! {
+    #[derive(Clone, Copy, Debug, PartialEq, Default)]
+    pub struct FontInfoFlag : u8 {
+        const WIDE_CODES = 0x01;
+        const BOLD = 0x02;
+        const ITALIC = 0x04;
+        const ANSI = 0x08;
+        const SHIFT_JIS = 0x10;
+        const SMALL_TEXT = 0x20;
+    }
+}
+
+/// Name, style and character codes of the glyphs of a DefineFont font,
+/// used by both DefineFontInfo and DefineFontInfo2.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct DefineFontInfoTag {
+    pub font_id: u16,
+    /// Raw font name, may contain a trailing null byte
+    pub name: Vec<u8>,
+    /// WIDE_CODES is set if required, and always set by DefineFontInfo2
+    pub flags: FontInfoFlag,
+    /// Only used by DefineFontInfo2
+    pub language: u8,
+    /// Character code of each glyph of the font, in the same order
+    pub codes: Vec<u16>,
+}
+
+impl DefineFontInfoTag {
+    /// Get the font's name, without the trailing null byte
+    pub fn name(&self) -> String {
+        String::from_utf8_lossy(&self.name)
+            .trim_end_matches('\0')
+            .to_owned()
+    }
+
+    pub fn read_with_version(stream: &mut StreamReader, version: u8) -> Result<Self> {
+        let font_id = stream.read_u16()?;
+        let mut name = vec![0u8; stream.read_u8()? as usize];
+        stream.read_exact(&mut name)?;
+        let flags = FontInfoFlag::from_bits_retain(stream.read_u8()?);
+        let language = if version >= 2 { stream.read_u8()? } else { 0 };
+
+        let mut codes = Vec::new();
+        while !stream.finished() {
+            codes.push(if flags.contains(FontInfoFlag::WIDE_CODES) {
+                stream.read_u16()?
+            } else {
+                stream.read_u8()? as u16
+            });
+        }
+        Ok(Self {
+            font_id,
+            name,
+            flags,
+            language,
+            codes,
+        })
+    }
+
+    pub fn write_with_version(&self, stream: &mut StreamWriter, version: u8) -> Result<()> {
+        let mut flags = self.flags;
+        flags.set(
+            FontInfoFlag::WIDE_CODES,
+            version >= 2
+                || flags.contains(FontInfoFlag::WIDE_CODES)
+                || self.codes.iter().any(|c| *c > 0xFF),
+        );
+
+        let name_len = u8::try_from(self.name.len())
+            .map_err(|_| RabcError::ValueOutOfRange("font name length"))?;
+        stream.write_u16(self.font_id)?;
+        stream.write_u8(name_len)?;
+        stream.write_exact(&self.name)?;
+        stream.write_u8(flags.bits())?;
+        if version >= 2 {
+            stream.write_u8(self.language)?;
+        }
+        for code in &self.codes {
+            if flags.contains(FontInfoFlag::WIDE_CODES) {
+                stream.write_u16(*code)?;
+            } else {
+                stream.write_u8(*code as u8)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ITag for DefineFontInfoTag {
+    fn read(stream: &mut StreamReader) -> Result<Self> {
+        Self::read_with_version(stream, 1)
+    }
+
+    fn write(&self, stream: &mut StreamWriter, _movie: &Movie) -> Result<()> {
+        self.write_with_version(stream, 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let tag = DefineFontInfoTag {
+            font_id: 2,
+            name: b"Arial\0".to_vec(),
+            flags: FontInfoFlag::BOLD | FontInfoFlag::ANSI,
+            language: 0,
+            codes: vec![b'a' as u16, b'b' as u16],
+        };
+        let mut stream = StreamWriter::default();
+        tag.write(&mut stream, &Movie::default()).unwrap();
+        assert_eq!(&stream.buffer()[9..], [0x0A, b'a', b'b']);
+        let mut reader = StreamReader::new(stream.buffer());
+        assert_eq!(DefineFontInfoTag::read(&mut reader).unwrap(), tag);
+        assert_eq!(tag.name(), "Arial");
+
+        // DefineFontInfo2 always uses wide codes
+        let tag = DefineFontInfoTag {
+            flags: FontInfoFlag::WIDE_CODES | FontInfoFlag::SMALL_TEXT,
+            language: 1,
+            codes: vec![0x3042, b'a' as u16],
+            ..tag
+        };
+        let mut stream = StreamWriter::default();
+        tag.write_with_version(&mut stream, 2).unwrap();
+        let mut reader = StreamReader::new(stream.buffer());
+        assert_eq!(
+            DefineFontInfoTag::read_with_version(&mut reader, 2).unwrap(),
+            tag
+        );
+        assert!(reader.finished());
+    }
+
+    #[test]
+    fn name_too_long() {
+        let tag = DefineFontInfoTag {
+            name: vec![b'a'; 0x100],
+            ..Default::default()
+        };
+        let mut stream = StreamWriter::default();
+        assert!(matches!(
+            tag.write(&mut stream, &Movie::default()),
+            Err(RabcError::ValueOutOfRange("font name length"))
+        ));
+    }
+}
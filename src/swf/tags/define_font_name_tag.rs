@@ -0,0 +1,46 @@
+use super::ITag;
+use crate::{error::Result, Movie, StreamReader, StreamWriter};
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct DefineFontNameTag {
+    pub font_id: u16,
+    pub name: String,
+    pub copyright: String,
+}
+
+impl ITag for DefineFontNameTag {
+    fn read(stream: &mut StreamReader) -> Result<Self> {
+        Ok(Self {
+            font_id: stream.read_u16()?,
+            name: stream.read_null_string()?,
+            copyright: stream.read_null_string()?,
+        })
+    }
+
+    fn write(&self, stream: &mut StreamWriter, _movie: &Movie) -> Result<()> {
+        stream.write_u16(self.font_id)?;
+        stream.write_null_string(&self.name)?;
+        stream.write_null_string(&self.copyright)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let tag = DefineFontNameTag {
+            font_id: 5,
+            name: "Myriad Pro".to_string(),
+            copyright: "(c) 2000".to_string(),
+        };
+        let mut stream = StreamWriter::default();
+        tag.write(&mut stream, &Movie::default()).unwrap();
+        assert_eq!(stream.buffer().len(), 2 + 11 + 9);
+        let mut reader = StreamReader::new(stream.buffer());
+        assert_eq!(DefineFontNameTag::read(&mut reader).unwrap(), tag);
+        assert!(reader.finished());
+    }
+}
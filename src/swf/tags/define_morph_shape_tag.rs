@@ -0,0 +1,923 @@
+use super::ITag;
+use crate::{
+    error::{RabcError, Result},
+    stream::{BitStreamReader, BitStreamWriter},
+    swf::datatypes::{
+        CapStyle, CurvedEdgeRecord, FillStyle, Fixed16, Fixed8, Gradient, GradientStop,
+        InterpolationMode, JoinStyle, LineStyle, Matrix, Position, Rect, Rgb, Rgba, Shape,
+        ShapeRecord, SpreadMode, StraightEdgeRecord, StyleChangeRecord, StyledShape,
+    },
+    Movie, StreamReader, StreamWriter,
+};
+
+const FILL_SOLID: u8 = 0x00;
+const FILL_LINEAR_GRADIENT: u8 = 0x10;
+const FILL_RADIAL_GRADIENT: u8 = 0x12;
+const FILL_FOCAL_RADIAL_GRADIENT: u8 = 0x13;
+const FILL_REPEATING_BITMAP: u8 = 0x40;
+const FILL_CLIPPED_BITMAP: u8 = 0x41;
+const FILL_NON_SMOOTHED_REPEATING_BITMAP: u8 = 0x42;
+const FILL_NON_SMOOTHED_CLIPPED_BITMAP: u8 = 0x43;
+
+/// Fill styles at the start and the end of the morph, both must be of the same kind
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct MorphFillStyle {
+    pub start: FillStyle,
+    pub end: FillStyle,
+}
+
+/// Line styles at the start and the end of the morph.
+/// Only the widths, colors and fills are morphed, the other settings are read from the start style.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct MorphLineStyle {
+    pub start: LineStyle,
+    pub end: LineStyle,
+}
+
+/// Shape morphing from its start edges to its end edges, used by both DefineMorphShape and
+/// DefineMorphShape2. The edge bounds, stroke hints and advanced line styles are only
+/// written in DefineMorphShape2 tags.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct DefineMorphShapeTag {
+    pub char_id: u16,
+    pub start_bounds: Rect,
+    pub end_bounds: Rect,
+    pub start_edge_bounds: Rect,
+    pub end_edge_bounds: Rect,
+    pub uses_non_scaling_strokes: bool,
+    pub uses_scaling_strokes: bool,
+    pub fill_styles: Vec<MorphFillStyle>,
+    pub line_styles: Vec<MorphLineStyle>,
+    pub start_edges: Shape,
+    /// Edges paired with the start edges, only holding edges and move-to records
+    pub end_edges: Shape,
+}
+
+impl MorphFillStyle {
+    fn read(stream: &mut StreamReader) -> Result<Self> {
+        let fill_type = stream.read_u8()?;
+        Ok(match fill_type {
+            FILL_SOLID => Self {
+                start: FillStyle::Solid(Rgba::read(stream)?),
+                end: FillStyle::Solid(Rgba::read(stream)?),
+            },
+            FILL_LINEAR_GRADIENT | FILL_RADIAL_GRADIENT | FILL_FOCAL_RADIAL_GRADIENT => {
+                let start_matrix = Matrix::read(stream)?;
+                let end_matrix = Matrix::read(stream)?;
+                let (start_gradient, end_gradient) = read_gradients(stream)?;
+                match fill_type {
+                    FILL_LINEAR_GRADIENT => Self {
+                        start: FillStyle::LinearGradient {
+                            matrix: start_matrix,
+                            gradient: start_gradient,
+                        },
+                        end: FillStyle::LinearGradient {
+                            matrix: end_matrix,
+                            gradient: end_gradient,
+                        },
+                    },
+                    FILL_RADIAL_GRADIENT => Self {
+                        start: FillStyle::RadialGradient {
+                            matrix: start_matrix,
+                            gradient: start_gradient,
+                        },
+                        end: FillStyle::RadialGradient {
+                            matrix: end_matrix,
+                            gradient: end_gradient,
+                        },
+                    },
+                    _ => Self {
+                        start: FillStyle::FocalRadialGradient {
+                            matrix: start_matrix,
+                            gradient: start_gradient,
+                            focal_point: Fixed8::read(stream)?,
+                        },
+                        end: FillStyle::FocalRadialGradient {
+                            matrix: end_matrix,
+                            gradient: end_gradient,
+                            focal_point: Fixed8::read(stream)?,
+                        },
+                    },
+                }
+            }
+            FILL_REPEATING_BITMAP
+            | FILL_CLIPPED_BITMAP
+            | FILL_NON_SMOOTHED_REPEATING_BITMAP
+            | FILL_NON_SMOOTHED_CLIPPED_BITMAP => {
+                let bitmap_id = stream.read_u16()?;
+                let repeat = fill_type & 0x01 == 0;
+                let smooth = fill_type & 0x02 == 0;
+                Self {
+                    start: FillStyle::Bitmap {
+                        bitmap_id,
+                        matrix: Matrix::read(stream)?,
+                        repeat,
+                        smooth,
+                    },
+                    end: FillStyle::Bitmap {
+                        bitmap_id,
+                        matrix: Matrix::read(stream)?,
+                        repeat,
+                        smooth,
+                    },
+                }
+            }
+            _ => {
+                return Err(RabcError::InvalidShapeRecord("unknown morph fill style"));
+            }
+        })
+    }
+
+    fn write(&self, stream: &mut StreamWriter) -> Result<()> {
+        match (&self.start, &self.end) {
+            (FillStyle::Solid(start), FillStyle::Solid(end)) => {
+                stream.write_u8(FILL_SOLID)?;
+                start.write(stream)?;
+                end.write(stream)?;
+            }
+            (
+                FillStyle::LinearGradient {
+                    matrix: start_matrix,
+                    gradient: start_gradient,
+                },
+                FillStyle::LinearGradient {
+                    matrix: end_matrix,
+                    gradient: end_gradient,
+                },
+            ) => {
+                stream.write_u8(FILL_LINEAR_GRADIENT)?;
+                start_matrix.write(stream)?;
+                end_matrix.write(stream)?;
+                write_gradients(stream, start_gradient, end_gradient)?;
+            }
+            (
+                FillStyle::RadialGradient {
+                    matrix: start_matrix,
+                    gradient: start_gradient,
+                },
+                FillStyle::RadialGradient {
+                    matrix: end_matrix,
+                    gradient: end_gradient,
+                },
+            ) => {
+                stream.write_u8(FILL_RADIAL_GRADIENT)?;
+                start_matrix.write(stream)?;
+                end_matrix.write(stream)?;
+                write_gradients(stream, start_gradient, end_gradient)?;
+            }
+            (
+                FillStyle::FocalRadialGradient {
+                    matrix: start_matrix,
+                    gradient: start_gradient,
+                    focal_point: start_focal_point,
+                },
+                FillStyle::FocalRadialGradient {
+                    matrix: end_matrix,
+                    gradient: end_gradient,
+                    focal_point: end_focal_point,
+                },
+            ) => {
+                stream.write_u8(FILL_FOCAL_RADIAL_GRADIENT)?;
+                start_matrix.write(stream)?;
+                end_matrix.write(stream)?;
+                write_gradients(stream, start_gradient, end_gradient)?;
+                start_focal_point.write(stream)?;
+                end_focal_point.write(stream)?;
+            }
+            (
+                FillStyle::Bitmap {
+                    bitmap_id,
+                    matrix: start_matrix,
+                    repeat,
+                    smooth,
+                },
+                FillStyle::Bitmap {
+                    matrix: end_matrix, ..
+                },
+            ) => {
+                let mut fill_type = FILL_REPEATING_BITMAP;
+                if !repeat {
+                    fill_type |= 0x01;
+                }
+                if !smooth {
+                    fill_type |= 0x02;
+                }
+                stream.write_u8(fill_type)?;
+                stream.write_u16(*bitmap_id)?;
+                start_matrix.write(stream)?;
+                end_matrix.write(stream)?;
+            }
+            _ => {
+                return Err(RabcError::InvalidShapeRecord(
+                    "morph fill styles must be of the same kind",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn interpolate(&self, ratio: f32) -> FillStyle {
+        interpolate_fill(&self.start, &self.end, ratio)
+    }
+}
+
+impl MorphLineStyle {
+    fn read(stream: &mut StreamReader, version: u8) -> Result<Self> {
+        let start_width = stream.read_u16()?;
+        let end_width = stream.read_u16()?;
+        if version < 2 {
+            return Ok(Self {
+                start: LineStyle {
+                    width: start_width,
+                    color: Rgba::read(stream)?,
+                    ..Default::default()
+                },
+                end: LineStyle {
+                    width: end_width,
+                    color: Rgba::read(stream)?,
+                    ..Default::default()
+                },
+            });
+        }
+
+        let flags = stream.read_u16()?.swap_bytes();
+        let join = match (flags >> 12) & 0x03 {
+            1 => JoinStyle::Bevel,
+            2 => JoinStyle::Miter(Fixed8::read(stream)?),
+            _ => JoinStyle::Round,
+        };
+        let mut start = LineStyle {
+            width: start_width,
+            start_cap: CapStyle::from_u8((flags >> 14) as u8),
+            end_cap: CapStyle::from_u8((flags & 0x03) as u8),
+            join,
+            no_h_scale: flags & 0x0400 != 0,
+            no_v_scale: flags & 0x0200 != 0,
+            pixel_hinting: flags & 0x0100 != 0,
+            no_close: flags & 0x0004 != 0,
+            ..Default::default()
+        };
+        let mut end = LineStyle {
+            width: end_width,
+            ..start.clone()
+        };
+        if flags & 0x0800 != 0 {
+            let fill = MorphFillStyle::read(stream)?;
+            start.fill = Some(fill.start);
+            end.fill = Some(fill.end);
+        } else {
+            start.color = Rgba::read(stream)?;
+            end.color = Rgba::read(stream)?;
+        }
+        Ok(Self { start, end })
+    }
+
+    fn write(&self, stream: &mut StreamWriter, version: u8) -> Result<()> {
+        stream.write_u16(self.start.width)?;
+        stream.write_u16(self.end.width)?;
+        if version < 2 {
+            self.start.color.write(stream)?;
+            return self.end.color.write(stream);
+        }
+
+        let style = &self.start;
+        let join = match style.join {
+            JoinStyle::Round => 0,
+            JoinStyle::Bevel => 1,
+            JoinStyle::Miter(_) => 2,
+        };
+        let mut flags = ((style.start_cap as u16) << 14) | (join << 12) | style.end_cap as u16;
+        if style.fill.is_some() {
+            flags |= 0x0800;
+        }
+        if style.no_h_scale {
+            flags |= 0x0400;
+        }
+        if style.no_v_scale {
+            flags |= 0x0200;
+        }
+        if style.pixel_hinting {
+            flags |= 0x0100;
+        }
+        if style.no_close {
+            flags |= 0x0004;
+        }
+        stream.write_u16(flags.swap_bytes())?;
+        if let JoinStyle::Miter(limit) = style.join {
+            limit.write(stream)?;
+        }
+        match (&self.start.fill, &self.end.fill) {
+            (Some(start), Some(end)) => MorphFillStyle {
+                start: start.clone(),
+                end: end.clone(),
+            }
+            .write(stream),
+            (None, _) => {
+                self.start.color.write(stream)?;
+                self.end.color.write(stream)
+            }
+            (Some(_), None) => Err(RabcError::InvalidShapeRecord(
+                "morph line styles must both have a fill",
+            )),
+        }
+    }
+
+    pub fn interpolate(&self, ratio: f32) -> LineStyle {
+        let fill = match (&self.start.fill, &self.end.fill) {
+            (Some(start), Some(end)) => Some(interpolate_fill(start, end, ratio)),
+            (fill, _) => fill.clone(),
+        };
+        LineStyle {
+            width: lerp(self.start.width.into(), self.end.width.into(), ratio) as u16,
+            color: lerp_color(&self.start.color, &self.end.color, ratio),
+            fill,
+            ..self.start.clone()
+        }
+    }
+}
+
+impl DefineMorphShapeTag {
+    pub fn read_with_version(stream: &mut StreamReader, version: u8) -> Result<Self> {
+        let char_id = stream.read_u16()?;
+        let start_bounds = Rect::read(stream)?;
+        let end_bounds = Rect::read(stream)?;
+        let mut tag = Self {
+            char_id,
+            start_bounds,
+            end_bounds,
+            ..Default::default()
+        };
+        if version >= 2 {
+            tag.start_edge_bounds = Rect::read(stream)?;
+            tag.end_edge_bounds = Rect::read(stream)?;
+            let flags = stream.read_u8()?;
+            tag.uses_non_scaling_strokes = flags & 0x02 != 0;
+            tag.uses_scaling_strokes = flags & 0x01 != 0;
+        }
+        // Offset to the end edges
+        let _offset = stream.read_u32()?;
+
+        let count = read_count(stream)?;
+        tag.fill_styles.reserve(count);
+        for _ in 0..count {
+            tag.fill_styles.push(MorphFillStyle::read(stream)?);
+        }
+        let count = read_count(stream)?;
+        tag.line_styles.reserve(count);
+        for _ in 0..count {
+            tag.line_styles.push(MorphLineStyle::read(stream, version)?);
+        }
+        tag.start_edges = Shape::read(stream)?;
+        tag.end_edges = Shape::read(stream)?;
+        Ok(tag)
+    }
+
+    pub fn write_with_version(&self, stream: &mut StreamWriter, version: u8) -> Result<()> {
+        stream.write_u16(self.char_id)?;
+        self.start_bounds.write(stream)?;
+        self.end_bounds.write(stream)?;
+        if version >= 2 {
+            self.start_edge_bounds.write(stream)?;
+            self.end_edge_bounds.write(stream)?;
+            stream.write_u8(
+                (u8::from(self.uses_non_scaling_strokes) << 1)
+                    | u8::from(self.uses_scaling_strokes),
+            )?;
+        }
+
+        let mut styles = StreamWriter::default();
+        write_count(&mut styles, self.fill_styles.len())?;
+        for fill in &self.fill_styles {
+            fill.write(&mut styles)?;
+        }
+        write_count(&mut styles, self.line_styles.len())?;
+        for line in &self.line_styles {
+            line.write(&mut styles, version)?;
+        }
+        self.start_edges.write(&mut styles)?;
+
+        stream.write_u32(styles.len() as u32)?;
+        stream.write_stream(&styles)?;
+        self.end_edges.write(stream)
+    }
+
+    /// Compute the shape at the given ratio, from 0 for the start shape to 1 for the end shape.
+    /// Straight edges paired with curved edges are morphed as curves.
+    pub fn interpolate(&self, ratio: f32) -> StyledShape {
+        let ratio = ratio.clamp(0.0, 1.0);
+        let mut records = Vec::with_capacity(self.start_edges.records.len());
+        let mut end_records = self.end_edges.records.iter().peekable();
+        // Absolute pen positions, to morph the move-to only present in one of the shapes
+        let mut start_pen = Position::default();
+        let mut end_pen = Position::default();
+        for record in &self.start_edges.records {
+            match record {
+                ShapeRecord::StyleChange(style) => {
+                    let mut style = style.clone();
+                    if let Some(start) = &style.move_to {
+                        start_pen = start.clone();
+                        // The end edges only hold the move-to of the style change records
+                        if let Some(ShapeRecord::StyleChange(end)) = end_records.peek() {
+                            end_pen = end.move_to.clone().unwrap_or_else(|| start.clone());
+                            end_records.next();
+                        }
+                        style.move_to = Some(lerp_position(&start_pen, &end_pen, ratio));
+                    }
+                    records.push(ShapeRecord::StyleChange(style));
+                }
+                edge => {
+                    // A move-to of the end edges without one in the start edges
+                    while let Some(ShapeRecord::StyleChange(end)) = end_records.peek() {
+                        if let Some(end) = &end.move_to {
+                            end_pen = end.clone();
+                            records.push(ShapeRecord::StyleChange(StyleChangeRecord {
+                                move_to: Some(lerp_position(&start_pen, &end_pen, ratio)),
+                                ..Default::default()
+                            }));
+                        }
+                        end_records.next();
+                    }
+                    let end = end_records.next().unwrap_or(edge);
+                    advance_pen(&mut start_pen, edge);
+                    advance_pen(&mut end_pen, end);
+                    records.push(interpolate_edge(edge, end, ratio));
+                }
+            }
+        }
+
+        StyledShape {
+            bounds: lerp_rect(&self.start_bounds, &self.end_bounds, ratio),
+            edge_bounds: lerp_rect(&self.start_edge_bounds, &self.end_edge_bounds, ratio),
+            fill_styles: self
+                .fill_styles
+                .iter()
+                .map(|f| f.interpolate(ratio))
+                .collect(),
+            line_styles: self
+                .line_styles
+                .iter()
+                .map(|l| l.interpolate(ratio))
+                .collect(),
+            shape: Shape { records },
+        }
+    }
+}
+
+impl ITag for DefineMorphShapeTag {
+    fn read(stream: &mut StreamReader) -> Result<Self> {
+        Self::read_with_version(stream, 1)
+    }
+
+    fn write(&self, stream: &mut StreamWriter, _movie: &Movie) -> Result<()> {
+        self.write_with_version(stream, 1)
+    }
+}
+
+/// Style arrays store their length on a byte, or on 2 more bytes if it's 0xFF
+fn read_count(stream: &mut StreamReader) -> Result<usize> {
+    let count = stream.read_u8()?;
+    if count == 0xFF {
+        return Ok(stream.read_u16()?.into());
+    }
+    Ok(count.into())
+}
+
+fn write_count(stream: &mut StreamWriter, count: usize) -> Result<()> {
+    if count < 0xFF {
+        return stream.write_u8(count as u8);
+    }
+    stream.write_u8(0xFF)?;
+    stream.write_u16(count as u16)
+}
+
+fn read_gradients(stream: &mut StreamReader) -> Result<(Gradient, Gradient)> {
+    let flags = {
+        let mut bs = BitStreamReader::new(stream);
+        (
+            SpreadMode::from_u8(bs.read_ub(2)? as u8),
+            InterpolationMode::from_u8(bs.read_ub(2)? as u8),
+            bs.read_ub(4)?,
+        )
+    };
+    let (spread, interpolation, count) = flags;
+    let mut start = Gradient {
+        spread,
+        interpolation,
+        stops: Vec::with_capacity(count as usize),
+    };
+    let mut end = start.clone();
+    for _ in 0..count {
+        start.stops.push(GradientStop {
+            ratio: stream.read_u8()?,
+            color: Rgba::read(stream)?,
+        });
+        end.stops.push(GradientStop {
+            ratio: stream.read_u8()?,
+            color: Rgba::read(stream)?,
+        });
+    }
+    Ok((start, end))
+}
+
+fn write_gradients(stream: &mut StreamWriter, start: &Gradient, end: &Gradient) -> Result<()> {
+    if start.stops.len() != end.stops.len() || start.stops.len() > 0x0F {
+        return Err(RabcError::InvalidShapeRecord(
+            "morph gradients must have the same number of stops, up to 15",
+        ));
+    }
+    {
+        let mut bs = BitStreamWriter::new(stream);
+        bs.write_ub(2, start.spread as u32)?;
+        bs.write_ub(2, start.interpolation as u32)?;
+        bs.write_ub(4, start.stops.len() as u32)?;
+        bs.flush()?;
+    }
+    for (start, end) in start.stops.iter().zip(&end.stops) {
+        stream.write_u8(start.ratio)?;
+        start.color.write(stream)?;
+        stream.write_u8(end.ratio)?;
+        end.color.write(stream)?;
+    }
+    Ok(())
+}
+
+fn lerp(start: i32, end: i32, ratio: f32) -> i32 {
+    start + ((end - start) as f32 * ratio).round() as i32
+}
+
+fn lerp_position(start: &Position, end: &Position, ratio: f32) -> Position {
+    Position {
+        x: lerp(start.x, end.x, ratio),
+        y: lerp(start.y, end.y, ratio),
+    }
+}
+
+fn lerp_rect(start: &Rect, end: &Rect, ratio: f32) -> Rect {
+    Rect {
+        min: lerp_position(&start.min, &end.min, ratio),
+        max: lerp_position(&start.max, &end.max, ratio),
+    }
+}
+
+fn lerp_color(start: &Rgba, end: &Rgba, ratio: f32) -> Rgba {
+    let channel = |a: u8, b: u8| lerp(a.into(), b.into(), ratio) as u8;
+    Rgba {
+        rgb: Rgb {
+            r: channel(start.rgb.r, end.rgb.r),
+            g: channel(start.rgb.g, end.rgb.g),
+            b: channel(start.rgb.b, end.rgb.b),
+        },
+        a: channel(start.a, end.a),
+    }
+}
+
+fn lerp_matrix(start: &Matrix, end: &Matrix, ratio: f32) -> Matrix {
+    Matrix {
+        scale_x: Fixed16(lerp(start.scale_x.0, end.scale_x.0, ratio)),
+        scale_y: Fixed16(lerp(start.scale_y.0, end.scale_y.0, ratio)),
+        rotate_skew0: Fixed16(lerp(start.rotate_skew0.0, end.rotate_skew0.0, ratio)),
+        rotate_skew1: Fixed16(lerp(start.rotate_skew1.0, end.rotate_skew1.0, ratio)),
+        translate_x: lerp(start.translate_x, end.translate_x, ratio),
+        translate_y: lerp(start.translate_y, end.translate_y, ratio),
+    }
+}
+
+fn lerp_gradient(start: &Gradient, end: &Gradient, ratio: f32) -> Gradient {
+    Gradient {
+        stops: start
+            .stops
+            .iter()
+            .zip(&end.stops)
+            .map(|(start, end)| GradientStop {
+                color: lerp_color(&start.color, &end.color, ratio),
+                ratio: lerp(start.ratio.into(), end.ratio.into(), ratio) as u8,
+            })
+            .collect(),
+        ..start.clone()
+    }
+}
+
+fn interpolate_fill(start: &FillStyle, end: &FillStyle, ratio: f32) -> FillStyle {
+    match (start, end) {
+        (FillStyle::Solid(start), FillStyle::Solid(end)) => {
+            FillStyle::Solid(lerp_color(start, end, ratio))
+        }
+        (
+            FillStyle::LinearGradient {
+                matrix: start_matrix,
+                gradient: start_gradient,
+            },
+            FillStyle::LinearGradient {
+                matrix: end_matrix,
+                gradient: end_gradient,
+            },
+        ) => FillStyle::LinearGradient {
+            matrix: lerp_matrix(start_matrix, end_matrix, ratio),
+            gradient: lerp_gradient(start_gradient, end_gradient, ratio),
+        },
+        (
+            FillStyle::RadialGradient {
+                matrix: start_matrix,
+                gradient: start_gradient,
+            },
+            FillStyle::RadialGradient {
+                matrix: end_matrix,
+                gradient: end_gradient,
+            },
+        ) => FillStyle::RadialGradient {
+            matrix: lerp_matrix(start_matrix, end_matrix, ratio),
+            gradient: lerp_gradient(start_gradient, end_gradient, ratio),
+        },
+        (
+            FillStyle::FocalRadialGradient {
+                matrix: start_matrix,
+                gradient: start_gradient,
+                focal_point: start_focal_point,
+            },
+            FillStyle::FocalRadialGradient {
+                matrix: end_matrix,
+                gradient: end_gradient,
+                focal_point: end_focal_point,
+            },
+        ) => FillStyle::FocalRadialGradient {
+            matrix: lerp_matrix(start_matrix, end_matrix, ratio),
+            gradient: lerp_gradient(start_gradient, end_gradient, ratio),
+            focal_point: Fixed8(
+                lerp(start_focal_point.0.into(), end_focal_point.0.into(), ratio) as i16,
+            ),
+        },
+        (
+            FillStyle::Bitmap {
+                bitmap_id,
+                matrix: start_matrix,
+                repeat,
+                smooth,
+            },
+            FillStyle::Bitmap {
+                matrix: end_matrix, ..
+            },
+        ) => FillStyle::Bitmap {
+            bitmap_id: *bitmap_id,
+            matrix: lerp_matrix(start_matrix, end_matrix, ratio),
+            repeat: *repeat,
+            smooth: *smooth,
+        },
+        (start, _) => start.clone(),
+    }
+}
+
+/// Edge deltas as a curve: control then anchor deltas
+fn curve_deltas(edge: &ShapeRecord) -> [i32; 4] {
+    match edge {
+        ShapeRecord::StraightEdge(edge) => {
+            let (half_x, half_y) = (edge.delta_x / 2, edge.delta_y / 2);
+            [half_x, half_y, edge.delta_x - half_x, edge.delta_y - half_y]
+        }
+        ShapeRecord::CurvedEdge(edge) => [
+            edge.control_delta_x,
+            edge.control_delta_y,
+            edge.anchor_delta_x,
+            edge.anchor_delta_y,
+        ],
+        ShapeRecord::StyleChange(_) => [0; 4],
+    }
+}
+
+fn advance_pen(pen: &mut Position, edge: &ShapeRecord) {
+    let [control_x, control_y, anchor_x, anchor_y] = curve_deltas(edge);
+    pen.x += control_x + anchor_x;
+    pen.y += control_y + anchor_y;
+}
+
+fn interpolate_edge(start: &ShapeRecord, end: &ShapeRecord, ratio: f32) -> ShapeRecord {
+    if let (ShapeRecord::StraightEdge(start), ShapeRecord::StraightEdge(end)) = (start, end) {
+        return ShapeRecord::StraightEdge(StraightEdgeRecord {
+            delta_x: lerp(start.delta_x, end.delta_x, ratio),
+            delta_y: lerp(start.delta_y, end.delta_y, ratio),
+        });
+    }
+
+    let start = curve_deltas(start);
+    let end = curve_deltas(end);
+    ShapeRecord::CurvedEdge(CurvedEdgeRecord {
+        control_delta_x: lerp(start[0], end[0], ratio),
+        control_delta_y: lerp(start[1], end[1], ratio),
+        anchor_delta_x: lerp(start[2], end[2], ratio),
+        anchor_delta_y: lerp(start[3], end[3], ratio),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn morph_shape() -> DefineMorphShapeTag {
+        DefineMorphShapeTag {
+            char_id: 5,
+            start_bounds: Rect {
+                min: Position { x: 0, y: 0 },
+                max: Position { x: 200, y: 200 },
+            },
+            end_bounds: Rect {
+                min: Position { x: 0, y: 0 },
+                max: Position { x: 400, y: 100 },
+            },
+            start_edge_bounds: Rect {
+                min: Position { x: 10, y: 10 },
+                max: Position { x: 190, y: 190 },
+            },
+            uses_scaling_strokes: true,
+            fill_styles: vec![
+                MorphFillStyle {
+                    start: FillStyle::Solid(Rgba {
+                        rgb: Rgb { r: 255, g: 0, b: 0 },
+                        a: 255,
+                    }),
+                    end: FillStyle::Solid(Rgba {
+                        rgb: Rgb { r: 0, g: 0, b: 255 },
+                        a: 0,
+                    }),
+                },
+                MorphFillStyle {
+                    start: FillStyle::FocalRadialGradient {
+                        matrix: Matrix::IDENTITY,
+                        gradient: Gradient {
+                            spread: SpreadMode::Reflect,
+                            stops: vec![GradientStop {
+                                color: Rgba::default(),
+                                ratio: 0,
+                            }],
+                            ..Default::default()
+                        },
+                        focal_point: Fixed8(-256),
+                    },
+                    end: FillStyle::FocalRadialGradient {
+                        matrix: Matrix {
+                            translate_x: 100,
+                            ..Default::default()
+                        },
+                        gradient: Gradient {
+                            spread: SpreadMode::Reflect,
+                            stops: vec![GradientStop {
+                                color: Rgba::default(),
+                                ratio: 255,
+                            }],
+                            ..Default::default()
+                        },
+                        focal_point: Fixed8::ONE,
+                    },
+                },
+            ],
+            line_styles: vec![MorphLineStyle {
+                start: LineStyle {
+                    width: 20,
+                    join: JoinStyle::Miter(Fixed8(3 << 8)),
+                    end_cap: CapStyle::Square,
+                    ..Default::default()
+                },
+                end: LineStyle {
+                    width: 60,
+                    join: JoinStyle::Miter(Fixed8(3 << 8)),
+                    end_cap: CapStyle::Square,
+                    ..Default::default()
+                },
+            }],
+            start_edges: Shape {
+                records: vec![
+                    ShapeRecord::StyleChange(StyleChangeRecord {
+                        move_to: Some(Position { x: 0, y: 0 }),
+                        fill_style0: Some(1),
+                        line_style: Some(1),
+                        ..Default::default()
+                    }),
+                    ShapeRecord::StraightEdge(StraightEdgeRecord {
+                        delta_x: 200,
+                        delta_y: 0,
+                    }),
+                    ShapeRecord::StraightEdge(StraightEdgeRecord {
+                        delta_x: 0,
+                        delta_y: 200,
+                    }),
+                ],
+            },
+            end_edges: Shape {
+                records: vec![
+                    ShapeRecord::StyleChange(StyleChangeRecord {
+                        move_to: Some(Position { x: 0, y: 100 }),
+                        ..Default::default()
+                    }),
+                    ShapeRecord::StraightEdge(StraightEdgeRecord {
+                        delta_x: 400,
+                        delta_y: 0,
+                    }),
+                    ShapeRecord::CurvedEdge(CurvedEdgeRecord {
+                        control_delta_x: 0,
+                        control_delta_y: -50,
+                        anchor_delta_x: 0,
+                        anchor_delta_y: -50,
+                    }),
+                ],
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let shape = morph_shape();
+        let mut stream = StreamWriter::default();
+        shape.write_with_version(&mut stream, 2).unwrap();
+        let mut reader = StreamReader::new(stream.buffer());
+        assert_eq!(
+            DefineMorphShapeTag::read_with_version(&mut reader, 2).unwrap(),
+            shape
+        );
+        assert!(reader.finished());
+    }
+
+    #[test]
+    fn interpolate() {
+        let morph = morph_shape();
+        // Straight edges paired with curves are morphed as curves
+        let shape = morph.interpolate(0.0);
+        assert_eq!(shape.shape.records[..2], morph.start_edges.records[..2]);
+        assert_eq!(
+            shape.shape.records[2],
+            ShapeRecord::CurvedEdge(CurvedEdgeRecord {
+                control_delta_x: 0,
+                control_delta_y: 100,
+                anchor_delta_x: 0,
+                anchor_delta_y: 100,
+            })
+        );
+
+        let shape = morph.interpolate(0.5);
+        assert_eq!(shape.bounds.max, Position { x: 300, y: 150 });
+        assert_eq!(
+            shape.fill_styles[0],
+            FillStyle::Solid(Rgba {
+                rgb: Rgb {
+                    r: 127,
+                    g: 0,
+                    b: 128
+                },
+                a: 127,
+            })
+        );
+        assert_eq!(shape.line_styles[0].width, 40);
+        assert_eq!(shape.line_styles[0].join, JoinStyle::Miter(Fixed8(3 << 8)));
+        assert_eq!(
+            shape.shape.records[1..],
+            [
+                ShapeRecord::StraightEdge(StraightEdgeRecord {
+                    delta_x: 300,
+                    delta_y: 0,
+                }),
+                ShapeRecord::CurvedEdge(CurvedEdgeRecord {
+                    control_delta_x: 0,
+                    control_delta_y: 25,
+                    anchor_delta_x: 0,
+                    anchor_delta_y: 25,
+                }),
+            ]
+        );
+        match &shape.shape.records[0] {
+            ShapeRecord::StyleChange(style) => {
+                assert_eq!(style.move_to, Some(Position { x: 0, y: 50 }));
+                assert_eq!(style.fill_style0, Some(1));
+            }
+            _ => panic!("expected a style change record"),
+        }
+    }
+
+    #[test]
+    fn interpolate_without_end_move_to() {
+        let straight =
+            |delta_x, delta_y| ShapeRecord::StraightEdge(StraightEdgeRecord { delta_x, delta_y });
+        let move_to = |x, y| {
+            ShapeRecord::StyleChange(StyleChangeRecord {
+                move_to: Some(Position { x, y }),
+                ..Default::default()
+            })
+        };
+        let morph = DefineMorphShapeTag {
+            start_edges: Shape {
+                records: vec![move_to(0, 0), straight(200, 0), straight(0, 200)],
+            },
+            end_edges: Shape {
+                records: vec![straight(400, 0), move_to(400, 100), straight(0, 100)],
+            },
+            ..Default::default()
+        };
+        // The end edges start at the origin, and their move-to is kept
+        assert_eq!(
+            morph.interpolate(0.5).shape.records,
+            [
+                move_to(0, 0),
+                straight(300, 0),
+                move_to(300, 50),
+                straight(0, 150),
+            ]
+        );
+    }
+}
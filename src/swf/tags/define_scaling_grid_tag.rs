@@ -0,0 +1,47 @@
+use super::ITag;
+use crate::{error::Result, swf::datatypes::Rect, Movie, StreamReader, StreamWriter};
+
+/// 9-slice scaling grid of a sprite or button
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct DefineScalingGridTag {
+    pub character_id: u16,
+    /// Center region of the grid, the corners are not scaled
+    pub splitter: Rect,
+}
+
+impl ITag for DefineScalingGridTag {
+    fn read(stream: &mut StreamReader) -> Result<Self> {
+        Ok(Self {
+            character_id: stream.read_u16()?,
+            splitter: Rect::read(stream)?,
+        })
+    }
+
+    fn write(&self, stream: &mut StreamWriter, _movie: &Movie) -> Result<()> {
+        stream.write_u16(self.character_id)?;
+        self.splitter.write(stream)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::swf::datatypes::Position;
+
+    #[test]
+    fn round_trip() {
+        let tag = DefineScalingGridTag {
+            character_id: 4,
+            splitter: Rect {
+                min: Position { x: 200, y: -40 },
+                max: Position { x: 1800, y: 600 },
+            },
+        };
+
+        let mut stream = StreamWriter::default();
+        tag.write(&mut stream, &Movie::default()).unwrap();
+        let mut reader = StreamReader::new(stream.buffer());
+        assert_eq!(DefineScalingGridTag::read(&mut reader).unwrap(), tag);
+        assert!(reader.finished());
+    }
+}
@@ -0,0 +1,98 @@
+use super::ITag;
+use crate::{error::Result, Movie, StreamReader, StreamWriter};
+
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct Scene {
+    /// Index of the first frame of the scene
+    pub offset: u32,
+    pub name: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct FrameLabel {
+    /// Index of the labelled frame, starting at 0
+    pub frame: u32,
+    pub name: String,
+}
+
+/// Scenes and frame labels of the main timeline
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct DefineSceneAndFrameLabelDataTag {
+    pub scenes: Vec<Scene>,
+    pub labels: Vec<FrameLabel>,
+}
+
+impl ITag for DefineSceneAndFrameLabelDataTag {
+    // EncodedU32 values use the same encoding as u30
+    fn read(stream: &mut StreamReader) -> Result<Self> {
+        let count = stream.read_u30()?;
+        let mut scenes = Vec::with_capacity(count.min(0xFFFF) as usize);
+        for _ in 0..count {
+            scenes.push(Scene {
+                offset: stream.read_u30()?,
+                name: stream.read_null_string()?,
+            });
+        }
+
+        let count = stream.read_u30()?;
+        let mut labels = Vec::with_capacity(count.min(0xFFFF) as usize);
+        for _ in 0..count {
+            labels.push(FrameLabel {
+                frame: stream.read_u30()?,
+                name: stream.read_null_string()?,
+            });
+        }
+        Ok(Self { scenes, labels })
+    }
+
+    fn write(&self, stream: &mut StreamWriter, _movie: &Movie) -> Result<()> {
+        stream.write_u30(self.scenes.len() as u32)?;
+        for scene in &self.scenes {
+            stream.write_u30(scene.offset)?;
+            stream.write_null_string(&scene.name)?;
+        }
+        stream.write_u30(self.labels.len() as u32)?;
+        for label in &self.labels {
+            stream.write_u30(label.frame)?;
+            stream.write_null_string(&label.name)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let tag = DefineSceneAndFrameLabelDataTag {
+            scenes: vec![
+                Scene {
+                    offset: 0,
+                    name: "Scene 1".to_string(),
+                },
+                Scene {
+                    offset: 200,
+                    name: "Scene 2".to_string(),
+                },
+            ],
+            labels: vec![FrameLabel {
+                frame: 0x12345678,
+                name: "intro".to_string(),
+            }],
+        };
+
+        let mut stream = StreamWriter::default();
+        tag.write(&mut stream, &Movie::default()).unwrap();
+        // 200 is encoded on 2 bytes
+        assert_eq!(&stream.buffer()[..4], b"\x02\x00Sc");
+        assert_eq!(stream.buffer()[10..12], [0xC8, 0x01]);
+        let mut reader = StreamReader::new(stream.buffer());
+        assert_eq!(
+            DefineSceneAndFrameLabelDataTag::read(&mut reader).unwrap(),
+            tag
+        );
+        assert!(reader.finished());
+    }
+}
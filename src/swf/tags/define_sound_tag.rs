@@ -0,0 +1,38 @@
+use super::ITag;
+use crate::{error::Result, swf::datatypes::SoundFormat, Movie, StreamReader, StreamWriter};
+
+/// Event sound, played with StartSound or StartSound2 tags
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct DefineSoundTag {
+    pub sound_id: u16,
+    pub format: SoundFormat,
+    /// Number of samples, per channel
+    pub sample_count: u32,
+    /// Encoded samples. MP3 data starts with a 16-bit seek samples value
+    pub data: Vec<u8>,
+}
+
+impl ITag for DefineSoundTag {
+    fn read(stream: &mut StreamReader) -> Result<Self> {
+        let sound_id = stream.read_u16()?;
+        let format = SoundFormat::read(stream)?;
+        let sample_count = stream.read_u32()?;
+        let mut data = vec![0u8; stream.remaining() as usize];
+        stream.read_exact(&mut data)?;
+
+        Ok(Self {
+            sound_id,
+            format,
+            sample_count,
+            data,
+        })
+    }
+
+    fn write(&self, stream: &mut StreamWriter, _movie: &Movie) -> Result<()> {
+        stream.write_u16(self.sound_id)?;
+        self.format.write(stream)?;
+        stream.write_u32(self.sample_count)?;
+        stream.write_exact(&self.data)?;
+        Ok(())
+    }
+}
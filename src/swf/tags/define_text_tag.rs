@@ -0,0 +1,306 @@
+use super::ITag;
+use crate::{
+    error::{RabcError, Result},
+    stream::{BitStreamReader, BitStreamWriter},
+    swf::datatypes::{Matrix, Rect, Rgb, Rgba},
+    Movie, StreamReader, StreamWriter,
+};
+
+const HAS_FONT: u8 = 0x08;
+const HAS_COLOR: u8 = 0x04;
+const HAS_Y_OFFSET: u8 = 0x02;
+const HAS_X_OFFSET: u8 = 0x01;
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct GlyphEntry {
+    /// Index of the glyph in the font's glyph table
+    pub index: u32,
+    pub advance: i32,
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct TextRecord {
+    pub font_id: Option<u16>,
+    /// The alpha channel is only written in DefineText2 tags
+    pub color: Option<Rgba>,
+    pub x_offset: Option<i16>,
+    pub y_offset: Option<i16>,
+    /// Font height in twips, only written if the font id is set
+    pub height: u16,
+    pub glyphs: Vec<GlyphEntry>,
+}
+
+/// Static text, used by both DefineText and DefineText2.
+/// DefineText2 text records have colors with an alpha channel.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct DefineTextTag {
+    pub char_id: u16,
+    pub bounds: Rect,
+    pub matrix: Matrix,
+    pub records: Vec<TextRecord>,
+}
+
+impl DefineTextTag {
+    pub fn read_with_version(stream: &mut StreamReader, version: u8) -> Result<Self> {
+        let char_id = stream.read_u16()?;
+        let bounds = Rect::read(stream)?;
+        let matrix = Matrix::read(stream)?;
+        let glyph_bits = stream.read_u8()?;
+        let advance_bits = stream.read_u8()?;
+
+        let mut records = Vec::new();
+        loop {
+            let flags = stream.read_u8()?;
+            if flags == 0 {
+                // EndOfRecordsFlag
+                break;
+            }
+            records.push(TextRecord::read(
+                stream,
+                flags,
+                version,
+                glyph_bits,
+                advance_bits,
+            )?);
+        }
+
+        Ok(Self {
+            char_id,
+            bounds,
+            matrix,
+            records,
+        })
+    }
+
+    pub fn write_with_version(&self, stream: &mut StreamWriter, version: u8) -> Result<()> {
+        let glyphs = self.records.iter().flat_map(|r| &r.glyphs);
+        let glyph_bits = glyphs
+            .clone()
+            .map(|g| BitStreamWriter::min_ubits(g.index))
+            .max()
+            .unwrap_or(0);
+        let advance_bits = glyphs
+            .map(|g| BitStreamWriter::min_sbits(g.advance))
+            .max()
+            .unwrap_or(0);
+
+        stream.write_u16(self.char_id)?;
+        self.bounds.write(stream)?;
+        self.matrix.write(stream)?;
+        stream.write_u8(glyph_bits)?;
+        stream.write_u8(advance_bits)?;
+        for record in &self.records {
+            record.write(stream, version, glyph_bits, advance_bits)?;
+        }
+        stream.write_u8(0)
+    }
+
+    /// Decode the text using the character codes of the fonts defined in the movie.
+    /// Glyphs whose font or index can't be found are replaced with U+FFFD.
+    pub fn text(&self, movie: &Movie) -> String {
+        let mut text = String::new();
+        let mut font = None;
+        for record in &self.records {
+            if let Some(font_id) = record.font_id {
+                font = movie.font(font_id);
+            }
+            for glyph in &record.glyphs {
+                let c = font
+                    .and_then(|f| f.glyphs.get(glyph.index as usize))
+                    .and_then(|g| char::from_u32(g.code.into()));
+                text.push(c.unwrap_or(char::REPLACEMENT_CHARACTER));
+            }
+        }
+        text
+    }
+}
+
+impl TextRecord {
+    fn read(
+        stream: &mut StreamReader,
+        flags: u8,
+        version: u8,
+        glyph_bits: u8,
+        advance_bits: u8,
+    ) -> Result<Self> {
+        let font_id = if flags & HAS_FONT != 0 {
+            Some(stream.read_u16()?)
+        } else {
+            None
+        };
+        let color = if flags & HAS_COLOR == 0 {
+            None
+        } else if version >= 2 {
+            Some(Rgba::read(stream)?)
+        } else {
+            Some(Rgba {
+                rgb: Rgb::read(stream)?,
+                a: 0xFF,
+            })
+        };
+        let x_offset = if flags & HAS_X_OFFSET != 0 {
+            Some(stream.read_i16()?)
+        } else {
+            None
+        };
+        let y_offset = if flags & HAS_Y_OFFSET != 0 {
+            Some(stream.read_i16()?)
+        } else {
+            None
+        };
+        let height = if font_id.is_some() {
+            stream.read_u16()?
+        } else {
+            0
+        };
+
+        let count = stream.read_u8()?;
+        let mut glyphs = Vec::with_capacity(count as usize);
+        let mut bs = BitStreamReader::new(stream);
+        for _ in 0..count {
+            glyphs.push(GlyphEntry {
+                index: bs.read_ub(glyph_bits)?,
+                advance: bs.read_sb(advance_bits)?,
+            });
+        }
+
+        Ok(Self {
+            font_id,
+            color,
+            x_offset,
+            y_offset,
+            height,
+            glyphs,
+        })
+    }
+
+    fn write(
+        &self,
+        stream: &mut StreamWriter,
+        version: u8,
+        glyph_bits: u8,
+        advance_bits: u8,
+    ) -> Result<()> {
+        let mut flags = 0x80;
+        if self.font_id.is_some() {
+            flags |= HAS_FONT;
+        }
+        if self.color.is_some() {
+            flags |= HAS_COLOR;
+        }
+        if self.y_offset.is_some() {
+            flags |= HAS_Y_OFFSET;
+        }
+        if self.x_offset.is_some() {
+            flags |= HAS_X_OFFSET;
+        }
+
+        stream.write_u8(flags)?;
+        if let Some(font_id) = self.font_id {
+            stream.write_u16(font_id)?;
+        }
+        if let Some(color) = &self.color {
+            if version >= 2 {
+                color.write(stream)?;
+            } else {
+                color.rgb.write(stream)?;
+            }
+        }
+        if let Some(x_offset) = self.x_offset {
+            stream.write_i16(x_offset)?;
+        }
+        if let Some(y_offset) = self.y_offset {
+            stream.write_i16(y_offset)?;
+        }
+        if self.font_id.is_some() {
+            stream.write_u16(self.height)?;
+        }
+
+        let glyph_count = u8::try_from(self.glyphs.len())
+            .map_err(|_| RabcError::ValueOutOfRange("glyph count"))?;
+        stream.write_u8(glyph_count)?;
+        let mut bs = BitStreamWriter::new(stream);
+        for glyph in &self.glyphs {
+            bs.write_ub(glyph_bits, glyph.index)?;
+            bs.write_sb(advance_bits, glyph.advance)?;
+        }
+        bs.flush()
+    }
+}
+
+impl ITag for DefineTextTag {
+    fn read(stream: &mut StreamReader) -> Result<Self> {
+        Self::read_with_version(stream, 1)
+    }
+
+    fn write(&self, stream: &mut StreamWriter, _movie: &Movie) -> Result<()> {
+        self.write_with_version(stream, 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::swf::datatypes::Position;
+
+    #[test]
+    fn round_trip() {
+        let text = DefineTextTag {
+            char_id: 7,
+            bounds: Rect {
+                min: Position { x: -20, y: -300 },
+                max: Position { x: 2000, y: 80 },
+            },
+            matrix: Matrix {
+                translate_x: 400,
+                translate_y: -25,
+                ..Default::default()
+            },
+            records: vec![
+                TextRecord {
+                    font_id: Some(3),
+                    color: Some(Rgba {
+                        rgb: Rgb { r: 1, g: 2, b: 3 },
+                        a: 0x80,
+                    }),
+                    y_offset: Some(240),
+                    height: 240,
+                    glyphs: vec![
+                        GlyphEntry {
+                            index: 0,
+                            advance: 200,
+                        },
+                        GlyphEntry {
+                            index: 12,
+                            advance: -5,
+                        },
+                    ],
+                    ..Default::default()
+                },
+                TextRecord {
+                    x_offset: Some(20),
+                    glyphs: vec![GlyphEntry {
+                        index: 1,
+                        advance: 0,
+                    }],
+                    ..Default::default()
+                },
+            ],
+        };
+
+        let mut stream = StreamWriter::default();
+        text.write_with_version(&mut stream, 2).unwrap();
+        let mut reader = StreamReader::new(stream.buffer());
+        assert_eq!(
+            DefineTextTag::read_with_version(&mut reader, 2).unwrap(),
+            text
+        );
+        assert!(reader.finished());
+
+        // DefineText doesn't store the alpha channel
+        let mut stream = StreamWriter::default();
+        text.write(&mut stream, &Movie::default()).unwrap();
+        let read = DefineTextTag::read(&mut StreamReader::new(stream.buffer())).unwrap();
+        assert_eq!(read.records[0].color.as_ref().unwrap().a, 0xFF);
+    }
+}
@@ -0,0 +1,150 @@
+use super::ITag;
+use crate::{error::Result, Movie, StreamReader, StreamWriter};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum VideoCodec {
+    /// Sorenson H.263
+    #[default]
+    SorensonH263,
+    ScreenVideo,
+    Vp6,
+    /// VP6 with an alpha channel
+    Vp6Alpha,
+    ScreenVideoV2,
+    /// Codec not defined by the specification, kept as is
+    Unknown(u8),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum VideoDeblocking {
+    /// Use the deblocking setting of each video packet
+    #[default]
+    VideoPacket,
+    Off,
+    Level1,
+    Level2,
+    Level3,
+    Level4,
+    /// Value not defined by the specification, kept as is
+    Unknown(u8),
+}
+
+/// Video stream displayed on the timeline, its frames are stored in VideoFrame tags
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct DefineVideoStreamTag {
+    pub char_id: u16,
+    pub frame_count: u16,
+    pub width: u16,
+    pub height: u16,
+    pub deblocking: VideoDeblocking,
+    pub smoothing: bool,
+    pub codec: VideoCodec,
+}
+
+impl VideoCodec {
+    pub fn from_u8(value: u8) -> Self {
+        match value {
+            2 => Self::SorensonH263,
+            3 => Self::ScreenVideo,
+            4 => Self::Vp6,
+            5 => Self::Vp6Alpha,
+            6 => Self::ScreenVideoV2,
+            _ => Self::Unknown(value),
+        }
+    }
+
+    pub fn to_u8(&self) -> u8 {
+        match self {
+            Self::SorensonH263 => 2,
+            Self::ScreenVideo => 3,
+            Self::Vp6 => 4,
+            Self::Vp6Alpha => 5,
+            Self::ScreenVideoV2 => 6,
+            Self::Unknown(value) => *value,
+        }
+    }
+}
+
+impl VideoDeblocking {
+    pub fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::Off,
+            2 => Self::Level1,
+            3 => Self::Level2,
+            4 => Self::Level3,
+            5 => Self::Level4,
+            _ => Self::Unknown(value),
+        }
+    }
+
+    pub fn to_u8(&self) -> u8 {
+        match self {
+            Self::VideoPacket => 0,
+            Self::Off => 1,
+            Self::Level1 => 2,
+            Self::Level2 => 3,
+            Self::Level3 => 4,
+            Self::Level4 => 5,
+            Self::Unknown(value) => *value,
+        }
+    }
+}
+
+impl ITag for DefineVideoStreamTag {
+    fn read(stream: &mut StreamReader) -> Result<Self> {
+        let char_id = stream.read_u16()?;
+        let frame_count = stream.read_u16()?;
+        let width = stream.read_u16()?;
+        let height = stream.read_u16()?;
+        let flags = stream.read_u8()?;
+        let codec = VideoCodec::from_u8(stream.read_u8()?);
+
+        Ok(Self {
+            char_id,
+            frame_count,
+            width,
+            height,
+            deblocking: VideoDeblocking::from_u8((flags >> 1) & 0x07),
+            smoothing: flags & 0x01 != 0,
+            codec,
+        })
+    }
+
+    fn write(&self, stream: &mut StreamWriter, _movie: &Movie) -> Result<()> {
+        stream.write_u16(self.char_id)?;
+        stream.write_u16(self.frame_count)?;
+        stream.write_u16(self.width)?;
+        stream.write_u16(self.height)?;
+        stream.write_u8((self.deblocking.to_u8() << 1) | u8::from(self.smoothing))?;
+        stream.write_u8(self.codec.to_u8())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for (deblocking, codec) in [
+            (VideoDeblocking::Level2, VideoCodec::Vp6),
+            (VideoDeblocking::Unknown(7), VideoCodec::Unknown(9)),
+        ] {
+            let tag = DefineVideoStreamTag {
+                char_id: 3,
+                frame_count: 10,
+                width: 320,
+                height: 240,
+                deblocking,
+                smoothing: true,
+                codec,
+            };
+            let mut stream = StreamWriter::default();
+            tag.write(&mut stream, &Movie::default()).unwrap();
+            let mut reader = StreamReader::new(stream.buffer());
+            assert_eq!(DefineVideoStreamTag::read(&mut reader).unwrap(), tag);
+            assert!(reader.finished());
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use super::ITag;
+use crate::{
+    avm1::{self, Action},
+    error::Result,
+    Movie, StreamReader, StreamWriter,
+};
+
+/// Actions executed when the frame is displayed
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct DoActionTag {
+    /// AVM1 bytecode, ending with an End action
+    pub actions: Vec<u8>,
+}
+
+impl DoActionTag {
+    pub fn parse(&self) -> Result<Vec<Action>> {
+        avm1::parse_actions(&self.actions)
+    }
+
+    pub fn save_actions(&mut self, actions: &[Action]) -> Result<()> {
+        self.actions = avm1::write_actions(actions)?;
+        Ok(())
+    }
+
+    /// Save actions that may have been inserted or removed since they were parsed,
+    /// see [`avm1::write_relocated_actions`]
+    pub fn save_relocated_actions(&mut self, actions: &[Action]) -> Result<()> {
+        self.actions = avm1::write_relocated_actions(actions)?;
+        Ok(())
+    }
+}
+
+impl ITag for DoActionTag {
+    fn read(stream: &mut StreamReader) -> Result<Self> {
+        let mut actions = vec![0u8; stream.remaining() as usize];
+        stream.read_exact(actions.as_mut())?;
+        Ok(Self { actions })
+    }
+
+    fn write(&self, stream: &mut StreamWriter, _movie: &Movie) -> Result<()> {
+        stream.write_exact(&self.actions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::{args::GotoFrameArg, Op};
+
+    #[test]
+    fn round_trip() {
+        let mut tag = DoActionTag {
+            actions: vec![0x81, 0x02, 0x00, 0x03, 0x00, 0x06, 0x00], // gotoFrame 3, play, end
+        };
+        let mut stream = StreamWriter::default();
+        tag.write(&mut stream, &Movie::default()).unwrap();
+        let mut reader = StreamReader::new(stream.buffer());
+        assert_eq!(DoActionTag::read(&mut reader).unwrap(), tag);
+
+        let mut actions = tag.parse().unwrap();
+        assert_eq!(actions[0].op, Op::GotoFrame(GotoFrameArg { frame: 3 }));
+        actions.remove(1);
+        tag.save_actions(&actions).unwrap();
+        assert_eq!(tag.actions, [0x81, 0x02, 0x00, 0x03, 0x00, 0x00]);
+    }
+}
@@ -0,0 +1,71 @@
+use super::ITag;
+use crate::{
+    avm1::{self, Action},
+    error::Result,
+    Movie, StreamReader, StreamWriter,
+};
+
+/// Actions executed once, when the sprite is first initialized
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct DoInitActionTag {
+    pub sprite_id: u16,
+    /// AVM1 bytecode, ending with an End action
+    pub actions: Vec<u8>,
+}
+
+impl DoInitActionTag {
+    pub fn parse(&self) -> Result<Vec<Action>> {
+        avm1::parse_actions(&self.actions)
+    }
+
+    pub fn save_actions(&mut self, actions: &[Action]) -> Result<()> {
+        self.actions = avm1::write_actions(actions)?;
+        Ok(())
+    }
+
+    /// Save actions that may have been inserted or removed since they were parsed,
+    /// see [`avm1::write_relocated_actions`]
+    pub fn save_relocated_actions(&mut self, actions: &[Action]) -> Result<()> {
+        self.actions = avm1::write_relocated_actions(actions)?;
+        Ok(())
+    }
+}
+
+impl ITag for DoInitActionTag {
+    fn read(stream: &mut StreamReader) -> Result<Self> {
+        let sprite_id = stream.read_u16()?;
+        let mut actions = vec![0u8; stream.remaining() as usize];
+        stream.read_exact(actions.as_mut())?;
+        Ok(Self { sprite_id, actions })
+    }
+
+    fn write(&self, stream: &mut StreamWriter, _movie: &Movie) -> Result<()> {
+        stream.write_u16(self.sprite_id)?;
+        stream.write_exact(&self.actions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::Op;
+
+    #[test]
+    fn round_trip() {
+        let mut tag = DoInitActionTag {
+            sprite_id: 12,
+            actions: vec![0x07, 0x00], // stop, end
+        };
+        let mut stream = StreamWriter::default();
+        tag.write(&mut stream, &Movie::default()).unwrap();
+        assert_eq!(*stream.buffer(), [12, 0, 0x07, 0x00]);
+        let mut reader = StreamReader::new(stream.buffer());
+        assert_eq!(DoInitActionTag::read(&mut reader).unwrap(), tag);
+
+        let mut actions = tag.parse().unwrap();
+        assert_eq!(actions[0].op, Op::Stop());
+        actions.insert(0, actions[0].clone());
+        tag.save_relocated_actions(&actions).unwrap();
+        assert_eq!(tag.actions, [0x07, 0x07, 0x00]);
+    }
+}
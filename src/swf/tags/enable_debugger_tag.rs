@@ -0,0 +1,75 @@
+use super::ITag;
+use crate::{error::Result, swf::crypt, Movie, StreamReader, StreamWriter};
+
+/// Allows a debugger to attach to the movie, used by both EnableDebugger and EnableDebugger2.
+/// EnableDebugger2 is used by SWF 6 and later.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct EnableDebuggerTag {
+    /// MD5-crypt hash of the password, as `$1$salt$hash`
+    pub password_hash: String,
+}
+
+impl EnableDebuggerTag {
+    /// Enable the debugger with a password, hashed with a random salt
+    pub fn with_password(password: &str) -> Self {
+        let mut tag = Self::default();
+        tag.set_password(password);
+        tag
+    }
+
+    pub fn set_password(&mut self, password: &str) {
+        self.password_hash = crypt::md5_crypt(password, &crypt::generate_salt());
+    }
+
+    /// Check the password against the hash. Returns false if no password is set
+    pub fn check_password(&self, password: &str) -> bool {
+        crypt::md5_crypt_verify(password, &self.password_hash)
+    }
+
+    pub fn read_with_version(stream: &mut StreamReader, version: u8) -> Result<Self> {
+        if version >= 2 {
+            let _reserved = stream.read_u16()?;
+        }
+        Ok(Self {
+            password_hash: stream.read_null_string()?,
+        })
+    }
+
+    pub fn write_with_version(&self, stream: &mut StreamWriter, version: u8) -> Result<()> {
+        if version >= 2 {
+            stream.write_u16(0)?;
+        }
+        stream.write_null_string(&self.password_hash)
+    }
+}
+
+impl ITag for EnableDebuggerTag {
+    fn read(stream: &mut StreamReader) -> Result<Self> {
+        Self::read_with_version(stream, 1)
+    }
+
+    fn write(&self, stream: &mut StreamWriter, _movie: &Movie) -> Result<()> {
+        self.write_with_version(stream, 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn password() {
+        let tag = EnableDebuggerTag::with_password("debug");
+        assert!(tag.password_hash.starts_with("$1$"));
+        assert!(tag.check_password("debug"));
+        assert!(!tag.check_password("release"));
+        assert!(!EnableDebuggerTag::default().check_password(""));
+
+        let mut stream = StreamWriter::default();
+        tag.write_with_version(&mut stream, 2).unwrap();
+        let mut reader = StreamReader::new(stream.buffer());
+        let read = EnableDebuggerTag::read_with_version(&mut reader, 2).unwrap();
+        assert!(read.check_password("debug"));
+        assert!(reader.finished());
+    }
+}
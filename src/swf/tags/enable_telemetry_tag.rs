@@ -0,0 +1,81 @@
+use super::ITag;
+use crate::{error::Result, swf::crypt, Movie, StreamReader, StreamWriter};
+
+/// Allows advanced telemetry to be collected by a profiler
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct EnableTelemetryTag {
+    /// SHA-256 hash of the password
+    pub password_hash: Option<[u8; 32]>,
+}
+
+impl EnableTelemetryTag {
+    pub fn with_password(password: &str) -> Self {
+        Self {
+            password_hash: Some(crypt::sha256(password.as_bytes())),
+        }
+    }
+
+    pub fn set_password(&mut self, password: &str) {
+        self.password_hash = Some(crypt::sha256(password.as_bytes()));
+    }
+
+    /// Check the password against the hash. Returns false if no password is set
+    pub fn check_password(&self, password: &str) -> bool {
+        self.password_hash == Some(crypt::sha256(password.as_bytes()))
+    }
+}
+
+impl ITag for EnableTelemetryTag {
+    fn read(stream: &mut StreamReader) -> Result<Self> {
+        let _reserved = stream.read_u16()?;
+        let password_hash = if stream.finished() {
+            None
+        } else {
+            let mut hash = [0u8; 32];
+            stream.read_exact(&mut hash)?;
+            Some(hash)
+        };
+        Ok(Self { password_hash })
+    }
+
+    fn write(&self, stream: &mut StreamWriter, _movie: &Movie) -> Result<()> {
+        stream.write_u16(0)?;
+        if let Some(hash) = &self.password_hash {
+            stream.write_exact(hash)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn password() {
+        let tag = EnableTelemetryTag::with_password("profile");
+        assert!(tag.check_password("profile"));
+        assert!(!tag.check_password("release"));
+        assert!(!EnableTelemetryTag::default().check_password(""));
+
+        let mut stream = StreamWriter::default();
+        tag.write(&mut stream, &Movie::default()).unwrap();
+        assert_eq!(stream.len(), 2 + 32);
+        let mut reader = StreamReader::new(stream.buffer());
+        let read = EnableTelemetryTag::read(&mut reader).unwrap();
+        assert!(read.check_password("profile"));
+        assert!(reader.finished());
+
+        // Only the reserved field without a password
+        let mut stream = StreamWriter::default();
+        EnableTelemetryTag::default()
+            .write(&mut stream, &Movie::default())
+            .unwrap();
+        assert_eq!(*stream.buffer(), [0, 0]);
+        let mut reader = StreamReader::new(stream.buffer());
+        assert_eq!(
+            EnableTelemetryTag::read(&mut reader).unwrap(),
+            EnableTelemetryTag::default()
+        );
+    }
+}
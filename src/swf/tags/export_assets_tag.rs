@@ -0,0 +1,81 @@
+use super::ITag;
+use crate::{
+    error::{RabcError, Result},
+    Movie, StreamReader, StreamWriter,
+};
+
+/// A character exported or imported by name
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct Asset {
+    pub char_id: u16,
+    pub name: String,
+}
+
+/// Characters made available to other movies by name
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct ExportAssetsTag {
+    pub assets: Vec<Asset>,
+}
+
+impl Asset {
+    pub fn read_list(stream: &mut StreamReader) -> Result<Vec<Self>> {
+        let count = stream.read_u16()?;
+        let mut assets = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            assets.push(Self {
+                char_id: stream.read_u16()?,
+                name: stream.read_null_string()?,
+            });
+        }
+        Ok(assets)
+    }
+
+    pub fn write_list(assets: &[Self], stream: &mut StreamWriter) -> Result<()> {
+        let count =
+            u16::try_from(assets.len()).map_err(|_| RabcError::ValueOutOfRange("asset count"))?;
+        stream.write_u16(count)?;
+        for asset in assets {
+            stream.write_u16(asset.char_id)?;
+            stream.write_null_string(&asset.name)?;
+        }
+        Ok(())
+    }
+}
+
+impl ITag for ExportAssetsTag {
+    fn read(stream: &mut StreamReader) -> Result<Self> {
+        Ok(Self {
+            assets: Asset::read_list(stream)?,
+        })
+    }
+
+    fn write(&self, stream: &mut StreamWriter, _movie: &Movie) -> Result<()> {
+        Asset::write_list(&self.assets, stream)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut tag = ExportAssetsTag {
+            assets: vec![Asset {
+                char_id: 2,
+                name: "Button".to_string(),
+            }],
+        };
+        let mut stream = StreamWriter::default();
+        tag.write(&mut stream, &Movie::default()).unwrap();
+        let mut reader = StreamReader::new(stream.buffer());
+        assert_eq!(ExportAssetsTag::read(&mut reader).unwrap(), tag);
+        assert!(reader.finished());
+
+        tag.assets = vec![Asset::default(); 0x10000];
+        assert!(matches!(
+            tag.write(&mut StreamWriter::default(), &Movie::default()),
+            Err(RabcError::ValueOutOfRange("asset count"))
+        ));
+    }
+}
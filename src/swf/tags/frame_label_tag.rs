@@ -0,0 +1,26 @@
+use super::ITag;
+use crate::{error::Result, Movie, StreamReader, StreamWriter};
+
+/// Label of the current frame
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct FrameLabelTag {
+    pub name: String,
+    /// Named anchor, which can be targeted from the browser's URL (SWF 6 and later)
+    pub is_anchor: bool,
+}
+
+impl ITag for FrameLabelTag {
+    fn read(stream: &mut StreamReader) -> Result<Self> {
+        let name = stream.read_null_string()?;
+        let is_anchor = !stream.finished() && stream.read_u8()? == 1;
+        Ok(Self { name, is_anchor })
+    }
+
+    fn write(&self, stream: &mut StreamWriter, _movie: &Movie) -> Result<()> {
+        stream.write_null_string(&self.name)?;
+        if self.is_anchor {
+            stream.write_u8(1)?;
+        }
+        Ok(())
+    }
+}
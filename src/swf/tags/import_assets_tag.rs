@@ -0,0 +1,77 @@
+use super::{Asset, ITag};
+use crate::{error::Result, Movie, StreamReader, StreamWriter};
+
+/// Characters imported by name from the movie at `url`, used by both ImportAssets and
+/// ImportAssets2. The character ids are the ids used by the importing movie.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct ImportAssetsTag {
+    pub url: String,
+    pub assets: Vec<Asset>,
+}
+
+impl ImportAssetsTag {
+    pub fn read_with_version(stream: &mut StreamReader, version: u8) -> Result<Self> {
+        let url = stream.read_null_string()?;
+        if version >= 2 {
+            let _reserved = stream.read_u8()?;
+            let _reserved = stream.read_u8()?;
+        }
+        Ok(Self {
+            url,
+            assets: Asset::read_list(stream)?,
+        })
+    }
+
+    pub fn write_with_version(&self, stream: &mut StreamWriter, version: u8) -> Result<()> {
+        stream.write_null_string(&self.url)?;
+        if version >= 2 {
+            // The first reserved byte must be 1
+            stream.write_u8(1)?;
+            stream.write_u8(0)?;
+        }
+        Asset::write_list(&self.assets, stream)
+    }
+}
+
+impl ITag for ImportAssetsTag {
+    fn read(stream: &mut StreamReader) -> Result<Self> {
+        Self::read_with_version(stream, 1)
+    }
+
+    fn write(&self, stream: &mut StreamWriter, _movie: &Movie) -> Result<()> {
+        self.write_with_version(stream, 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let tag = ImportAssetsTag {
+            url: "library.swf".to_string(),
+            assets: vec![
+                Asset {
+                    char_id: 3,
+                    name: "Button".to_string(),
+                },
+                Asset {
+                    char_id: 12,
+                    name: "Font".to_string(),
+                },
+            ],
+        };
+
+        for version in [1, 2] {
+            let mut stream = StreamWriter::default();
+            tag.write_with_version(&mut stream, version).unwrap();
+            let mut reader = StreamReader::new(stream.buffer());
+            assert_eq!(
+                ImportAssetsTag::read_with_version(&mut reader, version).unwrap(),
+                tag
+            );
+            assert!(reader.finished());
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use super::ITag;
+use crate::{error::Result, swf::crypt, Movie, StreamReader, StreamWriter};
+
+/// Prevents the movie from being imported in an authoring tool
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct ProtectTag {
+    /// MD5-crypt hash of the password, as `$1$salt$hash`
+    pub password_hash: Option<String>,
+}
+
+impl ProtectTag {
+    /// Protect the movie with a password, hashed with a random salt
+    pub fn with_password(password: &str) -> Self {
+        let mut tag = Self::default();
+        tag.set_password(password);
+        tag
+    }
+
+    pub fn set_password(&mut self, password: &str) {
+        self.password_hash = Some(crypt::md5_crypt(password, &crypt::generate_salt()));
+    }
+
+    /// Check the password against the hash. Returns false if no password is set
+    pub fn check_password(&self, password: &str) -> bool {
+        self.password_hash
+            .as_ref()
+            .is_some_and(|hash| crypt::md5_crypt_verify(password, hash))
+    }
+}
+
+impl ITag for ProtectTag {
+    fn read(stream: &mut StreamReader) -> Result<Self> {
+        let password_hash = if stream.finished() {
+            None
+        } else {
+            let _reserved = stream.read_u16()?;
+            Some(stream.read_null_string()?)
+        };
+        Ok(Self { password_hash })
+    }
+
+    fn write(&self, stream: &mut StreamWriter, _movie: &Movie) -> Result<()> {
+        if let Some(password_hash) = &self.password_hash {
+            stream.write_u16(0)?;
+            stream.write_null_string(password_hash)?;
+        }
+        Ok(())
+    }
+}
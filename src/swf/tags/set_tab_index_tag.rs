@@ -0,0 +1,43 @@
+use super::ITag;
+use crate::{error::Result, Movie, StreamReader, StreamWriter};
+
+/// Tab order of the object placed at `depth`
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct SetTabIndexTag {
+    pub depth: u16,
+    pub tab_index: u16,
+}
+
+impl ITag for SetTabIndexTag {
+    fn read(stream: &mut StreamReader) -> Result<Self> {
+        Ok(Self {
+            depth: stream.read_u16()?,
+            tab_index: stream.read_u16()?,
+        })
+    }
+
+    fn write(&self, stream: &mut StreamWriter, _movie: &Movie) -> Result<()> {
+        stream.write_u16(self.depth)?;
+        stream.write_u16(self.tab_index)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let tag = SetTabIndexTag {
+            depth: 3,
+            tab_index: 0x102,
+        };
+
+        let mut stream = StreamWriter::default();
+        tag.write(&mut stream, &Movie::default()).unwrap();
+        assert_eq!(*stream.buffer(), [0x03, 0x00, 0x02, 0x01]);
+        let mut reader = StreamReader::new(stream.buffer());
+        assert_eq!(SetTabIndexTag::read(&mut reader).unwrap(), tag);
+    }
+}
@@ -0,0 +1,15 @@
+use super::ITag;
+use crate::{error::Result, Movie, StreamReader, StreamWriter};
+
+/// Marks the end of a frame
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShowFrameTag {}
+
+impl ITag for ShowFrameTag {
+    fn read(_stream: &mut StreamReader) -> Result<Self> {
+        Ok(Self {})
+    }
+    fn write(&self, _stream: &mut StreamWriter, _movie: &Movie) -> Result<()> {
+        Ok(())
+    }
+}
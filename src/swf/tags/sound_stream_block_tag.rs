@@ -0,0 +1,117 @@
+use super::{ITag, SoundStreamHeadTag, Tag};
+use crate::{error::Result, swf::datatypes::AudioCompression, Movie, StreamReader, StreamWriter};
+
+/// Samples of the streaming sound for a single frame.
+/// MP3 blocks start with a 16-bit sample count and a 16-bit seek samples value
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct SoundStreamBlockTag {
+    pub data: Vec<u8>,
+}
+
+/// Streaming sound of a timeline, reassembled from its SoundStreamBlock tags
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct SoundStream {
+    pub head: SoundStreamHeadTag,
+    /// Samples of each block, without the MP3 block header
+    pub blocks: Vec<Vec<u8>>,
+    /// Total number of samples, per channel
+    pub sample_count: u32,
+}
+
+impl SoundStream {
+    /// Reassemble the first streaming sound of a timeline.
+    /// The stream ends at the end of the timeline or when another sound stream starts.
+    pub fn from_tags<'a>(tags: impl IntoIterator<Item = &'a Tag>) -> Option<Self> {
+        let mut sound: Option<Self> = None;
+        for tag in tags {
+            match tag {
+                Tag::SoundStreamHead(head) | Tag::SoundStreamHead2(head) => {
+                    if sound.is_some() {
+                        break;
+                    }
+                    sound = Some(Self {
+                        head: head.clone(),
+                        ..Default::default()
+                    });
+                }
+                Tag::SoundStreamBlock(block) => {
+                    if let Some(sound) = &mut sound {
+                        sound.push_block(&block.data);
+                    }
+                }
+                _ => {}
+            }
+        }
+        sound
+    }
+
+    fn push_block(&mut self, data: &[u8]) {
+        if self.head.stream_format.compression != AudioCompression::Mp3 {
+            self.sample_count += u32::from(self.head.sample_count);
+            self.blocks.push(data.to_vec());
+            return;
+        }
+        if data.len() < 4 {
+            return;
+        }
+        self.sample_count += u32::from(u16::from_le_bytes([data[0], data[1]]));
+        self.blocks.push(data[4..].to_vec());
+    }
+
+    /// Samples of all the blocks
+    pub fn data(&self) -> Vec<u8> {
+        self.blocks.concat()
+    }
+}
+
+impl ITag for SoundStreamBlockTag {
+    fn read(stream: &mut StreamReader) -> Result<Self> {
+        let mut data = vec![0u8; stream.remaining() as usize];
+        stream.read_exact(&mut data)?;
+        Ok(Self { data })
+    }
+
+    fn write(&self, stream: &mut StreamWriter, _movie: &Movie) -> Result<()> {
+        stream.write_exact(&self.data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::swf::datatypes::SoundFormat;
+
+    #[test]
+    fn reassemble_mp3_stream() {
+        let head = SoundStreamHeadTag {
+            stream_format: SoundFormat {
+                compression: AudioCompression::Mp3,
+                is_16bit: true,
+                ..Default::default()
+            },
+            sample_count: 576,
+            ..Default::default()
+        };
+        let tags = vec![
+            Tag::SoundStreamHead2(head.clone()),
+            Tag::SoundStreamBlock(SoundStreamBlockTag {
+                data: vec![0x40, 0x02, 0, 0, 0xFF, 0xFB],
+            }),
+            Tag::SoundStreamBlock(SoundStreamBlockTag {
+                data: vec![0x00, 0x00, 0x10, 0],
+            }),
+            Tag::SoundStreamBlock(SoundStreamBlockTag {
+                data: vec![0x40, 0x02, 0, 0, 0x90],
+            }),
+            Tag::SoundStreamHead(head),
+            Tag::SoundStreamBlock(SoundStreamBlockTag {
+                data: vec![0x40, 0x02, 0, 0, 0x55],
+            }),
+        ];
+
+        let sound = SoundStream::from_tags(&tags).unwrap();
+        assert_eq!(sound.sample_count, 1152);
+        assert_eq!(sound.blocks.len(), 3);
+        assert_eq!(sound.data(), vec![0xFF, 0xFB, 0x90]);
+    }
+}
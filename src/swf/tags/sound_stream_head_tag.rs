@@ -0,0 +1,72 @@
+use super::ITag;
+use crate::{
+    error::Result,
+    swf::datatypes::{AudioCompression, SoundFormat},
+    Movie, StreamReader, StreamWriter,
+};
+
+/// Format of the streaming sound of a timeline, used by both SoundStreamHead and SoundStreamHead2.
+/// The samples are stored in the following SoundStreamBlock tags, one per frame.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct SoundStreamHeadTag {
+    /// Advisory playback format
+    pub playback_format: SoundFormat,
+    pub stream_format: SoundFormat,
+    /// Average number of samples per SoundStreamBlock
+    pub sample_count: u16,
+    /// Number of samples to skip, only written for MP3 streams.
+    /// Some encoders omit it, so it is optional.
+    pub latency_seek: Option<i16>,
+}
+
+impl ITag for SoundStreamHeadTag {
+    fn read(stream: &mut StreamReader) -> Result<Self> {
+        // The compression of the playback format is reserved and always 0
+        let playback_format = SoundFormat::from_u8(stream.read_u8()? & 0x0F);
+        let stream_format = SoundFormat::read(stream)?;
+        let sample_count = stream.read_u16()?;
+        let latency_seek =
+            if stream_format.compression == AudioCompression::Mp3 && !stream.finished() {
+                Some(stream.read_i16()?)
+            } else {
+                None
+            };
+
+        Ok(Self {
+            playback_format,
+            stream_format,
+            sample_count,
+            latency_seek,
+        })
+    }
+
+    fn write(&self, stream: &mut StreamWriter, _movie: &Movie) -> Result<()> {
+        stream.write_u8(self.playback_format.to_u8() & 0x0F)?;
+        self.stream_format.write(stream)?;
+        stream.write_u16(self.sample_count)?;
+        if let Some(latency_seek) = self.latency_seek {
+            stream.write_i16(latency_seek)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn optional_latency_seek() {
+        for data in [
+            &[0x0E, 0x2E, 0x40, 0x02][..],
+            &[0x0E, 0x2E, 0x40, 0x02, 0xFF, 0xFF],
+        ] {
+            let head = SoundStreamHeadTag::read(&mut StreamReader::new(data)).unwrap();
+            assert_eq!(head.stream_format.compression, AudioCompression::Mp3);
+            assert_eq!(head.latency_seek, (data.len() == 6).then_some(-1));
+            let mut stream = StreamWriter::default();
+            head.write(&mut stream, &Movie::default()).unwrap();
+            assert_eq!(stream.buffer(), data);
+        }
+    }
+}
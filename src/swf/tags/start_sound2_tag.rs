@@ -0,0 +1,46 @@
+use super::ITag;
+use crate::{error::Result, swf::datatypes::SoundInfo, Movie, StreamReader, StreamWriter};
+
+/// Start or stop an event sound from its class name
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct StartSound2Tag {
+    pub class_name: String,
+    pub info: SoundInfo,
+}
+
+impl ITag for StartSound2Tag {
+    fn read(stream: &mut StreamReader) -> Result<Self> {
+        Ok(Self {
+            class_name: stream.read_null_string()?,
+            info: SoundInfo::read(stream)?,
+        })
+    }
+
+    fn write(&self, stream: &mut StreamWriter, _movie: &Movie) -> Result<()> {
+        stream.write_null_string(&self.class_name)?;
+        self.info.write(stream)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let tag = StartSound2Tag {
+            class_name: "sounds.Click".to_string(),
+            info: SoundInfo {
+                sync_stop: true,
+                ..Default::default()
+            },
+        };
+        let mut stream = StreamWriter::default();
+        tag.write(&mut stream, &Movie::default()).unwrap();
+        // Only the flags follow the class name
+        assert_eq!(stream.buffer().len(), 13 + 1);
+        let mut reader = StreamReader::new(stream.buffer());
+        assert_eq!(StartSound2Tag::read(&mut reader).unwrap(), tag);
+        assert!(reader.finished());
+    }
+}
@@ -0,0 +1,59 @@
+use super::ITag;
+use crate::{error::Result, swf::datatypes::SoundInfo, Movie, StreamReader, StreamWriter};
+
+/// Start or stop an event sound defined by a DefineSound tag
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct StartSoundTag {
+    pub sound_id: u16,
+    pub info: SoundInfo,
+}
+
+impl ITag for StartSoundTag {
+    fn read(stream: &mut StreamReader) -> Result<Self> {
+        Ok(Self {
+            sound_id: stream.read_u16()?,
+            info: SoundInfo::read(stream)?,
+        })
+    }
+
+    fn write(&self, stream: &mut StreamWriter, _movie: &Movie) -> Result<()> {
+        stream.write_u16(self.sound_id)?;
+        self.info.write(stream)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::RabcError, swf::datatypes::SoundEnvelope};
+
+    #[test]
+    fn round_trip() {
+        let mut tag = StartSoundTag {
+            sound_id: 3,
+            info: SoundInfo {
+                sync_no_multiple: true,
+                in_point: Some(100),
+                out_point: Some(44100),
+                loop_count: Some(2),
+                envelope: Some(vec![SoundEnvelope {
+                    pos44: 0,
+                    left_level: 32768,
+                    right_level: 0,
+                }]),
+                ..Default::default()
+            },
+        };
+        let mut stream = StreamWriter::default();
+        tag.write(&mut stream, &Movie::default()).unwrap();
+        let mut reader = StreamReader::new(stream.buffer());
+        assert_eq!(StartSoundTag::read(&mut reader).unwrap(), tag);
+        assert!(reader.finished());
+
+        tag.info.envelope = Some(vec![SoundEnvelope::default(); 0x100]);
+        assert!(matches!(
+            tag.write(&mut StreamWriter::default(), &Movie::default()),
+            Err(RabcError::ValueOutOfRange("envelope point count"))
+        ));
+    }
+}
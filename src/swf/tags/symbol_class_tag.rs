@@ -1,29 +1,46 @@
-use std::collections::HashMap;
-
-use super::ITag;
+use super::{Asset, ITag};
 use crate::{error::Result, Movie, StreamReader, StreamWriter};
 
-#[derive(Clone, Debug, PartialEq)]
+/// Link characters to ActionScript classes, the character 0 being the main timeline.
+/// The symbols are kept in their original order, with duplicates.
+/// Use the methods of [`Movie`] to handle the symbols of every SymbolClass tag.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
 pub struct SymbolClassTag {
-    /// ignored when writing. Use Movie.symbols instead
-    pub symbols: HashMap<u16, String>,
+    pub symbols: Vec<Asset>,
 }
 
 impl ITag for SymbolClassTag {
     fn read(stream: &mut StreamReader) -> Result<Self> {
-        let mut symbols: HashMap<u16, String> = HashMap::new();
-        for _ in 0..stream.read_u16()? {
-            symbols.insert(stream.read_u16()?, stream.read_null_string()?);
-        }
-        Ok(Self { symbols })
+        Ok(Self {
+            symbols: Asset::read_list(stream)?,
+        })
+    }
+
+    fn write(&self, stream: &mut StreamWriter, _movie: &Movie) -> Result<()> {
+        Asset::write_list(&self.symbols, stream)
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_keeps_order_and_duplicates() {
+        let tag = SymbolClassTag {
+            symbols: [(2, "B"), (1, "A"), (2, "B2"), (0, "Main")]
+                .map(|(char_id, name)| Asset {
+                    char_id,
+                    name: name.to_string(),
+                })
+                .to_vec(),
+        };
 
-    fn write(&self, stream: &mut StreamWriter, movie: &Movie) -> Result<()> {
-        stream.write_u16(self.symbols.len() as u16)?;
-        for (id, name) in &movie.symbols {
-            stream.write_u16(*id)?;
-            stream.write_null_string(name)?;
-        }
-        Ok(())
+        let mut stream = StreamWriter::default();
+        tag.write(&mut stream, &Movie::default()).unwrap();
+        assert_eq!(&stream.buffer()[..5], [4, 0, 2, 0, b'B']);
+        let mut reader = StreamReader::new(stream.buffer());
+        assert_eq!(SymbolClassTag::read(&mut reader).unwrap(), tag);
+        assert!(reader.finished());
     }
 }
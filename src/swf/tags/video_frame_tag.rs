@@ -0,0 +1,34 @@
+use super::ITag;
+use crate::{error::Result, Movie, StreamReader, StreamWriter};
+
+/// A single frame of a video stream defined by a DefineVideoStream tag
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct VideoFrameTag {
+    pub stream_id: u16,
+    /// Index of the frame in the video stream
+    pub frame_num: u16,
+    /// Video packet, encoded with the codec of the video stream
+    pub data: Vec<u8>,
+}
+
+impl ITag for VideoFrameTag {
+    fn read(stream: &mut StreamReader) -> Result<Self> {
+        let stream_id = stream.read_u16()?;
+        let frame_num = stream.read_u16()?;
+        let mut data = vec![0u8; stream.remaining() as usize];
+        stream.read_exact(&mut data)?;
+
+        Ok(Self {
+            stream_id,
+            frame_num,
+            data,
+        })
+    }
+
+    fn write(&self, stream: &mut StreamWriter, _movie: &Movie) -> Result<()> {
+        stream.write_u16(self.stream_id)?;
+        stream.write_u16(self.frame_num)?;
+        stream.write_exact(&self.data)?;
+        Ok(())
+    }
+}